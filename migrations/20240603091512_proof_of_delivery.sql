@@ -0,0 +1,27 @@
+-- Proof of delivery captured by the driver when an in-transit order is handed over.
+CREATE TABLE proof_of_deliveries (
+    id SERIAL PRIMARY KEY NOT NULL,
+    order_id INT NOT NULL UNIQUE,
+    recipient_name VARCHAR(255) NOT NULL,
+    delivered_at TIMESTAMPTZ NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    notes TEXT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (order_id) REFERENCES orders(id)
+);
+CREATE TRIGGER update_proof_of_delivery_modtime BEFORE UPDATE ON proof_of_deliveries FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE TABLE proof_of_delivery_files (
+    proof_of_delivery_id INT NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('signature', 'photo')),
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    content BYTEA NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    PRIMARY KEY (proof_of_delivery_id, kind),
+    FOREIGN KEY (proof_of_delivery_id) REFERENCES proof_of_deliveries(id)
+);
+CREATE TRIGGER update_proof_of_delivery_file_modtime BEFORE UPDATE ON proof_of_delivery_files FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
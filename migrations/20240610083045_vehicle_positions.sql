@@ -0,0 +1,65 @@
+-- High-volume GPS telemetry reported by vehicles, partitioned by day so old data can be dropped cheaply.
+CREATE TABLE vehicle_positions (
+    vehicle_id INT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    speed REAL NULL,
+    heading REAL NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (vehicle_id, recorded_at),
+    FOREIGN KEY (vehicle_id) REFERENCES vehicles(id)
+) PARTITION BY RANGE (recorded_at);
+
+-- Latest fix per vehicle, kept up to date on ingestion so the fleet map never scans the history.
+CREATE TABLE vehicle_last_positions (
+    vehicle_id INT PRIMARY KEY NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    speed REAL NULL,
+    heading REAL NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (vehicle_id) REFERENCES vehicles(id)
+);
+CREATE TRIGGER update_vehicle_last_position_modtime BEFORE UPDATE ON vehicle_last_positions FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+-- Creates the daily partitions for the retention window plus a few days ahead, and drops the expired ones.
+CREATE OR REPLACE FUNCTION maintain_vehicle_position_partitions(retention_days INT, premake_days INT)
+RETURNS VOID AS $$
+DECLARE
+    today DATE := (now() AT TIME ZONE 'UTC')::date;
+    day DATE;
+    partition_name TEXT;
+    partition RECORD;
+BEGIN
+    FOR day IN
+        SELECT generate_series(today - retention_days, today + premake_days, interval '1 day')::date
+    LOOP
+        partition_name := 'vehicle_positions_' || to_char(day, 'YYYYMMDD');
+
+        IF to_regclass(partition_name) IS NULL THEN
+            EXECUTE format(
+                'CREATE TABLE %I PARTITION OF vehicle_positions FOR VALUES FROM (%L) TO (%L)',
+                partition_name,
+                day::timestamp AT TIME ZONE 'UTC',
+                (day + 1)::timestamp AT TIME ZONE 'UTC'
+            );
+        END IF;
+    END LOOP;
+
+    FOR partition IN
+        SELECT c.relname
+        FROM pg_inherits i
+        JOIN pg_class c ON c.oid = i.inhrelid
+        JOIN pg_class p ON p.oid = i.inhparent
+        WHERE p.relname = 'vehicle_positions'
+          AND c.relname < 'vehicle_positions_' || to_char(today - retention_days, 'YYYYMMDD')
+    LOOP
+        EXECUTE format('DROP TABLE %I', partition.relname);
+    END LOOP;
+END;
+$$ language 'plpgsql';
+
+SELECT maintain_vehicle_position_partitions(30, 2);
@@ -0,0 +1,24 @@
+-- Route plans proposed by the planner. Drafts only become routes and vehicle_routes once accepted.
+CREATE TABLE route_plans (
+    id SERIAL PRIMARY KEY NOT NULL,
+    plan_status VARCHAR(20) NOT NULL,
+    depot_latitude DOUBLE PRECISION NOT NULL,
+    depot_longitude DOUBLE PRECISION NOT NULL,
+    departure_at TIMESTAMPTZ NOT NULL,
+    plan JSONB NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL
+);
+CREATE TRIGGER update_route_plan_modtime BEFORE UPDATE ON route_plans FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+-- Main routes created when a plan is accepted, so the draft can be traced to what was dispatched.
+CREATE TABLE route_plan_routes (
+    route_plan_id INT NOT NULL,
+    route_id INT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    PRIMARY KEY (route_plan_id, route_id),
+    FOREIGN KEY (route_plan_id) REFERENCES route_plans(id),
+    FOREIGN KEY (route_id) REFERENCES routes(id)
+);
+CREATE TRIGGER update_route_plan_route_modtime BEFORE UPDATE ON route_plan_routes FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
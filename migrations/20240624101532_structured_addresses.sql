@@ -0,0 +1,145 @@
+-- Splits the free-text address of customers and vendors into structured columns.
+
+-- Best-effort parser for the legacy "line, [line,] city REGION postcode[, country]" text.
+-- Anything it cannot recognise is kept in line 1 with the unknown country code 'ZZ' for review.
+CREATE FUNCTION parse_legacy_address(
+    address TEXT,
+    OUT line1 TEXT,
+    OUT line2 TEXT,
+    OUT city TEXT,
+    OUT region TEXT,
+    OUT postcode TEXT,
+    OUT country_code TEXT
+) AS $$
+DECLARE
+    parts TEXT[];
+    part_count INT;
+    locality TEXT;
+    matched TEXT[];
+BEGIN
+    parts := array_remove(ARRAY(SELECT btrim(p) FROM unnest(string_to_array(address, ',')) p), '');
+    part_count := coalesce(array_length(parts, 1), 0);
+    country_code := 'ZZ';
+    city := '';
+    postcode := '';
+
+    IF part_count > 1 THEN
+        country_code := CASE upper(parts[part_count])
+            WHEN 'AUSTRALIA' THEN 'AU' WHEN 'AU' THEN 'AU'
+            WHEN 'NEW ZEALAND' THEN 'NZ' WHEN 'NZ' THEN 'NZ'
+            WHEN 'UNITED STATES' THEN 'US' WHEN 'USA' THEN 'US' WHEN 'US' THEN 'US'
+            WHEN 'CANADA' THEN 'CA'
+            WHEN 'UNITED KINGDOM' THEN 'GB' WHEN 'UK' THEN 'GB' WHEN 'GB' THEN 'GB'
+            WHEN 'GERMANY' THEN 'DE' WHEN 'FRANCE' THEN 'FR' WHEN 'ITALY' THEN 'IT'
+            ELSE 'ZZ'
+        END;
+
+        IF country_code <> 'ZZ' THEN
+            part_count := part_count - 1;
+        END IF;
+    END IF;
+
+    IF part_count = 0 THEN
+        line1 := coalesce(btrim(address), '');
+        RETURN;
+    END IF;
+
+    line1 := parts[1];
+
+    IF part_count = 1 THEN
+        RETURN;
+    END IF;
+
+    locality := parts[part_count];
+
+    matched := regexp_match(locality, '^(.*?)\s*\m([A-Za-z]{2,3})\s+(\d{4,5}(-\d{4})?)$');
+    IF country_code = 'GB' THEN
+        matched := regexp_match(upper(locality), '^(.*?)\s*([A-Z]{1,2}\d[A-Z\d]?\s*\d[A-Z]{2})$');
+        IF matched IS NOT NULL THEN
+            city := substr(locality, 1, length(matched[1]));
+            postcode := matched[2];
+        ELSE
+            city := locality;
+        END IF;
+    ELSIF matched IS NOT NULL THEN
+        city := matched[1];
+        region := upper(matched[2]);
+        postcode := matched[3];
+    ELSE
+        matched := regexp_match(locality, '^(.*?)\s*(\d{4,5}(-\d{4})?)$');
+        IF matched IS NOT NULL THEN
+            city := matched[1];
+            postcode := matched[2];
+        ELSE
+            city := locality;
+        END IF;
+    END IF;
+
+    -- "12 Main St, Springfield, IL 62704": the city sits in its own part before the region.
+    IF city = '' AND part_count > 2 THEN
+        city := parts[part_count - 1];
+        part_count := part_count - 1;
+    END IF;
+
+    IF part_count > 2 THEN
+        line2 := array_to_string(parts[2:part_count - 1], ', ');
+    END IF;
+
+    IF country_code = 'ZZ' AND region IN ('ACT', 'NSW', 'NT', 'QLD', 'SA', 'TAS', 'VIC', 'WA')
+        AND postcode ~ '^\d{4}$' THEN
+        country_code := 'AU';
+    ELSIF country_code = 'ZZ' AND region IS NOT NULL AND postcode ~ '^\d{5}(-\d{4})?$' THEN
+        country_code := 'US';
+    END IF;
+END;
+$$ language 'plpgsql';
+
+ALTER TABLE customers RENAME COLUMN address TO legacy_address;
+ALTER TABLE customers
+    ALTER COLUMN legacy_address DROP NOT NULL,
+    ADD COLUMN address_line1 VARCHAR(255) NULL,
+    ADD COLUMN address_line2 VARCHAR(255) NULL,
+    ADD COLUMN city VARCHAR(100) NULL,
+    ADD COLUMN region VARCHAR(100) NULL,
+    ADD COLUMN postcode VARCHAR(20) NULL,
+    ADD COLUMN country_code VARCHAR(2) NULL,
+    ADD COLUMN latitude DOUBLE PRECISION NULL,
+    ADD COLUMN longitude DOUBLE PRECISION NULL;
+
+UPDATE customers
+SET (address_line1, address_line2, city, region, postcode, country_code) = (
+    SELECT p.line1, p.line2, p.city, p.region, p.postcode, p.country_code
+    FROM parse_legacy_address(legacy_address) p
+);
+
+ALTER TABLE customers
+    ALTER COLUMN address_line1 SET NOT NULL,
+    ALTER COLUMN city SET NOT NULL,
+    ALTER COLUMN postcode SET NOT NULL,
+    ALTER COLUMN country_code SET NOT NULL;
+
+ALTER TABLE vendors RENAME COLUMN address TO legacy_address;
+ALTER TABLE vendors
+    ALTER COLUMN legacy_address DROP NOT NULL,
+    ADD COLUMN address_line1 VARCHAR(255) NULL,
+    ADD COLUMN address_line2 VARCHAR(255) NULL,
+    ADD COLUMN city VARCHAR(100) NULL,
+    ADD COLUMN region VARCHAR(100) NULL,
+    ADD COLUMN postcode VARCHAR(20) NULL,
+    ADD COLUMN country_code VARCHAR(2) NULL,
+    ADD COLUMN latitude DOUBLE PRECISION NULL,
+    ADD COLUMN longitude DOUBLE PRECISION NULL;
+
+UPDATE vendors
+SET (address_line1, address_line2, city, region, postcode, country_code) = (
+    SELECT p.line1, p.line2, p.city, p.region, p.postcode, p.country_code
+    FROM parse_legacy_address(legacy_address) p
+);
+
+ALTER TABLE vendors
+    ALTER COLUMN address_line1 SET NOT NULL,
+    ALTER COLUMN city SET NOT NULL,
+    ALTER COLUMN postcode SET NOT NULL,
+    ALTER COLUMN country_code SET NOT NULL;
+
+DROP FUNCTION parse_legacy_address(TEXT);
@@ -0,0 +1,16 @@
+-- Geocoding results keyed by the normalised address, misses are kept too (without coordinates)
+-- so unknown addresses are not sent to the provider on every save.
+CREATE TABLE geocode_cache (
+    address_key VARCHAR(800) PRIMARY KEY NOT NULL,
+    provider VARCHAR(50) NULL,
+    latitude DOUBLE PRECISION NULL,
+    longitude DOUBLE PRECISION NULL,
+    geocoded_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL
+);
+CREATE TRIGGER update_geocode_cache_modtime BEFORE UPDATE ON geocode_cache FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+-- Feeds the re-geocode job.
+CREATE INDEX customers_missing_coordinates_idx ON customers (id) WHERE latitude IS NULL;
+CREATE INDEX vendors_missing_coordinates_idx ON vendors (id) WHERE latitude IS NULL;
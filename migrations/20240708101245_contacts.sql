@@ -0,0 +1,34 @@
+-- People to reach at a customer or vendor, each row belongs to exactly one of them.
+CREATE TABLE contacts (
+    id SERIAL PRIMARY KEY NOT NULL,
+    customer_id INT NULL,
+    vendor_id INT NULL,
+    name VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL,
+    email VARCHAR(255) NULL,
+    phone VARCHAR(50) NULL,
+    preferred_channel VARCHAR(20) NOT NULL,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE CASCADE,
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id) ON DELETE CASCADE,
+    CHECK (num_nonnulls(customer_id, vendor_id) = 1),
+    CHECK (role IN ('general', 'billing', 'operations', 'after_hours')),
+    CHECK (preferred_channel IN ('email', 'phone', 'sms'))
+);
+CREATE TRIGGER update_contact_modtime BEFORE UPDATE ON contacts FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX contacts_customer_id_idx ON contacts (customer_id) WHERE customer_id IS NOT NULL;
+CREATE INDEX contacts_vendor_id_idx ON contacts (vendor_id) WHERE vendor_id IS NOT NULL;
+
+-- At most one primary contact per role.
+CREATE UNIQUE INDEX contacts_customer_primary_idx ON contacts (customer_id, role) WHERE is_primary AND customer_id IS NOT NULL;
+CREATE UNIQUE INDEX contacts_vendor_primary_idx ON contacts (vendor_id, role) WHERE is_primary AND vendor_id IS NOT NULL;
+
+-- The single email and contact number become the primary general contact.
+INSERT INTO contacts (customer_id, name, role, email, phone, preferred_channel, is_primary)
+SELECT id, name, 'general', email, contact_number, 'email', TRUE FROM customers;
+
+INSERT INTO contacts (vendor_id, name, role, email, phone, preferred_channel, is_primary)
+SELECT id, name, 'general', email, contact_number, 'email', TRUE FROM vendors;
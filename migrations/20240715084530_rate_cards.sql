@@ -0,0 +1,36 @@
+-- What a vendor charges for one vehicle type over a date range, priced from the rate grid below.
+CREATE TABLE rate_cards (
+    id SERIAL PRIMARY KEY NOT NULL,
+    vendor_id INT NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    vehicle_type VARCHAR(50) NOT NULL,
+    minimum_charge DECIMAL(12, 2) NOT NULL,
+    effective_from DATE NOT NULL,
+    effective_to DATE NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id),
+    CHECK (minimum_charge >= 0),
+    CHECK (effective_to IS NULL OR effective_to >= effective_from)
+);
+CREATE TRIGGER update_rate_card_modtime BEFORE UPDATE ON rate_cards FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX rate_cards_vendor_id_idx ON rate_cards (vendor_id);
+CREATE INDEX rate_cards_effective_idx ON rate_cards (effective_from, effective_to);
+
+-- One cell per distance band and weight break, each starting at its lower bound (inclusive).
+CREATE TABLE rate_card_rates (
+    rate_card_id INT NOT NULL,
+    min_distance DECIMAL(10, 2) NOT NULL,
+    min_weight DECIMAL(10, 2) NOT NULL,
+    base_charge DECIMAL(12, 2) NOT NULL,
+    rate_per_km DECIMAL(12, 4) NOT NULL,
+    rate_per_kg DECIMAL(12, 4) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    PRIMARY KEY (rate_card_id, min_distance, min_weight),
+    FOREIGN KEY (rate_card_id) REFERENCES rate_cards(id) ON DELETE CASCADE,
+    CHECK (min_distance >= 0 AND min_weight >= 0),
+    CHECK (base_charge >= 0 AND rate_per_km >= 0 AND rate_per_kg >= 0)
+);
+CREATE TRIGGER update_rate_card_rate_modtime BEFORE UPDATE ON rate_card_rates FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
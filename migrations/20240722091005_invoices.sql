@@ -0,0 +1,68 @@
+-- Order lines carry what the customer is charged, priced from the catalogue when the order is taken.
+ALTER TABLE order_items ADD COLUMN quantity INT NOT NULL DEFAULT 1;
+ALTER TABLE order_items ADD COLUMN unit_price DECIMAL(10, 2) NULL;
+UPDATE order_items oi SET unit_price = i.unit_price FROM items i WHERE i.id = oi.item_id;
+ALTER TABLE order_items ALTER COLUMN unit_price SET NOT NULL;
+ALTER TABLE order_items ADD CHECK (quantity > 0);
+
+-- Last number handed out per invoice type. Numbers are taken in the transaction that issues the
+-- invoice, so a rollback gives the number back and the sequence has no gaps.
+CREATE TABLE invoice_sequences (
+    invoice_type VARCHAR(20) PRIMARY KEY NOT NULL,
+    prefix VARCHAR(10) NOT NULL,
+    last_number INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL
+);
+CREATE TRIGGER update_invoice_sequence_modtime BEFORE UPDATE ON invoice_sequences FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+INSERT INTO invoice_sequences (invoice_type, prefix) VALUES ('invoice', 'INV'), ('credit_note', 'CN');
+
+-- Invoices and credit notes, credit notes point at the invoice they credit and carry negative amounts.
+CREATE TABLE invoices (
+    id SERIAL PRIMARY KEY NOT NULL,
+    customer_id INT NOT NULL,
+    invoice_type VARCHAR(20) NOT NULL,
+    invoice_status VARCHAR(20) NOT NULL,
+    invoice_number VARCHAR(20) NULL UNIQUE,
+    credited_invoice_id INT NULL,
+    reason VARCHAR(500) NULL,
+    issued_at TIMESTAMPTZ NULL,
+    voided_at TIMESTAMPTZ NULL,
+    void_reason VARCHAR(500) NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (customer_id) REFERENCES customers(id),
+    FOREIGN KEY (credited_invoice_id) REFERENCES invoices(id),
+    FOREIGN KEY (invoice_type) REFERENCES invoice_sequences(invoice_type),
+    CHECK (invoice_status IN ('draft', 'issued', 'void')),
+    CHECK ((invoice_type = 'credit_note') = (credited_invoice_id IS NOT NULL)),
+    CHECK ((invoice_status = 'draft') = (invoice_number IS NULL))
+);
+CREATE TRIGGER update_invoice_modtime BEFORE UPDATE ON invoices FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX invoices_customer_id_idx ON invoices (customer_id);
+CREATE INDEX invoices_credited_invoice_id_idx ON invoices (credited_invoice_id) WHERE credited_invoice_id IS NOT NULL;
+
+CREATE TABLE invoice_lines (
+    id SERIAL PRIMARY KEY NOT NULL,
+    invoice_id INT NOT NULL,
+    order_id INT NULL,
+    item_id INT NULL,
+    credited_line_id INT NULL,
+    description VARCHAR(500) NOT NULL,
+    quantity INT NOT NULL,
+    unit_price DECIMAL(12, 2) NOT NULL,
+    line_total DECIMAL(14, 2) GENERATED ALWAYS AS (quantity * unit_price) STORED,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (invoice_id) REFERENCES invoices(id),
+    FOREIGN KEY (order_id, item_id) REFERENCES order_items(order_id, item_id),
+    FOREIGN KEY (credited_line_id) REFERENCES invoice_lines(id),
+    CHECK (quantity > 0)
+);
+CREATE TRIGGER update_invoice_line_modtime BEFORE UPDATE ON invoice_lines FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX invoice_lines_invoice_id_idx ON invoice_lines (invoice_id);
+CREATE INDEX invoice_lines_order_id_idx ON invoice_lines (order_id) WHERE order_id IS NOT NULL;
+CREATE INDEX invoice_lines_credited_line_id_idx ON invoice_lines (credited_line_id) WHERE credited_line_id IS NOT NULL;
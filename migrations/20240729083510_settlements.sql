@@ -0,0 +1,49 @@
+-- What a vehicle carried on a route and when it finished, a route is only paid once completed.
+ALTER TABLE vehicle_routes ADD COLUMN load DECIMAL(10, 2) NULL;
+ALTER TABLE vehicle_routes ADD COLUMN completed_at TIMESTAMPTZ NULL;
+
+CREATE INDEX vehicle_routes_completed_at_idx ON vehicle_routes (completed_at) WHERE completed_at IS NOT NULL;
+
+-- What we owe a vendor for the routes its vehicles completed over a period.
+CREATE TABLE settlements (
+    id SERIAL PRIMARY KEY NOT NULL,
+    vendor_id INT NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    settlement_status VARCHAR(20) NOT NULL,
+    approved_at TIMESTAMPTZ NULL,
+    approved_by VARCHAR(255) NULL,
+    rejected_at TIMESTAMPTZ NULL,
+    rejection_reason VARCHAR(500) NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id),
+    CHECK (period_end >= period_start),
+    CHECK (settlement_status IN ('draft', 'approved', 'rejected'))
+);
+CREATE TRIGGER update_settlement_modtime BEFORE UPDATE ON settlements FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX settlements_vendor_id_idx ON settlements (vendor_id);
+CREATE INDEX settlements_approved_at_idx ON settlements (approved_at) WHERE settlement_status = 'approved';
+
+-- One line per completed vehicle route, priced from the rate card in effect when it completed.
+CREATE TABLE settlement_lines (
+    settlement_id INT NOT NULL,
+    vehicle_id INT NOT NULL,
+    route_id INT NOT NULL,
+    rate_card_id INT NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL,
+    distance DECIMAL(10, 2) NOT NULL,
+    load DECIMAL(10, 2) NOT NULL,
+    amount DECIMAL(12, 2) NOT NULL,
+    minimum_charge_applied BOOLEAN NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    PRIMARY KEY (settlement_id, vehicle_id, route_id),
+    FOREIGN KEY (settlement_id) REFERENCES settlements(id),
+    FOREIGN KEY (vehicle_id, route_id) REFERENCES vehicle_routes(vehicle_id, route_id),
+    FOREIGN KEY (rate_card_id) REFERENCES rate_cards(id)
+);
+CREATE TRIGGER update_settlement_line_modtime BEFORE UPDATE ON settlement_lines FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+CREATE INDEX settlement_lines_route_idx ON settlement_lines (vehicle_id, route_id);
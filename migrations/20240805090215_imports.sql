@@ -0,0 +1,19 @@
+-- Bulk CSV imports of customers and vendors, kept so the rejected rows can be downloaded later.
+CREATE TABLE imports (
+    id SERIAL PRIMARY KEY NOT NULL,
+    entity VARCHAR(20) NOT NULL CHECK (entity IN ('customers', 'vendors')),
+    imported_by VARCHAR(255) NOT NULL,
+    total_rows INT NOT NULL,
+    created_count INT NOT NULL,
+    updated_count INT NOT NULL,
+    rejected_count INT NOT NULL,
+    -- The rejected rows as CSV, NULL when every row was imported.
+    error_file TEXT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL
+);
+CREATE TRIGGER update_import_modtime BEFORE UPDATE ON imports FOR EACH ROW EXECUTE PROCEDURE update_modified_column();
+
+-- Imports upsert by email.
+CREATE INDEX idx_customers_email ON customers (lower(email));
+CREATE INDEX idx_vendors_email ON vendors (lower(email));
@@ -0,0 +1,7 @@
+-- Requests per principal and rate limit window, shared by every instance of the API.
+CREATE TABLE rate_limit_windows (
+    principal VARCHAR(255) NOT NULL,
+    window_start TIMESTAMPTZ NOT NULL,
+    requests INT NOT NULL,
+    PRIMARY KEY (principal, window_start)
+);
@@ -0,0 +1,62 @@
+-- Every business row belongs to a tenant, the Entra ID tenant (`tid`) of the users who created it.
+-- Rows from before tenancy go to `app.default_tenant_id`, set by the service while migrating.
+DO $$
+DECLARE
+    tenant_table TEXT;
+BEGIN
+    FOREACH tenant_table IN ARRAY ARRAY[
+        'customers', 'orders', 'items', 'order_items', 'vendors', 'vehicles', 'routes',
+        'vehicle_routes', 'users', 'order_users', 'proof_of_deliveries', 'proof_of_delivery_files',
+        'vehicle_positions', 'vehicle_last_positions', 'route_plans', 'route_plan_routes',
+        'contacts', 'rate_cards', 'rate_card_rates', 'invoices', 'invoice_lines', 'settlements',
+        'settlement_lines', 'imports'
+    ]
+    LOOP
+        EXECUTE format(
+            $sql$ALTER TABLE %I ADD COLUMN tenant_id VARCHAR(64) NOT NULL
+                DEFAULT coalesce(nullif(current_setting('app.default_tenant_id', true), ''), 'unassigned')$sql$,
+            tenant_table
+        );
+        EXECUTE format('ALTER TABLE %I ALTER COLUMN tenant_id DROP DEFAULT', tenant_table);
+        EXECUTE format('CREATE INDEX %I ON %I (tenant_id)', tenant_table || '_tenant_id_idx', tenant_table);
+
+        -- Backs up the tenant filter of every query: a connection sees the rows of the tenant in
+        -- `app.tenant_id`, or every row when `app.all_tenants` is on for background jobs. Forced so
+        -- the table owner is held to it too, only superusers and BYPASSRLS roles skip it.
+        EXECUTE format('ALTER TABLE %I ENABLE ROW LEVEL SECURITY', tenant_table);
+        EXECUTE format('ALTER TABLE %I FORCE ROW LEVEL SECURITY', tenant_table);
+        EXECUTE format(
+            $sql$CREATE POLICY tenant_isolation ON %I
+                USING (tenant_id = current_setting('app.tenant_id', true)
+                    OR current_setting('app.all_tenants', true) = 'on')$sql$,
+            tenant_table
+        );
+    END LOOP;
+END
+$$;
+
+-- Each tenant numbers its invoices and credit notes on its own, without gaps.
+CREATE TABLE invoice_counters (
+    tenant_id VARCHAR(64) NOT NULL,
+    invoice_type VARCHAR(20) NOT NULL,
+    last_number INT NOT NULL,
+    PRIMARY KEY (tenant_id, invoice_type),
+    FOREIGN KEY (invoice_type) REFERENCES invoice_sequences(invoice_type)
+);
+
+INSERT INTO invoice_counters (tenant_id, invoice_type, last_number)
+SELECT coalesce(nullif(current_setting('app.default_tenant_id', true), ''), 'unassigned'),
+       invoice_type, last_number
+FROM invoice_sequences
+WHERE last_number > 0;
+
+ALTER TABLE invoice_sequences DROP COLUMN last_number;
+
+ALTER TABLE invoice_counters ENABLE ROW LEVEL SECURITY;
+ALTER TABLE invoice_counters FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON invoice_counters
+    USING (tenant_id = current_setting('app.tenant_id', true)
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE invoices DROP CONSTRAINT invoices_invoice_number_key;
+ALTER TABLE invoices ADD UNIQUE (tenant_id, invoice_number);
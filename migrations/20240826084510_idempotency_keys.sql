@@ -0,0 +1,19 @@
+-- The response to each POST sent with an `Idempotency-Key`, replayed when a client retries it.
+-- A row without a status is a request still being answered.
+CREATE TABLE idempotency_keys (
+    tenant_id VARCHAR(64) NOT NULL,
+    principal VARCHAR(255) NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    fingerprint BYTEA NOT NULL,
+    status SMALLINT,
+    headers JSONB,
+    body BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (tenant_id, principal, idempotency_key)
+);
+
+ALTER TABLE idempotency_keys ENABLE ROW LEVEL SECURITY;
+ALTER TABLE idempotency_keys FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON idempotency_keys
+    USING (tenant_id = current_setting('app.tenant_id', true)
+        OR current_setting('app.all_tenants', true) = 'on');
@@ -0,0 +1,18 @@
+-- When the goods of an order may be collected and delivered. The time zone is the one of the
+-- sites, and decides which day a window falls on.
+ALTER TABLE orders
+    ADD COLUMN time_zone VARCHAR(64) NULL,
+    ADD COLUMN pickup_earliest TIMESTAMPTZ NULL,
+    ADD COLUMN pickup_latest TIMESTAMPTZ NULL,
+    ADD COLUMN delivery_earliest TIMESTAMPTZ NULL,
+    ADD COLUMN delivery_latest TIMESTAMPTZ NULL,
+    ADD CONSTRAINT orders_pickup_window_check
+        CHECK ((pickup_earliest IS NULL) = (pickup_latest IS NULL) AND pickup_earliest < pickup_latest),
+    ADD CONSTRAINT orders_delivery_window_check
+        CHECK ((delivery_earliest IS NULL) = (delivery_latest IS NULL) AND delivery_earliest < delivery_latest),
+    ADD CONSTRAINT orders_time_zone_check
+        CHECK (time_zone IS NOT NULL OR (pickup_earliest IS NULL AND delivery_earliest IS NULL));
+
+-- The due today and late filters.
+CREATE INDEX orders_delivery_latest_idx ON orders (tenant_id, delivery_latest) WHERE delivery_latest IS NOT NULL;
+CREATE INDEX orders_pickup_latest_idx ON orders (tenant_id, pickup_latest) WHERE pickup_latest IS NOT NULL;
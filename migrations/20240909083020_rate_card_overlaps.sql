@@ -0,0 +1,11 @@
+-- Only one card per vehicle type can be in effect for a vendor on any day, enforced here so two
+-- saves racing past the check in the API cannot both land.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE rate_cards
+    ADD CONSTRAINT rate_cards_no_overlap EXCLUDE USING gist (
+        tenant_id WITH =,
+        vendor_id WITH =,
+        lower(vehicle_type) WITH =,
+        daterange(effective_from, effective_to, '[]') WITH &&
+    );
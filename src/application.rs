@@ -1,3 +1,13 @@
 pub mod auth;
+pub mod config;
+pub mod demo_data;
+pub mod graphql;
+pub mod idempotency;
+pub mod imports;
+pub mod jobs;
+pub mod metrics;
+pub mod rate_limit;
+pub mod reference_data;
 pub mod routes;
+pub mod telemetry;
 pub mod utils;
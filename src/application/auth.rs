@@ -1,36 +1,185 @@
-use axum::{async_trait, extract::FromRequestParts, RequestPartsExt};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts, Request},
+    middleware::Next,
+    response::Response,
+    RequestPartsExt,
+};
 use axum_extra::{
     headers::{authorization::Bearer, Authorization},
     TypedHeader,
 };
-use dotenvy::var;
 use http::request::Parts;
 use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use super::config::Config;
+use super::metrics::Metrics;
 use super::utils::http_utils::AuthError;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::tenancy;
+
+const JWKS_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long fetched signing keys are trusted before they are fetched again.
+const JWKS_TTL: Duration = Duration::from_secs(300);
+/// How often a token signed with a key that is not cached can have the keys fetched early.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Who issues the access tokens the API accepts and where its signing keys are published, see
+/// [`Config`].
+#[derive(Debug, Clone)]
+pub struct Authority {
+    pub jwks_url: Url,
+    pub issuer: String,
+    pub audience: String,
+}
+
+impl Authority {
+    /// The published signing keys, the `keys` of the JWKS document.
+    pub async fn signing_keys(&self) -> Result<Vec<serde_json::Value>> {
+        let mut jwks: serde_json::Value = reqwest::Client::new()
+            .get(self.jwks_url.clone())
+            .timeout(JWKS_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match jwks["keys"].take() {
+            serde_json::Value::Array(keys) => Ok(keys),
+            _ => Err(anyhow!("{} has no keys.", self.jwks_url)),
+        }
+    }
+}
+
+/// The signing keys of the [`Authority`], shared by token verification and the readiness check so
+/// the JWKS document is fetched once per [`JWKS_TTL`] rather than once per request. A failed fetch
+/// is not cached, the next caller tries again.
+#[derive(Default)]
+pub struct SigningKeys {
+    cached: Mutex<Option<(Instant, Arc<Vec<serde_json::Value>>)>>,
+    refreshed_at: Mutex<Option<Instant>>,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
+impl SigningKeys {
+    /// The cached keys, fetched again and counted in `metrics` once they are older than the TTL.
+    pub async fn get(
+        &self,
+        authority: &Authority,
+        metrics: &Metrics,
+    ) -> Result<Arc<Vec<serde_json::Value>>> {
+        if let Some((fetched_at, keys)) = self.cached.lock().unwrap().as_ref() {
+            if fetched_at.elapsed() < JWKS_TTL {
+                return Ok(keys.clone());
+            }
+        }
+
+        self.fetch(authority, metrics).await
+    }
+
+    /// Fetches the keys before the TTL is up, for a token signed with a key the authority rotated
+    /// in since they were cached. `None` when a refresh was already forced in the last
+    /// [`JWKS_REFRESH_INTERVAL`], so tokens with made-up key ids cannot fetch on every request.
+    pub async fn refresh(
+        &self,
+        authority: &Authority,
+        metrics: &Metrics,
+    ) -> Result<Option<Arc<Vec<serde_json::Value>>>> {
+        {
+            let mut refreshed_at = self.refreshed_at.lock().unwrap();
+            if refreshed_at.is_some_and(|at| at.elapsed() < JWKS_REFRESH_INTERVAL) {
+                return Ok(None);
+            }
+            *refreshed_at = Some(Instant::now());
+        }
+
+        self.fetch(authority, metrics).await.map(Some)
+    }
+
+    async fn fetch(
+        &self,
+        authority: &Authority,
+        metrics: &Metrics,
+    ) -> Result<Arc<Vec<serde_json::Value>>> {
+        let keys = authority.signing_keys().await;
+        metrics.observe_jwks_fetch(&keys);
+        let keys = Arc::new(keys?);
+
+        *self.cached.lock().unwrap() = Some((Instant::now(), keys.clone()));
+
+        Ok(keys)
+    }
+}
+
+/// The claims of a verified access token, kept with the request so later extractors and
+/// middleware get them without verifying the token again. Tokens an application gets for itself
+/// carry no user claims, only the client id in `azp`, or `appid` in version 1.0 tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequireAuth {
-    pub oid: String,
-    pub name: String,
-    pub preferred_username: String,
+    #[serde(default)]
+    pub oid: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub preferred_username: Option<String>,
+    #[serde(default)]
+    pub azp: Option<String>,
+    #[serde(default)]
+    pub appid: Option<String>,
     pub sub: String,
+    /// The tenant of the user or application, which picks the rows it works with.
+    pub tid: String,
     pub exp: usize,
+    #[serde(default)]
     pub roles: Vec<String>,
 }
 
+impl RequireAuth {
+    /// The user or application the request is counted and remembered for, its object id when the
+    /// token has one.
+    pub fn principal(&self) -> &str {
+        self.oid
+            .as_deref()
+            .or(self.client_id())
+            .unwrap_or(&self.sub)
+    }
+
+    /// Who to record as having done something, the user's sign-in name or the application.
+    pub fn username(&self) -> &str {
+        self.preferred_username
+            .as_deref()
+            .or(self.client_id())
+            .unwrap_or(self.principal())
+    }
+
+    fn client_id(&self) -> Option<&str> {
+        self.azp.as_deref().or(self.appid.as_deref())
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for RequireAuth
 where
+    Arc<Config>: FromRef<S>,
+    Arc<Metrics>: FromRef<S>,
+    Arc<SigningKeys>: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = AuthError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        let tenant_id = var("TENANT_ID").expect("Missing TENANT_ID environment variable.");
-        let audience = var("AUDIENDE").expect("Missing AUDIENDE environment variable.");
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(claims) = parts.extensions.get::<RequireAuth>() {
+            return Ok(claims.clone());
+        }
+
+        let config = Arc::<Config>::from_ref(state);
+        let authority = &config.auth;
 
         // Extract the token from the authorization header
         let TypedHeader(Authorization(bearer)) = parts
@@ -38,46 +187,39 @@ where
             .await
             .map_err(|_| AuthError)?;
 
-        let metadata = decode_header(bearer.token()).unwrap();
+        let metadata = decode_header(bearer.token()).map_err(|_| AuthError)?;
+        let kid = metadata.kid.ok_or(AuthError)?;
 
-        let url = Url::parse(&format!(
-            "https://login.microsoftonline.com/{}/discovery/v2.0/keys",
-            tenant_id
-        ));
+        let signing_keys = Arc::<SigningKeys>::from_ref(state);
+        let metrics = Arc::<Metrics>::from_ref(state);
+        let find = |keys: &[serde_json::Value]| keys.iter().find(|k| k["kid"] == kid).cloned();
 
-        let resp = reqwest::Client::new()
-            .get(url.unwrap())
-            .send()
-            .await
-            .map_err(|_| AuthError)?
-            .text()
+        let keys = signing_keys
+            .get(authority, &metrics)
             .await
             .map_err(|_| AuthError)?;
 
-        let keys: serde_json::Value = serde_json::from_str(&resp).map_err(|_| AuthError)?;
-
-        let key = keys["keys"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .find(|&k| &k["kid"] == metadata.kid.as_ref().unwrap())
-            .unwrap();
+        let key = match find(&keys) {
+            Some(key) => key,
+            // The authority may have rotated in a new key since the keys were cached.
+            None => signing_keys
+                .refresh(authority, &metrics)
+                .await
+                .map_err(|_| AuthError)?
+                .and_then(|keys| find(&keys))
+                .ok_or(AuthError)?,
+        };
 
         let mut validation = Validation::new(Algorithm::RS256);
 
-        validation.set_audience(&[audience]);
-        validation.set_issuer(&[format!(
-            "https://login.microsoftonline.com/{}/v2.0",
-            tenant_id
-        )]);
+        validation.set_audience(&[&authority.audience]);
+        validation.set_issuer(&[&authority.issuer]);
 
         let pub_key = key["x5c"]
             .as_array()
-            .unwrap()
-            .first()
-            .unwrap()
-            .as_str()
-            .unwrap();
+            .and_then(|certs| certs.first())
+            .and_then(|cert| cert.as_str())
+            .ok_or(AuthError)?;
 
         let cert = format!(
             "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----",
@@ -87,11 +229,36 @@ where
         // Decode the user data
         let token_data = decode::<RequireAuth>(
             bearer.token(),
-            &DecodingKey::from_rsa_pem(cert.as_bytes()).unwrap(),
+            &DecodingKey::from_rsa_pem(cert.as_bytes()).map_err(|_| AuthError)?,
             &validation,
         )
         .map_err(|_| AuthError)?;
 
+        parts.extensions.insert(token_data.claims.clone());
+
         Ok(token_data.claims)
     }
 }
+
+/// The tenant of the signed in user, from the `tid` claim.
+#[async_trait]
+impl<S> FromRequestParts<S> for TenantId
+where
+    Arc<Config>: FromRef<S>,
+    Arc<Metrics>: FromRef<S>,
+    Arc<SigningKeys>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = RequireAuth::from_request_parts(parts, state).await?;
+
+        Ok(TenantId::new(&claims.tid))
+    }
+}
+
+/// Handles the request with database connections limited to the rows of the caller's tenant.
+pub async fn scope_tenant(tenant: TenantId, request: Request, next: Next) -> Response {
+    tenancy::in_tenant(tenant, next.run(request)).await
+}
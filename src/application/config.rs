@@ -0,0 +1,801 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use http::{HeaderValue, Method};
+use serde::Deserialize;
+use url::Url;
+
+use super::auth::Authority;
+
+const DEFAULT_CONFIG_FILE: &str = "tsm.toml";
+const DEFAULT_MAX_CONNECTIONS: u32 = 20;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u32 = 30;
+const DEFAULT_RETENTION_DAYS: i32 = 30;
+const DEFAULT_RATE_LIMIT_REQUESTS: u32 = 300;
+const DEFAULT_RATE_LIMIT_WINDOW_SECS: u32 = 60;
+const DEFAULT_IDEMPOTENCY_KEY_TTL_HOURS: u32 = 24;
+const DEFAULT_METHODS: [Method; 6] = [
+    Method::GET,
+    Method::HEAD,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Picks the defaults and the `[profiles.<name>]` table of the config file, from `TSM_PROFILE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Development,
+    Test,
+    Production,
+}
+
+impl Profile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::Development => "development",
+            Profile::Test => "test",
+            Profile::Production => "production",
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "development" => Ok(Profile::Development),
+            "test" => Ok(Profile::Test),
+            "production" => Ok(Profile::Production),
+            _ => Err(anyhow!(
+                "Unknown profile '{}', use development, test or production.",
+                s
+            )),
+        }
+    }
+}
+
+/// How log lines are written to stdout, from `LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One line of text per event, for reading in a terminal.
+    Text,
+    /// One JSON object per event with the fields of its spans, for log collectors.
+    Json,
+}
+
+/// Where rate limit counters are kept, from `RATE_LIMIT_STORE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterStore {
+    /// In this process, each instance limits on its own.
+    Memory,
+    /// In the database, shared by every instance.
+    Postgres,
+}
+
+/// Everything the service reads at startup, validated before anything is started.
+///
+/// Each setting comes from the first of: its environment variable, the profile table of the
+/// config file, the top level of the config file, the profile defaults.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub profile: Profile,
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub auth: Authority,
+    pub geocoder: GeocoderConfig,
+    pub vehicle_positions: VehiclePositionsConfig,
+    pub metrics: MetricsConfig,
+    pub telemetry: TelemetryConfig,
+    pub rate_limit: RateLimitConfig,
+    pub tenancy: TenancyConfig,
+    pub idempotency: IdempotencyConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub bind_address: SocketAddr,
+    /// How long a shutdown waits for open requests and background jobs before exiting anyway.
+    pub shutdown_timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<HeaderValue>,
+    pub allowed_methods: Vec<Method>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeocoderConfig {
+    pub url: Option<Url>,
+    pub gazetteer_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VehiclePositionsConfig {
+    pub retention_days: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+    /// The bearer token `/metrics` asks for, required in production. The endpoint is open
+    /// without one.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    pub log_format: LogFormat,
+    /// An OpenTelemetry collector taking OTLP over HTTP, e.g. `http://localhost:4318`. Spans are
+    /// only exported when set.
+    pub otlp_endpoint: Option<Url>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub store: CounterStore,
+    /// The requests a principal may make per window when none of its roles is listed.
+    pub requests: u32,
+    pub window: Duration,
+    /// The requests per window of principals holding the role, the highest of its roles applies.
+    pub roles: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TenancyConfig {
+    /// The tenant the rows saved before tenancy are given when the migration runs, the Entra ID
+    /// tenant unless set. Without either they go to `unassigned`.
+    pub default_tenant_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyConfig {
+    /// How long the response to a request with an `Idempotency-Key` is replayed, after which the
+    /// key may be used again.
+    pub key_ttl: Duration,
+}
+
+impl Config {
+    /// Reads the process environment and the file at `TSM_CONFIG`, or `tsm.toml` when it exists.
+    pub fn load() -> Result<Self> {
+        let env = |name: &str| dotenvy::var(name).ok().filter(|v| !v.is_empty());
+
+        let (path, required) = match env("TSM_CONFIG") {
+            Some(path) => (PathBuf::from(path), true),
+            None => (PathBuf::from(DEFAULT_CONFIG_FILE), false),
+        };
+
+        let file = match std::fs::read_to_string(&path) {
+            Ok(contents) => Some((path, contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => None,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Cannot read {}", path.display()));
+            }
+        };
+
+        Self::from_sources(
+            env,
+            file.as_ref()
+                .map(|(path, contents)| (path.as_path(), contents.as_str())),
+        )
+    }
+
+    /// Resolves the configuration from `env` and an optional config file path and contents,
+    /// reporting every invalid or missing setting at once.
+    pub fn from_sources(
+        env: impl Fn(&str) -> Option<String>,
+        file: Option<(&Path, &str)>,
+    ) -> Result<Self> {
+        let profile = match env("TSM_PROFILE") {
+            Some(profile) => profile.parse().context("TSM_PROFILE")?,
+            None => Profile::Development,
+        };
+
+        let mut errors = Vec::new();
+
+        let env_layer = Layer::from_env(&env, &mut errors);
+
+        let mut layer = env_layer;
+        if let Some((path, contents)) = file {
+            let mut file_layer: Layer = toml_edit::de::from_str(contents)
+                .with_context(|| format!("Cannot parse {}", path.display()))?;
+
+            let mut profiles = std::mem::take(&mut file_layer.profiles);
+            if let Some(name) = profiles
+                .keys()
+                .find(|name| name.parse::<Profile>().is_err())
+            {
+                bail!(
+                    "Cannot parse {}: unknown profile [profiles.{}].",
+                    path.display(),
+                    name
+                );
+            }
+            if let Some(profile_layer) = profiles.remove(profile.as_str()) {
+                if !profile_layer.profiles.is_empty() {
+                    bail!(
+                        "Cannot parse {}: profiles cannot be nested.",
+                        path.display()
+                    );
+                }
+                layer = layer.or(profile_layer);
+            }
+            layer = layer.or(file_layer);
+        }
+
+        let config = Self::resolve(profile, layer, &mut errors);
+
+        if !errors.is_empty() {
+            bail!("Invalid configuration:\n  - {}", errors.join("\n  - "));
+        }
+
+        Ok(config.expect("a configuration without errors"))
+    }
+
+    fn resolve(profile: Profile, layer: Layer, errors: &mut Vec<String>) -> Option<Self> {
+        let default_bind_address = match profile {
+            Profile::Development | Profile::Test => "127.0.0.1:3000",
+            Profile::Production => "0.0.0.0:3000",
+        };
+        let bind_address = layer
+            .server
+            .bind_address
+            .as_deref()
+            .unwrap_or(default_bind_address)
+            .parse::<SocketAddr>()
+            .map_err(|e| errors.push(format!("server.bind_address (BIND_ADDRESS): {}.", e)))
+            .ok();
+
+        let database_url = match layer.database.url {
+            Some(url) if url.starts_with("postgres://") || url.starts_with("postgresql://") => {
+                Some(url)
+            }
+            Some(_) => {
+                errors.push("database.url (DATABASE_URL) must be a postgres:// URL.".to_string());
+                None
+            }
+            None => {
+                errors.push("database.url (DATABASE_URL) is required.".to_string());
+                None
+            }
+        };
+
+        let max_connections = layer
+            .database
+            .max_connections
+            .unwrap_or(DEFAULT_MAX_CONNECTIONS);
+        if max_connections == 0 {
+            errors.push(
+                "database.max_connections (DATABASE_MAX_CONNECTIONS) must be at least 1."
+                    .to_string(),
+            );
+        }
+
+        // Production serves the frontend from its own origin unless told otherwise.
+        let allowed_origins = layer.cors.allowed_origins.unwrap_or_else(|| match profile {
+            Profile::Development | Profile::Test => vec!["http://localhost:5173".to_string()],
+            Profile::Production => Vec::new(),
+        });
+        let allowed_origins: Vec<HeaderValue> = allowed_origins
+            .iter()
+            .filter_map(|origin| {
+                parse_origin(origin)
+                    .map_err(|e| {
+                        errors.push(format!(
+                            "cors.allowed_origins (CORS_ALLOWED_ORIGINS): '{}' {}.",
+                            origin, e
+                        ))
+                    })
+                    .ok()
+            })
+            .collect();
+
+        let allowed_methods = match layer.cors.allowed_methods {
+            Some(methods) => methods
+                .iter()
+                .filter_map(|method| {
+                    Method::from_bytes(method.trim().to_uppercase().as_bytes())
+                        .map_err(|_| {
+                            errors.push(format!(
+                                "cors.allowed_methods (CORS_ALLOWED_METHODS): '{}' is not an HTTP method.",
+                                method
+                            ))
+                        })
+                        .ok()
+                })
+                .collect(),
+            None => DEFAULT_METHODS.to_vec(),
+        };
+
+        // Business gauges of every tenant are not for anyone who finds the endpoint.
+        let token = layer.metrics.token;
+        if token.is_none() && profile == Profile::Production {
+            errors.push("metrics.token (METRICS_TOKEN) is required in production.".to_string());
+        }
+
+        let default_tenant_id = layer
+            .tenancy
+            .default_tenant_id
+            .or_else(|| layer.auth.tenant_id.clone());
+
+        let auth = resolve_authority(layer.auth, errors);
+
+        let geocoder_url = layer.geocoder.url.and_then(|url| {
+            Url::parse(&url)
+                .map_err(|e| errors.push(format!("geocoder.url (GEOCODER_URL): {}.", e)))
+                .ok()
+        });
+
+        let retention_days = layer
+            .vehicle_positions
+            .retention_days
+            .unwrap_or(DEFAULT_RETENTION_DAYS);
+        if retention_days < 1 {
+            errors.push(
+                "vehicle_positions.retention_days (VEHICLE_POSITION_RETENTION_DAYS) must be at least 1."
+                    .to_string(),
+            );
+        }
+
+        let log_format = match layer.telemetry.log_format.as_deref() {
+            Some("text") => LogFormat::Text,
+            Some("json") => LogFormat::Json,
+            Some(other) => {
+                errors.push(format!(
+                    "telemetry.log_format (LOG_FORMAT): '{}' is not text or json.",
+                    other
+                ));
+                LogFormat::Text
+            }
+            None => match profile {
+                Profile::Development | Profile::Test => LogFormat::Text,
+                Profile::Production => LogFormat::Json,
+            },
+        };
+
+        let otlp_endpoint = layer.telemetry.otlp_endpoint.and_then(|url| {
+            Url::parse(&url)
+                .map_err(|e| {
+                    errors.push(format!(
+                        "telemetry.otlp_endpoint (OTEL_EXPORTER_OTLP_ENDPOINT): {}.",
+                        e
+                    ))
+                })
+                .ok()
+        });
+
+        let counter_store = match layer.rate_limit.store.as_deref() {
+            Some("memory") => CounterStore::Memory,
+            Some("postgres") => CounterStore::Postgres,
+            Some(other) => {
+                errors.push(format!(
+                    "rate_limit.store (RATE_LIMIT_STORE): '{}' is not memory or postgres.",
+                    other
+                ));
+                CounterStore::Memory
+            }
+            None => match profile {
+                Profile::Development | Profile::Test => CounterStore::Memory,
+                Profile::Production => CounterStore::Postgres,
+            },
+        };
+
+        let rate_limit_requests = layer
+            .rate_limit
+            .requests
+            .unwrap_or(DEFAULT_RATE_LIMIT_REQUESTS);
+        let rate_limit_window_secs = layer
+            .rate_limit
+            .window_secs
+            .unwrap_or(DEFAULT_RATE_LIMIT_WINDOW_SECS);
+        let rate_limit_roles = layer.rate_limit.roles.unwrap_or_default();
+        if rate_limit_requests == 0 || rate_limit_roles.values().any(|requests| *requests == 0) {
+            errors.push(
+                "rate_limit.requests (RATE_LIMIT_REQUESTS) and rate_limit.roles (RATE_LIMIT_ROLES) must allow at least 1 request."
+                    .to_string(),
+            );
+        }
+        if rate_limit_window_secs == 0 {
+            errors.push(
+                "rate_limit.window_secs (RATE_LIMIT_WINDOW_SECS) must be at least 1.".to_string(),
+            );
+        }
+
+        let idempotency_key_ttl_hours = layer
+            .idempotency
+            .key_ttl_hours
+            .unwrap_or(DEFAULT_IDEMPOTENCY_KEY_TTL_HOURS);
+        if idempotency_key_ttl_hours == 0 {
+            errors.push(
+                "idempotency.key_ttl_hours (IDEMPOTENCY_KEY_TTL_HOURS) must be at least 1."
+                    .to_string(),
+            );
+        }
+
+        Some(Self {
+            profile,
+            server: ServerConfig {
+                bind_address: bind_address?,
+                shutdown_timeout: Duration::from_secs(u64::from(
+                    layer
+                        .server
+                        .shutdown_timeout_secs
+                        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
+                )),
+            },
+            database: DatabaseConfig {
+                url: database_url?,
+                max_connections,
+            },
+            cors: CorsConfig {
+                allowed_origins,
+                allowed_methods,
+            },
+            auth: auth?,
+            geocoder: GeocoderConfig {
+                url: geocoder_url,
+                gazetteer_path: layer.geocoder.gazetteer_path.map(PathBuf::from),
+            },
+            vehicle_positions: VehiclePositionsConfig { retention_days },
+            metrics: MetricsConfig { token },
+            telemetry: TelemetryConfig {
+                log_format,
+                otlp_endpoint,
+            },
+            rate_limit: RateLimitConfig {
+                store: counter_store,
+                requests: rate_limit_requests,
+                window: Duration::from_secs(u64::from(rate_limit_window_secs)),
+                roles: rate_limit_roles,
+            },
+            tenancy: TenancyConfig { default_tenant_id },
+            idempotency: IdempotencyConfig {
+                key_ttl: Duration::from_secs(u64::from(idempotency_key_ttl_hours) * 60 * 60),
+            },
+        })
+    }
+}
+
+/// The Entra ID tenant's endpoints, unless the keys and issuer of another provider are given.
+fn resolve_authority(auth: AuthLayer, errors: &mut Vec<String>) -> Option<Authority> {
+    let audience = auth.audience.or_else(|| {
+        errors.push("auth.audience (AUDIENCE) is required.".to_string());
+        None
+    });
+
+    let (jwks_url, issuer) = match (auth.tenant_id, auth.jwks_url, auth.issuer) {
+        (_, Some(jwks_url), Some(issuer)) => (jwks_url, issuer),
+        (Some(tenant_id), jwks_url, issuer) => (
+            jwks_url.unwrap_or_else(|| {
+                format!(
+                    "https://login.microsoftonline.com/{}/discovery/v2.0/keys",
+                    tenant_id
+                )
+            }),
+            issuer
+                .unwrap_or_else(|| format!("https://login.microsoftonline.com/{}/v2.0", tenant_id)),
+        ),
+        (None, _, _) => {
+            errors.push(
+                "auth.tenant_id (TENANT_ID) is required, or both auth.jwks_url (JWKS_URL) and auth.issuer (TOKEN_ISSUER)."
+                    .to_string(),
+            );
+            return None;
+        }
+    };
+
+    let jwks_url = Url::parse(&jwks_url)
+        .map_err(|e| errors.push(format!("auth.jwks_url (JWKS_URL): {}.", e)))
+        .ok()?;
+
+    Some(Authority {
+        jwks_url,
+        issuer,
+        audience: audience?,
+    })
+}
+
+/// An origin as browsers send it, scheme and host with the port when it is not the default.
+fn parse_origin(origin: &str) -> Result<HeaderValue, &'static str> {
+    let url = Url::parse(origin).map_err(|_| "is not a URL")?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("is not an http or https origin");
+    }
+    if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+        return Err("has a path, query or fragment");
+    }
+
+    HeaderValue::from_str(&url.origin().ascii_serialization()).map_err(|_| "is not a valid origin")
+}
+
+/// Settings from one source, each left unset falls through to the next source.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Layer {
+    server: ServerLayer,
+    database: DatabaseLayer,
+    cors: CorsLayer,
+    auth: AuthLayer,
+    geocoder: GeocoderLayer,
+    vehicle_positions: VehiclePositionsLayer,
+    metrics: MetricsLayer,
+    telemetry: TelemetryLayer,
+    rate_limit: RateLimitLayer,
+    tenancy: TenancyLayer,
+    idempotency: IdempotencyLayer,
+    profiles: BTreeMap<String, Layer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerLayer {
+    bind_address: Option<String>,
+    shutdown_timeout_secs: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DatabaseLayer {
+    url: Option<String>,
+    max_connections: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CorsLayer {
+    allowed_origins: Option<Vec<String>>,
+    allowed_methods: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AuthLayer {
+    tenant_id: Option<String>,
+    audience: Option<String>,
+    jwks_url: Option<String>,
+    issuer: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GeocoderLayer {
+    url: Option<String>,
+    gazetteer_path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct VehiclePositionsLayer {
+    retention_days: Option<i32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MetricsLayer {
+    token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TelemetryLayer {
+    log_format: Option<String>,
+    otlp_endpoint: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RateLimitLayer {
+    store: Option<String>,
+    requests: Option<u32>,
+    window_secs: Option<u32>,
+    roles: Option<BTreeMap<String, u32>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TenancyLayer {
+    default_tenant_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IdempotencyLayer {
+    key_ttl_hours: Option<u32>,
+}
+
+impl Layer {
+    fn from_env(env: &impl Fn(&str) -> Option<String>, errors: &mut Vec<String>) -> Self {
+        let list = |name: &str| {
+            env(name).map(|v| {
+                v.split(',')
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+        };
+        let mut number = |name: &str| {
+            env(name).and_then(|v| {
+                v.trim()
+                    .parse()
+                    .map_err(|_| errors.push(format!("{}: '{}' is not a whole number.", name, v)))
+                    .ok()
+            })
+        };
+
+        let shutdown_timeout_secs = number("SHUTDOWN_TIMEOUT_SECS");
+        let rate_limit_requests = number("RATE_LIMIT_REQUESTS");
+        let rate_limit_window_secs = number("RATE_LIMIT_WINDOW_SECS");
+        let idempotency_key_ttl_hours = number("IDEMPOTENCY_KEY_TTL_HOURS");
+        let max_connections = number("DATABASE_MAX_CONNECTIONS");
+        let retention_days = number("VEHICLE_POSITION_RETENTION_DAYS")
+            .map(|days: u32| i32::try_from(days).unwrap_or(i32::MAX));
+
+        // Role=requests pairs, e.g. Integration=1200,Admin=600.
+        let rate_limit_roles = list("RATE_LIMIT_ROLES").map(|pairs: Vec<String>| {
+            pairs
+                .iter()
+                .filter_map(|pair| {
+                    let parsed = pair.split_once('=').and_then(|(role, requests)| {
+                        Some((role.trim(), requests.trim().parse().ok()?))
+                    });
+                    if parsed.is_none() {
+                        errors.push(format!(
+                            "RATE_LIMIT_ROLES: '{}' is not a role=requests pair.",
+                            pair
+                        ));
+                    }
+                    parsed.map(|(role, requests)| (role.to_string(), requests))
+                })
+                .collect()
+        });
+
+        // AUDIENDE is the name the service was first deployed with.
+        let audience = env("AUDIENCE").or_else(|| {
+            env("AUDIENDE").inspect(|_| {
+                tracing::warn!("AUDIENDE is deprecated, set AUDIENCE instead.");
+            })
+        });
+
+        Self {
+            server: ServerLayer {
+                bind_address: env("BIND_ADDRESS"),
+                shutdown_timeout_secs,
+            },
+            database: DatabaseLayer {
+                url: env("DATABASE_URL"),
+                max_connections,
+            },
+            cors: CorsLayer {
+                allowed_origins: list("CORS_ALLOWED_ORIGINS"),
+                allowed_methods: list("CORS_ALLOWED_METHODS"),
+            },
+            auth: AuthLayer {
+                tenant_id: env("TENANT_ID"),
+                audience,
+                jwks_url: env("JWKS_URL"),
+                issuer: env("TOKEN_ISSUER"),
+            },
+            geocoder: GeocoderLayer {
+                url: env("GEOCODER_URL"),
+                gazetteer_path: env("GEOCODER_GAZETTEER_PATH"),
+            },
+            vehicle_positions: VehiclePositionsLayer { retention_days },
+            metrics: MetricsLayer {
+                token: env("METRICS_TOKEN"),
+            },
+            telemetry: TelemetryLayer {
+                log_format: env("LOG_FORMAT"),
+                otlp_endpoint: env("OTEL_EXPORTER_OTLP_ENDPOINT"),
+            },
+            rate_limit: RateLimitLayer {
+                store: env("RATE_LIMIT_STORE"),
+                requests: rate_limit_requests,
+                window_secs: rate_limit_window_secs,
+                roles: rate_limit_roles,
+            },
+            tenancy: TenancyLayer {
+                default_tenant_id: env("DEFAULT_TENANT_ID"),
+            },
+            idempotency: IdempotencyLayer {
+                key_ttl_hours: idempotency_key_ttl_hours,
+            },
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    /// This layer with its unset settings taken from `fallback`.
+    fn or(self, fallback: Layer) -> Layer {
+        Layer {
+            server: ServerLayer {
+                bind_address: self.server.bind_address.or(fallback.server.bind_address),
+                shutdown_timeout_secs: self
+                    .server
+                    .shutdown_timeout_secs
+                    .or(fallback.server.shutdown_timeout_secs),
+            },
+            database: DatabaseLayer {
+                url: self.database.url.or(fallback.database.url),
+                max_connections: self
+                    .database
+                    .max_connections
+                    .or(fallback.database.max_connections),
+            },
+            cors: CorsLayer {
+                allowed_origins: self.cors.allowed_origins.or(fallback.cors.allowed_origins),
+                allowed_methods: self.cors.allowed_methods.or(fallback.cors.allowed_methods),
+            },
+            auth: AuthLayer {
+                tenant_id: self.auth.tenant_id.or(fallback.auth.tenant_id),
+                audience: self.auth.audience.or(fallback.auth.audience),
+                jwks_url: self.auth.jwks_url.or(fallback.auth.jwks_url),
+                issuer: self.auth.issuer.or(fallback.auth.issuer),
+            },
+            geocoder: GeocoderLayer {
+                url: self.geocoder.url.or(fallback.geocoder.url),
+                gazetteer_path: self
+                    .geocoder
+                    .gazetteer_path
+                    .or(fallback.geocoder.gazetteer_path),
+            },
+            vehicle_positions: VehiclePositionsLayer {
+                retention_days: self
+                    .vehicle_positions
+                    .retention_days
+                    .or(fallback.vehicle_positions.retention_days),
+            },
+            metrics: MetricsLayer {
+                token: self.metrics.token.or(fallback.metrics.token),
+            },
+            telemetry: TelemetryLayer {
+                log_format: self.telemetry.log_format.or(fallback.telemetry.log_format),
+                otlp_endpoint: self
+                    .telemetry
+                    .otlp_endpoint
+                    .or(fallback.telemetry.otlp_endpoint),
+            },
+            rate_limit: RateLimitLayer {
+                store: self.rate_limit.store.or(fallback.rate_limit.store),
+                requests: self.rate_limit.requests.or(fallback.rate_limit.requests),
+                window_secs: self
+                    .rate_limit
+                    .window_secs
+                    .or(fallback.rate_limit.window_secs),
+                roles: self.rate_limit.roles.or(fallback.rate_limit.roles),
+            },
+            tenancy: TenancyLayer {
+                default_tenant_id: self
+                    .tenancy
+                    .default_tenant_id
+                    .or(fallback.tenancy.default_tenant_id),
+            },
+            idempotency: IdempotencyLayer {
+                key_ttl_hours: self
+                    .idempotency
+                    .key_ttl_hours
+                    .or(fallback.idempotency.key_ttl_hours),
+            },
+            profiles: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,515 @@
+//! Fake but consistent data for development and testing, written through the aggregates and
+//! repositories like any other change so it keeps to the business rules.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::domain::aggregates::{
+    customer::Customer,
+    item::Item,
+    order::{Order, OrderLine, OrderStatus},
+    proof_of_delivery::ProofOfDelivery,
+    route_plan::RoutePlan,
+    vehicle::Vehicle,
+    vehicle_route::VehicleRoute,
+    vendor::Vendor,
+};
+use crate::domain::services::route_planner::{
+    plan_routes, Plan, PlanningProblem, PlanningVehicle, Stop,
+};
+use crate::domain::value_objects::{
+    address::Address, coordinates::Coordinates, tenant_id::TenantId,
+};
+use crate::infrastructure::repositories::{
+    customer_repository::{CustomerRepository, Repository as _},
+    item_repository::{ItemRepository, Repository as _},
+    order_repository::{OrderRepository, Repository as _},
+    proof_of_delivery_repository::{ProofOfDeliveryRepository, Repository as _},
+    route_plan_repository::{Repository as _, RoutePlanRepository},
+    vehicle_repository::{Repository as _, VehicleRepository},
+    vehicle_route_repository::{Repository as _, VehicleRouteRepository},
+    vendor_repository::{Repository as _, VendorRepository},
+};
+
+const AVERAGE_SPEED_KMH: f64 = 40.0;
+const SERVICE_MINUTES: i64 = 10;
+
+// Sydney, with the suburbs the customers and vendors are spread over.
+const DEPOT: (f64, f64) = (-33.8688, 151.2093);
+const SUBURBS: &[(&str, &str, f64, f64)] = &[
+    ("Parramatta", "2150", -33.8150, 151.0011),
+    ("Chatswood", "2067", -33.7969, 151.1803),
+    ("Bondi", "2026", -33.8915, 151.2767),
+    ("Newtown", "2042", -33.8981, 151.1745),
+    ("Manly", "2095", -33.7969, 151.2846),
+    ("Liverpool", "2170", -33.9200, 150.9238),
+    ("Hornsby", "2077", -33.7025, 151.0994),
+    ("Cronulla", "2230", -34.0587, 151.1526),
+    ("Ryde", "2112", -33.8149, 151.1056),
+    ("Blacktown", "2148", -33.7668, 150.9054),
+    ("Hurstville", "2220", -33.9670, 151.1020),
+    ("Penrith", "2750", -33.7507, 150.6877),
+];
+const STREETS: &[&str] = &[
+    "George St",
+    "King St",
+    "Church St",
+    "Victoria Rd",
+    "Pacific Hwy",
+    "Station St",
+    "Railway Pde",
+    "High St",
+];
+const NAMES: &[&str] = &[
+    "Harbour",
+    "Coastal",
+    "Summit",
+    "Redgum",
+    "Southern Cross",
+    "Ironbark",
+    "Wattle",
+    "Kookaburra",
+    "Parkside",
+    "Golden Gully",
+];
+const CUSTOMER_TRADES: &[&str] = &[
+    "Grocers",
+    "Hardware",
+    "Pharmacy",
+    "Outfitters",
+    "Cafe",
+    "Building Supplies",
+];
+const VENDOR_TRADES: &[&str] = &["Freight", "Logistics", "Haulage", "Transport"];
+const RECIPIENTS: &[&str] = &[
+    "Alex Nguyen",
+    "Sam Taylor",
+    "Jordan Smith",
+    "Priya Patel",
+    "Chris Wong",
+    "Morgan Kelly",
+];
+// (type, capacity in the units of the order lines)
+const VEHICLE_TYPES: &[(&str, i64)] = &[("van", 60), ("ute", 40), ("truck", 150)];
+// (name, price in cents)
+const ITEMS: &[(&str, i64)] = &[
+    ("Pallet", 1250),
+    ("Crate", 400),
+    ("Carton of paper", 3500),
+    ("Bag of cement", 1190),
+    ("Water cooler bottle", 950),
+    ("Box of tiles", 6400),
+    ("Roll of cable", 8900),
+    ("Drum of paint", 12500),
+    ("Case of produce", 2800),
+    ("Toolbox", 7900),
+];
+// Every status comes round once per five orders.
+const STATUSES: [OrderStatus; 5] = [
+    OrderStatus::Pending,
+    OrderStatus::Confirmed,
+    OrderStatus::InTransit,
+    OrderStatus::Delivered,
+    OrderStatus::Cancelled,
+];
+
+/// How much to generate. The same seed and options always give the same data.
+#[derive(Clone, Debug)]
+pub struct DemoDataOptions {
+    pub seed: u64,
+    pub customers: usize,
+    pub vendors: usize,
+    pub vehicles_per_vendor: usize,
+    pub orders: usize,
+    /// When the routes of the orders in transit leave the depot. The delivered orders went out
+    /// the day before.
+    pub departure_at: DateTime<Utc>,
+}
+
+impl DemoDataOptions {
+    pub fn new(seed: u64, departure_at: DateTime<Utc>) -> Self {
+        Self {
+            seed,
+            customers: 20,
+            vendors: 4,
+            vehicles_per_vendor: 3,
+            orders: 60,
+            departure_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DemoDataSummary {
+    pub customers: usize,
+    pub vendors: usize,
+    pub vehicles: usize,
+    pub items: usize,
+    pub orders: usize,
+    /// The main routes, each with its legs.
+    pub routes: usize,
+}
+
+/// Generates a tenant's worth of demo data on top of whatever it already has.
+///
+/// Orders go through their life cycle: the confirmed ones are planned onto the vendors' vehicles,
+/// the in transit ones are on today's routes and the delivered ones on yesterday's, which are
+/// completed, each with its proof of delivery. Orders the planner cannot fit stay confirmed.
+pub async fn generate(
+    db_pool: PgPool,
+    tenant: TenantId,
+    options: &DemoDataOptions,
+) -> Result<DemoDataSummary> {
+    let mut rng = Rng::new(options.seed);
+    let mut summary = DemoDataSummary::default();
+
+    let customers: Vec<Customer> = company_names(&mut rng, CUSTOMER_TRADES, options.customers)
+        .into_iter()
+        .map(|name| {
+            Customer::new(
+                0,
+                &name,
+                &email("orders", &name),
+                address(&mut rng),
+                Some(&phone(&mut rng)),
+            )
+        })
+        .collect();
+    let customer_ids = CustomerRepository::new(db_pool.clone(), tenant.clone())
+        .import(&customers)
+        .await?;
+    summary.customers = customer_ids.len();
+
+    let vendors: Vec<Vendor> = company_names(&mut rng, VENDOR_TRADES, options.vendors)
+        .into_iter()
+        .map(|name| {
+            Vendor::new(
+                0,
+                &name,
+                &email("dispatch", &name),
+                address(&mut rng),
+                Some(&phone(&mut rng)),
+            )
+        })
+        .collect();
+    let vendor_ids = VendorRepository::new(db_pool.clone(), tenant.clone())
+        .import(&vendors)
+        .await?;
+    summary.vendors = vendor_ids.len();
+
+    let vehicle_repo = VehicleRepository::new(db_pool.clone(), tenant.clone());
+    let mut fleet = Vec::new();
+    for vendor_id in &vendor_ids {
+        for _ in 0..options.vehicles_per_vendor {
+            let (vehicle_type, capacity) = *rng.pick(VEHICLE_TYPES);
+            let vehicle = Vehicle::new(0, *vendor_id, vehicle_type, capacity.into(), true);
+            let id = vehicle_repo.create(&vehicle).await?;
+
+            fleet.push(Vehicle::new(
+                id,
+                *vendor_id,
+                vehicle_type,
+                capacity.into(),
+                true,
+            ));
+        }
+    }
+    summary.vehicles = fleet.len();
+
+    let items: Vec<Item> = ITEMS
+        .iter()
+        .map(|(name, cents)| {
+            Item::new(
+                0,
+                name,
+                None,
+                rng.between(50, 500) as i32,
+                Decimal::new(*cents, 2),
+            )
+        })
+        .collect();
+    let item_ids = ItemRepository::new(db_pool.clone(), tenant.clone())
+        .import(&items)
+        .await?;
+    summary.items = item_ids.len();
+
+    if customer_ids.is_empty() {
+        return Ok(summary);
+    }
+
+    let order_repo = OrderRepository::new(db_pool.clone(), tenant.clone());
+    // (order, its load, where it goes)
+    let mut orders: Vec<(Order, Decimal, Coordinates)> = Vec::new();
+
+    for i in 0..options.orders {
+        let customer = rng.between(0, customer_ids.len() as u64 - 1) as usize;
+
+        let mut lines: Vec<OrderLine> = Vec::new();
+        for _ in 0..rng.between(1, 3) {
+            let item = rng.between(0, items.len() as u64 - 1) as usize;
+            if lines.iter().any(|l| l.item_id == item_ids[item]) {
+                continue;
+            }
+
+            lines.push(OrderLine {
+                item_id: item_ids[item],
+                quantity: rng.between(1, 12) as i32,
+                unit_price: items[item].unit_price,
+            });
+        }
+
+        let mut order = Order::place(customer_ids[customer]);
+        let id = order_repo.create(&order, &lines).await?;
+        order = Order::new(id, order.customer_id, order.order_status, None);
+
+        match STATUSES[i % STATUSES.len()] {
+            OrderStatus::Pending => {}
+            OrderStatus::Cancelled => order.cancel(),
+            // Planned below, the ones left behind stay confirmed.
+            _ => order.confirm(),
+        }
+        if order.order_status != OrderStatus::Pending {
+            order_repo.update_status(&order).await?;
+        }
+
+        let load = lines.iter().map(|l| Decimal::from(l.quantity)).sum();
+        let location = customers[customer].address.coordinates.unwrap();
+        orders.push((order, load, location));
+    }
+    summary.orders = orders.len();
+
+    // Yesterday's routes are done, today's are on the road.
+    let rounds = [
+        (
+            OrderStatus::Delivered,
+            options.departure_at - Duration::days(1),
+        ),
+        (OrderStatus::InTransit, options.departure_at),
+    ];
+
+    for (status, departure_at) in rounds {
+        let planned: Vec<usize> = (0..orders.len())
+            .filter(|i| STATUSES[i % STATUSES.len()] == status)
+            .collect();
+        if planned.is_empty() || fleet.is_empty() {
+            continue;
+        }
+
+        let (plan, route_ids) = plan(
+            db_pool.clone(),
+            tenant.clone(),
+            departure_at,
+            planned.iter().map(|i| &orders[*i]),
+            &fleet,
+        )
+        .await?;
+        summary.routes += route_ids.len();
+
+        let pod_repo = ProofOfDeliveryRepository::new(db_pool.clone(), tenant.clone());
+
+        for i in planned {
+            let (order, _, location) = &mut orders[i];
+            if plan.unassigned_order_ids.contains(&order.id()) {
+                continue;
+            }
+
+            // Accepting the plan assigned it.
+            order.assign();
+            order.dispatch();
+            order_repo.update_status(order).await?;
+
+            // Handed over when the driver was done at the stop, like a driver recording it.
+            if status == OrderStatus::Delivered {
+                let stop = plan
+                    .routes
+                    .iter()
+                    .flat_map(|r| &r.stops)
+                    .find(|s| s.order_id == order.id())
+                    .expect("a planned stop for each assigned order");
+                let recipient = *rng.pick(RECIPIENTS);
+                let proof_of_delivery = ProofOfDelivery::new(
+                    0,
+                    order.id(),
+                    recipient,
+                    stop.departure_at,
+                    location.latitude,
+                    location.longitude,
+                    None,
+                );
+
+                order.mark_delivered();
+                pod_repo.create(order, &proof_of_delivery).await?;
+            }
+        }
+
+        if status == OrderStatus::Delivered {
+            let vehicle_route_repo = VehicleRouteRepository::new(db_pool.clone(), tenant.clone());
+
+            for (route, route_id) in plan.routes.iter().zip(&route_ids) {
+                let mut vehicle_route =
+                    VehicleRoute::new(route.vehicle_id, *route_id, Some(route.load), None);
+                vehicle_route.complete(route.finish_at, None);
+                vehicle_route_repo.complete(&vehicle_route).await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Plans the orders onto the fleet and accepts the plan, which writes the routes and their legs.
+/// Returns the plan and the main route of each of its routes.
+async fn plan<'a>(
+    db_pool: PgPool,
+    tenant: TenantId,
+    departure_at: DateTime<Utc>,
+    orders: impl Iterator<Item = &'a (Order, Decimal, Coordinates)>,
+    fleet: &[Vehicle],
+) -> Result<(Plan, Vec<i32>)> {
+    let depot = Coordinates::new(DEPOT.0, DEPOT.1);
+
+    let problem = PlanningProblem {
+        depot,
+        departure_at,
+        average_speed_kmh: AVERAGE_SPEED_KMH,
+        stops: orders
+            .map(|(order, load, location)| Stop {
+                order_id: order.id(),
+                location: *location,
+                load: *load,
+                pickup_window: None,
+                window: None,
+                service_time: Duration::minutes(SERVICE_MINUTES),
+            })
+            .collect(),
+        vehicles: fleet
+            .iter()
+            .map(|v| PlanningVehicle {
+                vehicle_id: v.id(),
+                capacity: v.capacity,
+            })
+            .collect(),
+    };
+
+    let mut route_plan = RoutePlan::draft(depot, departure_at, plan_routes(&problem));
+    if !route_plan.can_accept() {
+        return Ok((route_plan.plan.clone(), Vec::new()));
+    }
+
+    let repo = RoutePlanRepository::new(db_pool, tenant);
+    let id = repo.create(&route_plan).await?;
+
+    route_plan = RoutePlan::new(
+        id,
+        route_plan.plan_status,
+        route_plan.depot,
+        route_plan.departure_at,
+        route_plan.plan.clone(),
+        Vec::new(),
+    );
+    route_plan.accept();
+    repo.accept(&route_plan).await?;
+
+    let accepted = repo
+        .by_id(id)
+        .await?
+        .expect("the route plan was just accepted");
+    let route_ids = accepted
+        .plan
+        .routes
+        .iter()
+        .filter_map(|r| accepted.route_id_for(r.vehicle_id))
+        .collect();
+
+    Ok((accepted.plan.clone(), route_ids))
+}
+
+/// `count` different names, shuffled. Once the combinations run out they come round again with
+/// a number.
+fn company_names(rng: &mut Rng, trades: &[&str], count: usize) -> Vec<String> {
+    let mut names: Vec<String> = NAMES
+        .iter()
+        .flat_map(|n| trades.iter().map(move |t| format!("{} {}", n, t)))
+        .collect();
+    rng.shuffle(&mut names);
+
+    (0..count)
+        .map(|i| match i / names.len() {
+            0 => names[i].clone(),
+            round => format!("{} {}", names[i % names.len()], round + 1),
+        })
+        .collect()
+}
+
+fn email(mailbox: &str, name: &str) -> String {
+    let domain: String = name
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+
+    format!("{}@{}.example.com", mailbox, domain)
+}
+
+fn phone(rng: &mut Rng) -> String {
+    format!("02 9{:03} {:04}", rng.between(0, 999), rng.between(0, 9999))
+}
+
+fn address(rng: &mut Rng) -> Address {
+    let (city, postcode, latitude, longitude) = *rng.pick(SUBURBS);
+    let line = format!("{} {}", rng.between(1, 400), rng.pick(STREETS));
+
+    // Spread the addresses around the suburb, about a kilometre either way.
+    let coordinates = Coordinates::new(latitude + rng.offset(0.01), longitude + rng.offset(0.01));
+
+    Address::new(
+        &[&line],
+        city,
+        Some("NSW"),
+        postcode,
+        "AU",
+        Some(coordinates),
+    )
+    .expect("the demo suburbs are valid addresses")
+}
+
+/// SplitMix64. Hand-rolled so the sequence for a seed never changes with a dependency upgrade.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..=high`.
+    fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+
+    fn pick<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.between(0, values.len() as u64 - 1) as usize]
+    }
+
+    /// Fisher-Yates.
+    fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.between(0, i as u64) as usize);
+        }
+    }
+
+    /// Uniform in `-max..max`.
+    fn offset(&mut self, max: f64) -> f64 {
+        (self.next() as f64 / u64::MAX as f64 * 2.0 - 1.0) * max
+    }
+}
@@ -0,0 +1,158 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Error, Object, Result, Schema};
+use sqlx::PgPool;
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::{
+    customer_queries, order_queries, route_queries, vehicle_queries, vendor_queries,
+};
+use crate::infrastructure::tenancy;
+
+mod loader;
+mod types;
+
+use loader::{CustomerId, OrderId, QueryLoader, RouteId, VehicleId, VendorId};
+use types::{Customer, Order, OrderDue, Route, Vehicle, Vendor};
+
+/// Deeper queries are refused, relations can otherwise be followed in circles.
+const MAX_DEPTH: usize = 10;
+const MAX_COMPLEXITY: usize = 500;
+
+pub type ApiSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+pub fn schema() -> ApiSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Runs `request` against the rows of `tenant`. Relations are read through a loader that lives
+/// for this request only, so each level of the query costs one database round trip per relation.
+pub async fn execute(
+    schema: &ApiSchema,
+    request: async_graphql::Request,
+    db_pool: PgPool,
+    tenant: TenantId,
+) -> async_graphql::Response {
+    let scope = tenant.clone();
+    // The loader batches on tasks of its own, which would otherwise run outside the tenant.
+    let loader = DataLoader::new(
+        QueryLoader::new(db_pool.clone(), tenant.clone()),
+        move |load| tokio::spawn(tenancy::in_tenant(scope.clone(), load)),
+    );
+
+    schema
+        .execute(request.data(Scope { db_pool, tenant }).data(loader))
+        .await
+}
+
+/// What the root fields query, the relations go through the loader.
+struct Scope {
+    db_pool: PgPool,
+    tenant: TenantId,
+}
+
+fn scope<'a>(ctx: &Context<'a>) -> &'a Scope {
+    ctx.data_unchecked::<Scope>()
+}
+
+fn data_loader<'a>(ctx: &Context<'a>) -> &'a DataLoader<QueryLoader> {
+    ctx.data_unchecked::<DataLoader<QueryLoader>>()
+}
+
+/// Logs the error and answers with a message that gives nothing away, like `AppError`.
+fn internal(e: anyhow::Error) -> Error {
+    tracing::error!("GraphQL error: {:#}", e);
+
+    Error::new("Something went wrong")
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Customers by name.
+    async fn customers(&self, ctx: &Context<'_>) -> Result<Vec<Customer>> {
+        let Scope { db_pool, tenant } = scope(ctx);
+        let customers = customer_queries::list_customers(db_pool.clone(), tenant)
+            .await
+            .map_err(internal)?;
+
+        Ok(customers.into_iter().map(Customer).collect())
+    }
+
+    async fn customer(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Customer>> {
+        Ok(data_loader(ctx)
+            .load_one(CustomerId(id))
+            .await?
+            .map(Customer))
+    }
+
+    /// Vendors by name.
+    async fn vendors(&self, ctx: &Context<'_>) -> Result<Vec<Vendor>> {
+        let Scope { db_pool, tenant } = scope(ctx);
+        let vendors = vendor_queries::list_vendors(db_pool.clone(), tenant)
+            .await
+            .map_err(internal)?;
+
+        Ok(vendors.into_iter().map(Vendor).collect())
+    }
+
+    async fn vendor(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Vendor>> {
+        Ok(data_loader(ctx).load_one(VendorId(id)).await?.map(Vendor))
+    }
+
+    /// Orders newest first, only those with `status` and `due` when given.
+    async fn orders(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        due: Option<OrderDue>,
+    ) -> Result<Vec<Order>> {
+        let Scope { db_pool, tenant } = scope(ctx);
+        let orders = order_queries::list_orders(
+            db_pool.clone(),
+            tenant,
+            status.as_deref(),
+            due.map(Into::into),
+        )
+        .await
+        .map_err(internal)?;
+
+        Ok(orders.into_iter().map(Order).collect())
+    }
+
+    async fn order(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Order>> {
+        Ok(data_loader(ctx).load_one(OrderId(id)).await?.map(Order))
+    }
+
+    /// The vehicles of every vendor.
+    async fn vehicles(&self, ctx: &Context<'_>) -> Result<Vec<Vehicle>> {
+        let Scope { db_pool, tenant } = scope(ctx);
+        let vehicles = vehicle_queries::list_vehicles(db_pool.clone(), tenant)
+            .await
+            .map_err(internal)?;
+
+        Ok(vehicles.into_iter().map(Vehicle).collect())
+    }
+
+    async fn vehicle(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Vehicle>> {
+        Ok(data_loader(ctx).load_one(VehicleId(id)).await?.map(Vehicle))
+    }
+
+    /// The trips of the accepted route plans, newest first. Their legs are under `legs`.
+    async fn routes(&self, ctx: &Context<'_>) -> Result<Vec<Route>> {
+        let Scope { db_pool, tenant } = scope(ctx);
+        let routes = route_queries::list_main_routes(db_pool.clone(), tenant)
+            .await
+            .map_err(internal)?;
+
+        Ok(routes.into_iter().map(Route).collect())
+    }
+
+    /// A trip or one of its legs.
+    async fn route(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Route>> {
+        Ok(data_loader(ctx).load_one(RouteId(id)).await?.map(Route))
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use async_graphql::dataloader::Loader;
+use async_graphql::Error;
+use sqlx::PgPool;
+
+use super::internal;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::customer_queries::list_customers_by_ids;
+use crate::infrastructure::queries::order_queries::{
+    list_order_assignments, list_order_lines, list_orders_by_customer_ids, list_orders_by_ids,
+};
+use crate::infrastructure::queries::route_queries::{
+    list_route_legs, list_routes_by_ids, list_vehicle_routes_by_route_ids,
+    list_vehicle_routes_by_vehicle_ids,
+};
+use crate::infrastructure::queries::vehicle_queries::{
+    list_vehicles_by_ids, list_vehicles_by_vendor_ids,
+};
+use crate::infrastructure::queries::vendor_queries::list_vendors_by_ids;
+use crate::models::customer_dto::CustomerDto;
+use crate::models::order_dto::{OrderAssignmentDto, OrderDto, OrderLineDto};
+use crate::models::route_dto::DispatchedRouteDto;
+use crate::models::settlement_dto::VehicleRouteDto;
+use crate::models::vehicle_dto::VehicleDto;
+use crate::models::vendor_dto::VendorDto;
+
+/// Reads the relations asked for by the fields resolved at the same level of a query with one
+/// query each. The key type says what is loaded.
+pub struct QueryLoader {
+    db_pool: PgPool,
+    tenant: TenantId,
+}
+
+impl QueryLoader {
+    pub fn new(db_pool: PgPool, tenant: TenantId) -> Self {
+        QueryLoader { db_pool, tenant }
+    }
+}
+
+// Loads the records with the given ids.
+macro_rules! by_id {
+    ($key:ident, $dto:ty, $query:path) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $key(pub i32);
+
+        impl Loader<$key> for QueryLoader {
+            type Value = $dto;
+            type Error = Error;
+
+            async fn load(&self, keys: &[$key]) -> Result<HashMap<$key, $dto>, Error> {
+                let ids: Vec<i32> = keys.iter().map(|key| key.0).collect();
+                let rows = $query(self.db_pool.clone(), &self.tenant, &ids)
+                    .await
+                    .map_err(internal)?;
+
+                Ok(rows.into_iter().map(|row| ($key(row.id), row)).collect())
+            }
+        }
+    };
+}
+
+// Loads the records related to each of the given ids, keeping the order of the query.
+macro_rules! related {
+    ($key:ident, $dto:ty, $query:path, $field:ident) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $key(pub i32);
+
+        impl Loader<$key> for QueryLoader {
+            type Value = Vec<$dto>;
+            type Error = Error;
+
+            async fn load(&self, keys: &[$key]) -> Result<HashMap<$key, Vec<$dto>>, Error> {
+                let ids: Vec<i32> = keys.iter().map(|key| key.0).collect();
+                let rows = $query(self.db_pool.clone(), &self.tenant, &ids)
+                    .await
+                    .map_err(internal)?;
+
+                let mut related: HashMap<$key, Vec<$dto>> = HashMap::new();
+                for row in rows {
+                    // Takes optional fields too, the rows loaded by one always have it.
+                    if let Some(id) = Option::<i32>::from(row.$field) {
+                        related.entry($key(id)).or_default().push(row);
+                    }
+                }
+
+                Ok(related)
+            }
+        }
+    };
+}
+
+by_id!(CustomerId, CustomerDto, list_customers_by_ids);
+by_id!(VendorId, VendorDto, list_vendors_by_ids);
+by_id!(OrderId, OrderDto, list_orders_by_ids);
+by_id!(VehicleId, VehicleDto, list_vehicles_by_ids);
+by_id!(RouteId, DispatchedRouteDto, list_routes_by_ids);
+
+related!(
+    CustomerOrders,
+    OrderDto,
+    list_orders_by_customer_ids,
+    customer_id
+);
+related!(OrderLines, OrderLineDto, list_order_lines, order_id);
+related!(
+    OrderAssignments,
+    OrderAssignmentDto,
+    list_order_assignments,
+    order_id
+);
+related!(
+    VendorVehicles,
+    VehicleDto,
+    list_vehicles_by_vendor_ids,
+    vendor_id
+);
+related!(
+    RouteLegs,
+    DispatchedRouteDto,
+    list_route_legs,
+    main_route_id
+);
+related!(
+    VehicleRoutes,
+    VehicleRouteDto,
+    list_vehicle_routes_by_vehicle_ids,
+    vehicle_id
+);
+related!(
+    RouteVehicles,
+    VehicleRouteDto,
+    list_vehicle_routes_by_route_ids,
+    route_id
+);
@@ -0,0 +1,397 @@
+use async_graphql::{Context, Enum, Object, Result};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveTime, Utc};
+use rust_decimal::Decimal;
+
+use super::data_loader;
+use super::loader::{
+    CustomerId, CustomerOrders, OrderAssignments, OrderLines, RouteId, RouteLegs, RouteVehicles,
+    VehicleId, VehicleRoutes, VendorId, VendorVehicles,
+};
+use crate::models::address_dto::AddressDto;
+use crate::models::customer_dto::CustomerDto;
+use crate::models::order_dto::{
+    self, ItemDto, OrderAssignmentDto, OrderDto, OrderLineDto, OrderWindowDto,
+};
+use crate::models::route_dto::DispatchedRouteDto;
+use crate::models::settlement_dto::VehicleRouteDto;
+use crate::models::vehicle_dto::VehicleDto;
+use crate::models::vendor_dto::VendorDto;
+
+pub struct Address(AddressDto);
+
+#[Object]
+impl Address {
+    async fn lines(&self) -> &[String] {
+        &self.0.lines
+    }
+
+    async fn city(&self) -> &str {
+        &self.0.city
+    }
+
+    async fn region(&self) -> Option<&str> {
+        self.0.region.as_deref()
+    }
+
+    async fn postcode(&self) -> &str {
+        &self.0.postcode
+    }
+
+    async fn country_code(&self) -> &str {
+        &self.0.country_code
+    }
+
+    /// Unset until the address is geocoded.
+    async fn latitude(&self) -> Option<f64> {
+        self.0.latitude
+    }
+
+    async fn longitude(&self) -> Option<f64> {
+        self.0.longitude
+    }
+}
+
+pub struct Customer(pub CustomerDto);
+
+#[Object]
+impl Customer {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn contact_number(&self) -> Option<&str> {
+        self.0.contact_number.as_deref()
+    }
+
+    async fn address(&self) -> Address {
+        Address(self.0.address.clone())
+    }
+
+    /// Newest first.
+    async fn orders(&self, ctx: &Context<'_>) -> Result<Vec<Order>> {
+        let orders = data_loader(ctx).load_one(CustomerOrders(self.0.id)).await?;
+
+        Ok(orders.unwrap_or_default().into_iter().map(Order).collect())
+    }
+}
+
+pub struct Vendor(pub VendorDto);
+
+#[Object]
+impl Vendor {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn contact_number(&self) -> Option<&str> {
+        self.0.contact_number.as_deref()
+    }
+
+    async fn address(&self) -> Address {
+        Address(self.0.address.clone())
+    }
+
+    async fn vehicles(&self, ctx: &Context<'_>) -> Result<Vec<Vehicle>> {
+        let vehicles = data_loader(ctx).load_one(VendorVehicles(self.0.id)).await?;
+
+        Ok(vehicles
+            .unwrap_or_default()
+            .into_iter()
+            .map(Vehicle)
+            .collect())
+    }
+}
+
+/// Still to be collected or delivered, with a window closing today or closed already.
+#[derive(Clone, Copy, PartialEq, Eq, Enum)]
+pub enum OrderDue {
+    Today,
+    Late,
+}
+
+impl From<OrderDue> for order_dto::OrderDue {
+    fn from(due: OrderDue) -> Self {
+        match due {
+            OrderDue::Today => order_dto::OrderDue::Today,
+            OrderDue::Late => order_dto::OrderDue::Late,
+        }
+    }
+}
+
+/// A pickup or delivery window at the local time of the order's sites.
+pub struct OrderWindow(OrderWindowDto);
+
+#[Object]
+impl OrderWindow {
+    async fn earliest(&self) -> DateTime<FixedOffset> {
+        self.0.earliest
+    }
+
+    async fn latest(&self) -> DateTime<FixedOffset> {
+        self.0.latest
+    }
+}
+
+pub struct Order(pub OrderDto);
+
+#[Object]
+impl Order {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn status(&self) -> &str {
+        &self.0.status
+    }
+
+    async fn created_at(&self) -> Option<NaiveDateTime> {
+        self.0.created_at
+    }
+
+    /// The IANA time zone of the order's sites, the windows are given in it.
+    async fn time_zone(&self) -> Option<&str> {
+        self.0.time_zone.as_deref()
+    }
+
+    async fn pickup_window(&self) -> Option<OrderWindow> {
+        self.0.pickup_window.map(OrderWindow)
+    }
+
+    async fn delivery_window(&self) -> Option<OrderWindow> {
+        self.0.delivery_window.map(OrderWindow)
+    }
+
+    async fn customer(&self, ctx: &Context<'_>) -> Result<Option<Customer>> {
+        let customer = data_loader(ctx)
+            .load_one(CustomerId(self.0.customer_id))
+            .await?;
+
+        Ok(customer.map(Customer))
+    }
+
+    /// The items ordered, by item name.
+    async fn lines(&self, ctx: &Context<'_>) -> Result<Vec<OrderLine>> {
+        let lines = data_loader(ctx).load_one(OrderLines(self.0.id)).await?;
+
+        Ok(lines
+            .unwrap_or_default()
+            .into_iter()
+            .map(OrderLine)
+            .collect())
+    }
+
+    /// The vehicles and routes the accepted route plans put the order on.
+    async fn assignments(&self, ctx: &Context<'_>) -> Result<Vec<OrderAssignment>> {
+        let assignments = data_loader(ctx)
+            .load_one(OrderAssignments(self.0.id))
+            .await?;
+
+        Ok(assignments
+            .unwrap_or_default()
+            .into_iter()
+            .map(OrderAssignment)
+            .collect())
+    }
+}
+
+pub struct OrderLine(OrderLineDto);
+
+#[Object]
+impl OrderLine {
+    async fn item(&self) -> Item {
+        Item(self.0.item.clone())
+    }
+
+    async fn quantity(&self) -> i32 {
+        self.0.quantity
+    }
+
+    /// The price the item was ordered at.
+    async fn unit_price(&self) -> Decimal {
+        self.0.unit_price
+    }
+}
+
+pub struct Item(ItemDto);
+
+#[Object]
+impl Item {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn quantity_available(&self) -> i32 {
+        self.0.quantity_available
+    }
+
+    /// The current list price.
+    async fn unit_price(&self) -> Decimal {
+        self.0.unit_price
+    }
+}
+
+pub struct OrderAssignment(OrderAssignmentDto);
+
+#[Object]
+impl OrderAssignment {
+    async fn vehicle(&self, ctx: &Context<'_>) -> Result<Option<Vehicle>> {
+        let vehicle = data_loader(ctx)
+            .load_one(VehicleId(self.0.vehicle_id))
+            .await?;
+
+        Ok(vehicle.map(Vehicle))
+    }
+
+    /// The trip the order is delivered on.
+    async fn route(&self, ctx: &Context<'_>) -> Result<Option<Route>> {
+        let route = data_loader(ctx).load_one(RouteId(self.0.route_id)).await?;
+
+        Ok(route.map(Route))
+    }
+}
+
+pub struct Vehicle(pub VehicleDto);
+
+#[Object]
+impl Vehicle {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn vehicle_type(&self) -> &str {
+        &self.0.vehicle_type
+    }
+
+    async fn capacity(&self) -> Decimal {
+        self.0.capacity
+    }
+
+    async fn availability_status(&self) -> bool {
+        self.0.availability_status
+    }
+
+    async fn vendor(&self, ctx: &Context<'_>) -> Result<Option<Vendor>> {
+        let vendor = data_loader(ctx)
+            .load_one(VendorId(self.0.vendor_id))
+            .await?;
+
+        Ok(vendor.map(Vendor))
+    }
+
+    /// The trips the vehicle was dispatched on, newest first.
+    async fn routes(&self, ctx: &Context<'_>) -> Result<Vec<VehicleRoute>> {
+        let routes = data_loader(ctx).load_one(VehicleRoutes(self.0.id)).await?;
+
+        Ok(routes
+            .unwrap_or_default()
+            .into_iter()
+            .map(VehicleRoute)
+            .collect())
+    }
+}
+
+/// A vehicle dispatched on a trip.
+pub struct VehicleRoute(VehicleRouteDto);
+
+#[Object]
+impl VehicleRoute {
+    async fn load(&self) -> Option<Decimal> {
+        self.0.load
+    }
+
+    /// Unset until the vehicle has driven the route.
+    async fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.completed_at
+    }
+
+    async fn vehicle(&self, ctx: &Context<'_>) -> Result<Option<Vehicle>> {
+        let vehicle = data_loader(ctx)
+            .load_one(VehicleId(self.0.vehicle_id))
+            .await?;
+
+        Ok(vehicle.map(Vehicle))
+    }
+
+    async fn route(&self, ctx: &Context<'_>) -> Result<Option<Route>> {
+        let route = data_loader(ctx).load_one(RouteId(self.0.route_id)).await?;
+
+        Ok(route.map(Route))
+    }
+}
+
+pub struct Route(pub DispatchedRouteDto);
+
+#[Object]
+impl Route {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn origin(&self) -> &str {
+        &self.0.origin
+    }
+
+    async fn destination(&self) -> &str {
+        &self.0.destination
+    }
+
+    /// In kilometres.
+    async fn distance(&self) -> Decimal {
+        self.0.distance
+    }
+
+    async fn estimated_travel_time(&self) -> NaiveTime {
+        self.0.estimated_travel_time
+    }
+
+    /// The trip this is a leg of, unset for the trip itself.
+    async fn main_route(&self, ctx: &Context<'_>) -> Result<Option<Route>> {
+        let Some(id) = self.0.main_route_id else {
+            return Ok(None);
+        };
+
+        Ok(data_loader(ctx).load_one(RouteId(id)).await?.map(Route))
+    }
+
+    /// The legs of the trip in the order they are driven, none for a leg.
+    async fn legs(&self, ctx: &Context<'_>) -> Result<Vec<Route>> {
+        let legs = data_loader(ctx).load_one(RouteLegs(self.0.id)).await?;
+
+        Ok(legs.unwrap_or_default().into_iter().map(Route).collect())
+    }
+
+    /// The vehicles dispatched on the trip.
+    async fn vehicles(&self, ctx: &Context<'_>) -> Result<Vec<VehicleRoute>> {
+        let vehicles = data_loader(ctx).load_one(RouteVehicles(self.0.id)).await?;
+
+        Ok(vehicles
+            .unwrap_or_default()
+            .into_iter()
+            .map(VehicleRoute)
+            .collect())
+    }
+}
@@ -0,0 +1,253 @@
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{TimeDelta, Utc};
+use futures::{stream, FutureExt, StreamExt};
+use http::{
+    header::{CONTENT_TYPE, LOCATION},
+    HeaderName, HeaderValue, Method, StatusCode,
+};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::Instrument;
+
+use super::auth::RequireAuth;
+use super::config::Config;
+use super::utils::http_utils::AppError;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::idempotency::{self, Claim, SavedResponse};
+use crate::infrastructure::tenancy;
+
+/// Sent by clients with a POST they may retry, the same key for each attempt.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on a response sent again for a repeated key.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LENGTH: usize = 255;
+
+// The largest upload the API takes, see the imports and proof of delivery routes. Responses are
+// only saved up to the same size.
+const MAX_BODY_BYTES: usize = 20 * 1024 * 1024;
+
+// Other response headers are set again by the middleware around this one.
+const SAVED_HEADERS: [HeaderName; 2] = [CONTENT_TYPE, LOCATION];
+
+/// Answers a POST carrying an `Idempotency-Key` once per caller and key: a repeat of the same
+/// request gets the saved response, a different request with the key gets 409, as does a repeat
+/// while the first is still being answered. Server errors are not saved, so the client can retry.
+///
+/// The key is claimed and answered in a task of its own, so the response is still saved when the
+/// client goes away mid-request, and the key is released when the handler panics, rather than
+/// leaving retries in progress until the key expires.
+pub async fn replay_idempotent(
+    State(db_pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    claims: RequireAuth,
+    tenant: TenantId,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if request.method() != Method::POST {
+        return Ok(next.run(request).await);
+    }
+    let key = match request.headers().get(IDEMPOTENCY_KEY) {
+        None => return Ok(next.run(request).await),
+        Some(key) => match key.to_str() {
+            Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+            _ => {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Idempotency-Key must be 1 to 255 visible characters",
+                )
+                    .into_response())
+            }
+        },
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return Ok(StatusCode::PAYLOAD_TOO_LARGE.into_response());
+    };
+
+    let fingerprint = Sha256::new()
+        .chain_update(parts.method.as_str())
+        .chain_update(b"\n")
+        .chain_update(parts.uri.to_string())
+        .chain_update(b"\n")
+        .chain_update(&body)
+        .finalize();
+
+    let key_ttl = TimeDelta::from_std(config.idempotency.key_ttl).unwrap_or(TimeDelta::MAX);
+    let expired_before = Utc::now() - key_ttl;
+
+    let principal = claims.principal().to_string();
+
+    let request = Request::from_parts(parts, Body::from(body));
+    let claim_and_answer = {
+        let tenant = tenant.clone();
+        async move {
+            let claim = idempotency::claim(
+                &db_pool,
+                &tenant,
+                &principal,
+                &key,
+                &fingerprint,
+                expired_before,
+            )
+            .await?;
+
+            Ok::<_, AppError>(match claim {
+                Claim::Claimed => answer(db_pool, tenant, principal, key, request, next).await,
+                Claim::InProgress => (
+                    StatusCode::CONFLICT,
+                    "A request with this Idempotency-Key is still being answered",
+                )
+                    .into_response(),
+                Claim::Mismatch => (
+                    StatusCode::CONFLICT,
+                    "This Idempotency-Key was used for a different request",
+                )
+                    .into_response(),
+                Claim::Replay(saved) => replay(saved),
+            })
+        }
+    };
+
+    tokio::spawn(tenancy::in_tenant(tenant, claim_and_answer).in_current_span()).await?
+}
+
+/// Runs the request of a claimed key, then saves its response under the key or releases the key.
+async fn answer(
+    db_pool: PgPool,
+    tenant: TenantId,
+    principal: String,
+    key: String,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = AssertUnwindSafe(next.run(request)).catch_unwind().await;
+
+    let release = || async {
+        if let Err(e) = idempotency::release(&db_pool, &tenant, &principal, &key).await {
+            tracing::warn!("Cannot release idempotency key: {:#}", e);
+        }
+    };
+
+    let (parts, body) = match response {
+        Ok(response) if !response.status().is_server_error() => response.into_parts(),
+        response => {
+            release().await;
+            // A panic was reported by the panic hook, the client gets a plain server error.
+            return response.unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
+        }
+    };
+
+    let body = match buffer(body, MAX_BODY_BYTES).await {
+        Ok(Buffered::Whole(body)) => body,
+        Ok(Buffered::TooLarge(body)) => {
+            tracing::warn!(
+                "Not saving a response over {} bytes for an idempotency key",
+                MAX_BODY_BYTES
+            );
+            release().await;
+            return Response::from_parts(parts, body);
+        }
+        Err(e) => {
+            release().await;
+            return AppError::from(e).into_response();
+        }
+    };
+
+    let saved = SavedResponse {
+        status: parts.status.as_u16(),
+        headers: SAVED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = parts.headers.get(name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect(),
+        body: body.to_vec(),
+    };
+
+    // The work is done, the client gets its response even if a retry would not.
+    if let Err(e) = idempotency::save(&db_pool, &tenant, &principal, &key, &saved).await {
+        tracing::error!("Cannot save the response for an idempotency key: {:#}", e);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+enum Buffered {
+    Whole(Bytes),
+    /// The part read so far followed by the rest, to send on without keeping it.
+    TooLarge(Body),
+}
+
+/// Reads `body` into memory unless it turns out longer than `limit` bytes.
+async fn buffer(body: Body, limit: usize) -> Result<Buffered, axum::Error> {
+    let mut rest = body.into_data_stream();
+    let mut chunks = Vec::new();
+    let mut length = 0;
+
+    while let Some(chunk) = rest.next().await {
+        let chunk = chunk?;
+        length += chunk.len();
+        chunks.push(chunk);
+
+        if length > limit {
+            let read = stream::iter(chunks.into_iter().map(Ok));
+            return Ok(Buffered::TooLarge(Body::from_stream(read.chain(rest))));
+        }
+    }
+
+    Ok(Buffered::Whole(chunks.concat().into()))
+}
+
+fn replay(saved: SavedResponse) -> Response {
+    let mut response = Response::new(Body::from(saved.body));
+
+    *response.status_mut() =
+        StatusCode::from_u16(saved.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    let headers = response.headers_mut();
+    for (name, value) in saved.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn buffers_a_body_up_to_the_limit() {
+        let Buffered::Whole(body) = buffer(Body::from("0123"), 4).await.unwrap() else {
+            panic!("a body of the limit is kept");
+        };
+        assert_eq!(body, "0123");
+    }
+
+    #[tokio::test]
+    async fn passes_on_a_longer_body_whole() {
+        let chunks = ["01", "23", "45"].map(|chunk| Ok::<_, axum::Error>(Bytes::from(chunk)));
+        let body = Body::from_stream(stream::iter(chunks));
+
+        let Buffered::TooLarge(body) = buffer(body, 3).await.unwrap() else {
+            panic!("a body over the limit is passed on");
+        };
+        assert_eq!(to_bytes(body, usize::MAX).await.unwrap(), "012345");
+    }
+}
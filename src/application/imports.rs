@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use axum::async_trait;
+use csv::{ReaderBuilder, StringRecord, Trim};
+use sqlx::PgPool;
+use validator::{Validate, ValidationErrors};
+
+use crate::domain::aggregates::import::{Import, ImportEntity};
+use crate::domain::aggregates::{customer::Customer, vendor::Vendor};
+use crate::domain::value_objects::address::Address;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::{
+    customer_queries::list_customers_by_email, vendor_queries::list_vendors_by_email,
+};
+use crate::infrastructure::repositories::customer_repository::{
+    CustomerRepository, Repository as _,
+};
+use crate::infrastructure::repositories::import_repository::{ImportRepository, Repository as _};
+use crate::infrastructure::repositories::vendor_repository::{Repository as _, VendorRepository};
+use crate::models::address_dto::AddressDto;
+use crate::models::customer_dto::CreateCustomerRequest;
+use crate::models::import_dto::{
+    ImportAction, ImportMode, ImportReportDto, ImportRowDto, PartyCsvRow,
+};
+use crate::models::vendor_dto::CreateVendorRequest;
+
+const MAX_ROWS: usize = 10_000;
+
+pub enum ImportOutcome {
+    /// The file cannot be imported at all, e.g. a column is missing. Nothing was saved.
+    Rejected(String),
+    /// Records matched by email changed while the file was imported. Nothing was saved, importing
+    /// the file again matches it afresh.
+    Conflict(String),
+    Report(ImportReportDto),
+}
+
+/// A customer or vendor read from a valid row.
+struct Party {
+    id: i32,
+    name: String,
+    email: String,
+    address: Address,
+    contact_number: Option<String>,
+}
+
+/// An existing customer or vendor sharing an email with the file.
+struct Existing {
+    id: i32,
+    email: String,
+    address: AddressDto,
+}
+
+// Customers and vendors share the import handler, these validate and save the rows.
+#[async_trait]
+trait Parties: Send + 'static {
+    const ENTITY: ImportEntity;
+
+    /// Checks a row with the same rules as the create endpoint.
+    fn validate(row: &PartyCsvRow) -> Result<(), ValidationErrors>;
+
+    async fn existing(
+        db_pool: PgPool,
+        tenant: &TenantId,
+        emails: &[String],
+    ) -> Result<Vec<Existing>>;
+
+    /// Returns `None` when the records matched by email have changed, see the repositories.
+    async fn import(
+        db_pool: PgPool,
+        tenant: TenantId,
+        parties: &[Party],
+    ) -> Result<Option<Vec<i32>>>;
+}
+
+struct Customers;
+
+#[async_trait]
+impl Parties for Customers {
+    const ENTITY: ImportEntity = ImportEntity::Customers;
+
+    fn validate(row: &PartyCsvRow) -> Result<(), ValidationErrors> {
+        CreateCustomerRequest {
+            name: row.name.clone(),
+            email: row.email.clone(),
+            address: row.address(),
+            contact_number: row.contact_number.clone(),
+        }
+        .validate()
+    }
+
+    async fn existing(
+        db_pool: PgPool,
+        tenant: &TenantId,
+        emails: &[String],
+    ) -> Result<Vec<Existing>> {
+        let customers = list_customers_by_email(db_pool, tenant, emails).await?;
+
+        Ok(customers
+            .into_iter()
+            .map(|c| Existing {
+                id: c.id,
+                email: c.email,
+                address: c.address,
+            })
+            .collect())
+    }
+
+    async fn import(
+        db_pool: PgPool,
+        tenant: TenantId,
+        parties: &[Party],
+    ) -> Result<Option<Vec<i32>>> {
+        let customers: Vec<Customer> = parties
+            .iter()
+            .map(|p| {
+                Customer::new(
+                    p.id,
+                    &p.name,
+                    &p.email,
+                    p.address.clone(),
+                    p.contact_number.as_deref(),
+                )
+            })
+            .collect();
+
+        CustomerRepository::new(db_pool, tenant)
+            .import_by_email(&customers)
+            .await
+    }
+}
+
+struct Vendors;
+
+#[async_trait]
+impl Parties for Vendors {
+    const ENTITY: ImportEntity = ImportEntity::Vendors;
+
+    fn validate(row: &PartyCsvRow) -> Result<(), ValidationErrors> {
+        CreateVendorRequest {
+            name: row.name.clone(),
+            email: row.email.clone(),
+            address: row.address(),
+            contact_number: row.contact_number.clone(),
+        }
+        .validate()
+    }
+
+    async fn existing(
+        db_pool: PgPool,
+        tenant: &TenantId,
+        emails: &[String],
+    ) -> Result<Vec<Existing>> {
+        let vendors = list_vendors_by_email(db_pool, tenant, emails).await?;
+
+        Ok(vendors
+            .into_iter()
+            .map(|v| Existing {
+                id: v.id,
+                email: v.email,
+                address: v.address,
+            })
+            .collect())
+    }
+
+    async fn import(
+        db_pool: PgPool,
+        tenant: TenantId,
+        parties: &[Party],
+    ) -> Result<Option<Vec<i32>>> {
+        let vendors: Vec<Vendor> = parties
+            .iter()
+            .map(|p| {
+                Vendor::new(
+                    p.id,
+                    &p.name,
+                    &p.email,
+                    p.address.clone(),
+                    p.contact_number.as_deref(),
+                )
+            })
+            .collect();
+
+        VendorRepository::new(db_pool, tenant)
+            .import_by_email(&vendors)
+            .await
+    }
+}
+
+/// Validates a CSV file row by row and, in commit mode, creates or updates by email every valid row
+/// in one transaction and records the import. Rows are not geocoded here, the address geocoding
+/// job locates them.
+pub async fn import_csv(
+    entity: ImportEntity,
+    db_pool: PgPool,
+    tenant: TenantId,
+    csv: &[u8],
+    mode: ImportMode,
+    imported_by: &str,
+) -> Result<ImportOutcome> {
+    match entity {
+        ImportEntity::Customers => {
+            import::<Customers>(db_pool, tenant, csv, mode, imported_by).await
+        }
+        ImportEntity::Vendors => import::<Vendors>(db_pool, tenant, csv, mode, imported_by).await,
+    }
+}
+
+async fn import<P: Parties>(
+    db_pool: PgPool,
+    tenant: TenantId,
+    csv: &[u8],
+    mode: ImportMode,
+    imported_by: &str,
+) -> Result<ImportOutcome> {
+    let mut reader = ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(csv);
+
+    let columns = match reader.headers() {
+        Ok(columns) => columns.clone(),
+        Err(e) => {
+            return Ok(ImportOutcome::Rejected(format!(
+                "The header row cannot be read: {}.",
+                e
+            )))
+        }
+    };
+
+    let missing: Vec<&str> = PartyCsvRow::REQUIRED_COLUMNS
+        .into_iter()
+        .filter(|c| !columns.iter().any(|h| h == *c))
+        .collect();
+    if !missing.is_empty() {
+        return Ok(ImportOutcome::Rejected(format!(
+            "Missing column(s): {}.",
+            missing.join(", ")
+        )));
+    }
+
+    let email_column = columns.iter().position(|h| h == "email");
+
+    let mut rows: Vec<(StringRecord, ImportRowDto)> = Vec::new();
+    let mut parties: Vec<(usize, Party)> = Vec::new();
+    let mut lines_by_email: HashMap<String, u64> = HashMap::new();
+
+    for (index, record) in reader.records().enumerate() {
+        if index == MAX_ROWS {
+            return Ok(ImportOutcome::Rejected(format!(
+                "An import cannot have more than {} rows.",
+                MAX_ROWS
+            )));
+        }
+
+        // The header is line 1.
+        let line = index as u64 + 2;
+
+        let (record, row) = match record {
+            Ok(record) => {
+                let row = record.deserialize::<PartyCsvRow>(Some(&columns));
+                (record, row.map_err(|e| vec![e.to_string()]))
+            }
+            Err(e) => (StringRecord::new(), Err(vec![e.to_string()])),
+        };
+
+        let row = row.and_then(|row| {
+            P::validate(&row).map_err(|e| validation_messages(&e))?;
+
+            let email = row.email.to_lowercase();
+            if let Some(first) = lines_by_email.get(&email) {
+                return Err(vec![format!("The email is already on line {}.", first)]);
+            }
+            lines_by_email.insert(email, line);
+
+            Ok(row)
+        });
+
+        match row {
+            Ok(row) => {
+                let address = row.address().to_domain()?;
+
+                rows.push((
+                    record,
+                    ImportRowDto {
+                        line,
+                        email: Some(row.email.clone()),
+                        action: ImportAction::Create,
+                        id: None,
+                        errors: Vec::new(),
+                    },
+                ));
+                parties.push((
+                    rows.len() - 1,
+                    Party {
+                        id: 0,
+                        name: row.name,
+                        email: row.email,
+                        address,
+                        contact_number: row.contact_number,
+                    },
+                ));
+            }
+            Err(errors) => rows.push((
+                record.clone(),
+                ImportRowDto {
+                    line,
+                    email: email_column
+                        .and_then(|i| record.get(i))
+                        .filter(|e| !e.is_empty())
+                        .map(str::to_string),
+                    action: ImportAction::Reject,
+                    id: None,
+                    errors,
+                },
+            )),
+        }
+    }
+
+    let emails: Vec<String> = parties.iter().map(|(_, p)| p.email.clone()).collect();
+
+    let mut existing: HashMap<String, Vec<Existing>> = HashMap::new();
+    for e in P::existing(db_pool.clone(), &tenant, &emails).await? {
+        existing.entry(e.email.to_lowercase()).or_default().push(e);
+    }
+
+    // Rows updating an existing record take its id and keep its coordinates when the address
+    // is unchanged, rows matching several records are ambiguous.
+    let mut accepted: Vec<(usize, Party)> = Vec::with_capacity(parties.len());
+    for (index, mut party) in parties {
+        match existing.get(&party.email.to_lowercase()).map(Vec::as_slice) {
+            None | Some([]) => accepted.push((index, party)),
+            Some([e]) => {
+                if party.address.coordinates.is_none() {
+                    if let Ok(current) = e.address.to_domain() {
+                        if current.with_coordinates(None) == party.address {
+                            party.address = current;
+                        }
+                    }
+                }
+                party.id = e.id;
+                rows[index].1.action = ImportAction::Update;
+                rows[index].1.id = Some(e.id);
+                accepted.push((index, party));
+            }
+            Some(matches) => {
+                let row = &mut rows[index].1;
+                row.action = ImportAction::Reject;
+                row.errors.push(format!(
+                    "The email matches {} existing {}.",
+                    matches.len(),
+                    P::ENTITY
+                ));
+            }
+        }
+    }
+
+    let count =
+        |action: ImportAction| rows.iter().filter(|(_, r)| r.action == action).count() as i32;
+    let (created, updated, rejected) = (
+        count(ImportAction::Create),
+        count(ImportAction::Update),
+        count(ImportAction::Reject),
+    );
+
+    let mut report = ImportReportDto {
+        mode,
+        import_id: None,
+        total_rows: rows.len() as i32,
+        created,
+        updated,
+        rejected,
+        error_file: None,
+        rows: Vec::new(),
+    };
+
+    if mode == ImportMode::DryRun {
+        report.rows = rows.into_iter().map(|(_, r)| r).collect();
+
+        return Ok(ImportOutcome::Report(report));
+    }
+
+    let (indexes, parties): (Vec<usize>, Vec<Party>) = accepted.into_iter().unzip();
+
+    if !parties.is_empty() {
+        let Some(ids) = P::import(db_pool.clone(), tenant.clone(), &parties).await? else {
+            return Ok(ImportOutcome::Conflict(format!(
+                "Some of the {} matched by email have just changed, import the file again.",
+                P::ENTITY
+            )));
+        };
+        for (index, id) in indexes.into_iter().zip(ids) {
+            rows[index].1.id = Some(id);
+        }
+    }
+
+    let error_file = match rejected {
+        0 => None,
+        _ => Some(error_file(&columns, &rows)?),
+    };
+
+    let import = Import::new(
+        0,
+        P::ENTITY,
+        imported_by,
+        created,
+        updated,
+        rejected,
+        error_file,
+    );
+
+    let id = ImportRepository::new(db_pool, tenant)
+        .create(&import)
+        .await?;
+
+    report.import_id = Some(id);
+    report.error_file = import
+        .error_file
+        .as_ref()
+        .map(|_| format!("/v1/api/imports/{}/errors", id));
+    report.rows = rows.into_iter().map(|(_, r)| r).collect();
+
+    Ok(ImportOutcome::Report(report))
+}
+
+fn error_file(columns: &StringRecord, rows: &[(StringRecord, ImportRowDto)]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer.write_record(columns.iter().chain(["line", "errors"]))?;
+
+    for (record, row) in rows
+        .iter()
+        .filter(|(_, r)| r.action == ImportAction::Reject)
+    {
+        let line = row.line.to_string();
+        let errors = row.errors.join("; ");
+
+        // Pads short rows so every line has the columns of the header.
+        let fields = (0..columns.len()).map(|i| record.get(i).unwrap_or_default());
+
+        writer.write_record(fields.chain([line.as_str(), errors.as_str()]))?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+pub fn validation_messages(errors: &ValidationErrors) -> Vec<String> {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+
+    fields
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |e| {
+                let message = e
+                    .message
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| e.code.to_string());
+
+                match field {
+                    "__all__" => message,
+                    field => format!("{}: {}", field, message),
+                }
+            })
+        })
+        .collect()
+}
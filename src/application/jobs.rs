@@ -0,0 +1,3 @@
+pub mod address_geocoding;
+pub mod invoicing;
+pub mod vehicle_position_retention;
@@ -0,0 +1,138 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::services::geocoder::Geocoder;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::tenant_queries::list_tenants_without_coordinates;
+use crate::infrastructure::repositories::{
+    customer_repository::{CustomerRepository, Repository as _},
+    vendor_repository::{Repository as _, VendorRepository},
+};
+use crate::infrastructure::tenancy;
+
+const INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Default)]
+pub struct GeocodingSummary {
+    pub customers_located: u64,
+    pub vendors_located: u64,
+    pub unresolved: u64,
+}
+
+/// Geocodes customers and vendors saved without coordinates, e.g. rows migrated from the legacy
+/// free text address or saved while the provider was down. Works through one tenant at a time.
+pub async fn run(db_pool: PgPool, geocoder: &dyn Geocoder) -> Result<GeocodingSummary> {
+    let mut summary = GeocodingSummary::default();
+
+    let tenants =
+        tenancy::across_tenants(list_tenants_without_coordinates(db_pool.clone())).await?;
+
+    for tenant in tenants {
+        tenancy::in_tenant(
+            tenant.clone(),
+            run_tenant(db_pool.clone(), tenant, geocoder, &mut summary),
+        )
+        .await?;
+    }
+
+    Ok(summary)
+}
+
+async fn run_tenant(
+    db_pool: PgPool,
+    tenant: TenantId,
+    geocoder: &dyn Geocoder,
+    summary: &mut GeocodingSummary,
+) -> Result<()> {
+    let customers = CustomerRepository::new(db_pool.clone(), tenant.clone());
+    let mut after_id = 0;
+
+    loop {
+        let ids = customers
+            .ids_without_coordinates(after_id, BATCH_SIZE)
+            .await?;
+
+        for id in &ids {
+            let mut customer = customers.by_id(*id).await?;
+
+            match geocoder.geocode(&customer.address).await? {
+                Some(coordinates) => {
+                    customer.locate(coordinates);
+                    if customers.update_coordinates(&customer).await? {
+                        summary.customers_located += 1;
+                    }
+                }
+                None => summary.unresolved += 1,
+            }
+        }
+
+        match ids.last() {
+            Some(last) => after_id = *last,
+            None => break,
+        }
+    }
+
+    let vendors = VendorRepository::new(db_pool, tenant);
+    let mut after_id = 0;
+
+    loop {
+        let ids = vendors
+            .ids_without_coordinates(after_id, BATCH_SIZE)
+            .await?;
+
+        for id in &ids {
+            let mut vendor = vendors.by_id(*id).await?;
+
+            match geocoder.geocode(&vendor.address).await? {
+                Some(coordinates) => {
+                    vendor.locate(coordinates);
+                    if vendors.update_coordinates(&vendor).await? {
+                        summary.vendors_located += 1;
+                    }
+                }
+                None => summary.unresolved += 1,
+            }
+        }
+
+        match ids.last() {
+            Some(last) => after_id = *last,
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs every six hours until `shutdown`, which also abandons a run in progress. A run only
+/// saves located addresses, the next one picks up where it stopped.
+pub fn spawn(
+    db_pool: PgPool,
+    geocoder: Arc<dyn Geocoder>,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(INTERVAL);
+
+        loop {
+            let result = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    result = run(db_pool.clone(), &*geocoder) => result,
+                },
+            };
+
+            match result {
+                Ok(summary) => tracing::info!("Address geocoding finished: {:?}", summary),
+                Err(err) => tracing::error!("Address geocoding failed: {:#}", err),
+            }
+        }
+
+        tracing::debug!("address geocoding stopped");
+    })
+}
@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::domain::aggregates::invoice::Invoice;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::customer_queries::list_customers;
+use crate::infrastructure::repositories::invoice_repository::{InvoiceRepository, Repository as _};
+
+/// The invoices a run has drafted, and issued when asked to.
+#[derive(Debug)]
+pub struct InvoicingRun {
+    pub invoices: Vec<Invoice>,
+    /// Customers skipped because their orders were invoiced meanwhile.
+    pub skipped: Vec<i32>,
+}
+
+/// Drafts one invoice per customer with delivered orders left to bill, then issues the drafts
+/// when `issue` is set. Run on demand, e.g. by `tsm-admin invoice`, inside the tenant's scope.
+pub async fn run(db_pool: PgPool, tenant: TenantId, issue: bool) -> Result<InvoicingRun> {
+    let repo = InvoiceRepository::new(db_pool.clone(), tenant.clone());
+
+    let mut run = InvoicingRun {
+        invoices: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for customer in list_customers(db_pool, &tenant).await? {
+        let lines = repo.uninvoiced_lines(customer.id, None).await?;
+        if lines.is_empty() {
+            continue;
+        }
+
+        let Some(id) = repo.create(&Invoice::draft(customer.id, lines)).await? else {
+            run.skipped.push(customer.id);
+            continue;
+        };
+
+        if issue {
+            let draft = fetch(&repo, id).await?;
+            if repo.issue(&draft, Utc::now()).await?.is_none() {
+                tracing::warn!("Invoice {} was no longer a draft when issued.", id);
+            }
+        }
+
+        run.invoices.push(fetch(&repo, id).await?);
+    }
+
+    Ok(run)
+}
+
+async fn fetch(repo: &InvoiceRepository, id: i32) -> Result<Invoice> {
+    repo.by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("Invoice {} vanished after being created.", id))
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::infrastructure::repositories::vehicle_position_repository::apply_retention;
+
+// Partitions are created ahead of time so inserts never hit a missing day.
+const PREMAKE_DAYS: i32 = 2;
+const INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Runs every hour until `shutdown`, letting a run in progress finish its partition changes.
+pub fn spawn(db_pool: PgPool, retention_days: i32, shutdown: CancellationToken) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            if let Err(err) = apply_retention(&db_pool, retention_days, PREMAKE_DAYS).await {
+                tracing::error!("Vehicle position retention failed: {:#}", err);
+            }
+        }
+
+        tracing::debug!("vehicle position retention stopped");
+    })
+}
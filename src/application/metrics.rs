@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+// The default Prometheus buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Requests that matched no route share one label so scanners cannot grow the series.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// The process wide counters, rendered in the Prometheus text format at `/metrics`.
+///
+/// Gauges that describe the database, the pool or the business are not kept here, they are read
+/// when scraped and passed to [`Metrics::render`].
+#[derive(Default)]
+pub struct Metrics {
+    requests: Mutex<BTreeMap<RequestLabels, RequestSeries>>,
+    requests_in_flight: AtomicI64,
+    jwks_fetch_successes: AtomicU64,
+    jwks_fetch_failures: AtomicU64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestLabels {
+    method: String,
+    route: String,
+    status: u16,
+}
+
+#[derive(Default)]
+struct RequestSeries {
+    // Not cumulative, one count per bucket and the overflow last.
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    sum: f64,
+    count: u64,
+}
+
+/// A value read at scrape time.
+pub struct Gauge {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl Metrics {
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let labels = RequestLabels {
+            method: method.to_string(),
+            route: route.to_string(),
+            status,
+        };
+        let seconds = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|le| seconds <= *le)
+            .unwrap_or(LATENCY_BUCKETS.len());
+
+        let mut requests = self.requests.lock().unwrap();
+        let series = requests.entry(labels).or_default();
+        series.buckets[bucket] += 1;
+        series.sum += seconds;
+        series.count += 1;
+    }
+
+    pub fn observe_jwks_fetch<T, E>(&self, result: &Result<T, E>) {
+        let counter = match result {
+            Ok(_) => &self.jwks_fetch_successes,
+            Err(_) => &self.jwks_fetch_failures,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The counters followed by `gauges`, in the Prometheus text exposition format.
+    pub fn render(&self, gauges: &[Gauge]) -> String {
+        let mut out = String::new();
+
+        let requests = self.requests.lock().unwrap();
+
+        header(
+            &mut out,
+            "tsm_http_requests_total",
+            "counter",
+            "HTTP requests answered, by method, matched route and status.",
+        );
+        for (labels, series) in requests.iter() {
+            sample(
+                &mut out,
+                "tsm_http_requests_total",
+                &labels.pairs(),
+                series.count as f64,
+            );
+        }
+
+        header(
+            &mut out,
+            "tsm_http_request_duration_seconds",
+            "histogram",
+            "Time to answer HTTP requests, by method, matched route and status.",
+        );
+        for (labels, series) in requests.iter() {
+            let mut cumulative = 0;
+            for (i, count) in series.buckets.iter().enumerate() {
+                cumulative += count;
+                let le = LATENCY_BUCKETS
+                    .get(i)
+                    .map_or("+Inf".to_string(), |le| le.to_string());
+                let mut pairs = labels.pairs();
+                pairs.push(("le", le));
+                sample(
+                    &mut out,
+                    "tsm_http_request_duration_seconds_bucket",
+                    &pairs,
+                    cumulative as f64,
+                );
+            }
+            sample(
+                &mut out,
+                "tsm_http_request_duration_seconds_sum",
+                &labels.pairs(),
+                series.sum,
+            );
+            sample(
+                &mut out,
+                "tsm_http_request_duration_seconds_count",
+                &labels.pairs(),
+                series.count as f64,
+            );
+        }
+
+        drop(requests);
+
+        header(
+            &mut out,
+            "tsm_http_requests_in_flight",
+            "gauge",
+            "HTTP requests being answered.",
+        );
+        sample(
+            &mut out,
+            "tsm_http_requests_in_flight",
+            &[],
+            self.requests_in_flight.load(Ordering::Relaxed) as f64,
+        );
+
+        header(
+            &mut out,
+            "tsm_jwks_fetches_total",
+            "counter",
+            "Fetches of the identity provider's signing keys, by outcome.",
+        );
+        for (outcome, counter) in [
+            ("success", &self.jwks_fetch_successes),
+            ("failure", &self.jwks_fetch_failures),
+        ] {
+            sample(
+                &mut out,
+                "tsm_jwks_fetches_total",
+                &[("outcome", outcome.to_string())],
+                counter.load(Ordering::Relaxed) as f64,
+            );
+        }
+
+        for gauge in gauges {
+            header(&mut out, gauge.name, "gauge", gauge.help);
+            for (labels, value) in &gauge.samples {
+                sample(&mut out, gauge.name, labels, *value);
+            }
+        }
+
+        out
+    }
+}
+
+impl RequestLabels {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("method", self.method.clone()),
+            ("route", self.route.clone()),
+            ("status", self.status.to_string()),
+        ]
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, String)], value: f64) {
+    out.push_str(name);
+
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, escape(v)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+
+    let _ = writeln!(out, " {}", value);
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Counts and times every request by its route template, e.g. `/v1/api/customers/:id`.
+pub async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_ROUTE.to_string(), |p| p.as_str().to_string());
+    let method = request.method().to_string();
+
+    let _in_flight = InFlight::start(&metrics.requests_in_flight);
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics.observe_request(&method, &route, response.status().as_u16(), start.elapsed());
+
+    response
+}
+
+/// Counts a request in flight until dropped, also when the client goes away mid-request.
+struct InFlight<'a>(&'a AtomicI64);
+
+impl<'a> InFlight<'a> {
+    fn start(gauge: &'a AtomicI64) -> Self {
+        gauge.fetch_add(1, Ordering::Relaxed);
+        Self(gauge)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use http::{header::RETRY_AFTER, StatusCode};
+use sqlx::PgPool;
+
+use super::auth::RequireAuth;
+use super::config::{CounterStore, RateLimitConfig};
+use crate::infrastructure::rate_limiting::{self, RateLimitStore};
+
+/// Counts the requests of each principal, a user or an application, in fixed windows aligned to
+/// the epoch so every instance agrees on when a window starts.
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    config: RateLimitConfig,
+}
+
+impl RateLimiter {
+    pub fn new(db_pool: PgPool, config: RateLimitConfig) -> Self {
+        let store = rate_limiting::store(db_pool, config.store == CounterStore::Postgres);
+
+        Self { store, config }
+    }
+
+    /// The requests per window of a principal holding `roles`.
+    pub fn limit(&self, roles: &[String]) -> u32 {
+        roles
+            .iter()
+            .filter_map(|role| self.config.roles.get(role))
+            .max()
+            .copied()
+            .unwrap_or(self.config.requests)
+    }
+
+    /// Counts a request and returns how long to wait when it is over the limit. Lets requests
+    /// through when the counters cannot be reached, an outage of the store is not an outage of
+    /// the API.
+    pub async fn check(&self, principal: &str, roles: &[String]) -> Option<Duration> {
+        let now = Utc::now();
+        let window = self.config.window.as_secs() as i64;
+        let window_start = DateTime::from_timestamp(now.timestamp() - now.timestamp() % window, 0)
+            .expect("a timestamp within range");
+
+        let requests = match self.store.hit(principal, window_start).await {
+            Ok(requests) => requests,
+            Err(e) => {
+                tracing::warn!("Cannot count requests for rate limiting: {:#}", e);
+                return None;
+            }
+        };
+
+        let limit = self.limit(roles);
+        if requests <= limit {
+            return None;
+        }
+
+        if requests == limit + 1 {
+            tracing::warn!(
+                principal,
+                limit,
+                "rate limited until the window starting {} ends",
+                window_start
+            );
+        }
+
+        let window_end = window_start + TimeDelta::seconds(window);
+        Some((window_end - now).to_std().unwrap_or_default())
+    }
+}
+
+/// Answers 429 with `Retry-After` once the caller has used up its requests for the window.
+pub async fn limit_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    claims: RequireAuth,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.check(claims.principal(), &claims.roles).await {
+        None => next.run(request).await,
+        Some(retry_after) => {
+            // Whole seconds, rounded up so a retry never lands in the same window.
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, seconds.max(1).to_string())],
+                "Too many requests",
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{anyhow, Result};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::application::imports::validation_messages;
+use crate::domain::aggregates::item::Item;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::repositories::item_repository::{ItemRepository, Repository as _};
+use crate::models::item_dto::ReferenceData;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SeedSummary {
+    pub created: usize,
+    pub updated: usize,
+}
+
+/// Loads the catalogue of `data` into the tenant, all or nothing. Items are matched by name,
+/// ignoring case, so seeding the same file again updates them in place.
+pub async fn seed(db_pool: PgPool, tenant: TenantId, data: &ReferenceData) -> Result<SeedSummary> {
+    let invalid: Vec<String> = data
+        .items
+        .iter()
+        .filter_map(|i| {
+            let errors = i.validate().err()?;
+            Some(format!(
+                "item '{}': {}",
+                i.name,
+                validation_messages(&errors).join(", ")
+            ))
+        })
+        .collect();
+
+    if !invalid.is_empty() {
+        return Err(anyhow!("Invalid reference data: {}", invalid.join("; ")));
+    }
+
+    let repository = ItemRepository::new(db_pool, tenant);
+
+    let names: Vec<String> = data.items.iter().map(|i| i.name.clone()).collect();
+    let existing = repository.by_names(&names).await?;
+
+    let mut updated = 0;
+    let items: Vec<Item> = data
+        .items
+        .iter()
+        .map(|i| {
+            let id = existing
+                .iter()
+                .find(|e| e.name.to_lowercase() == i.name.to_lowercase())
+                .map_or(0, Item::id);
+            if id != 0 {
+                updated += 1;
+            }
+
+            Item::new(
+                id,
+                &i.name,
+                i.description.as_deref(),
+                i.quantity_available,
+                i.unit_price,
+            )
+        })
+        .collect();
+
+    repository.import(&items).await?;
+
+    Ok(SeedSummary {
+        created: items.len() - updated,
+        updated,
+    })
+}
@@ -1,22 +1,177 @@
+use std::future::IntoFuture;
+use std::sync::Arc;
+
 use anyhow::Context;
-use axum::{middleware::from_extractor, Router};
-use http::{HeaderValue, Method};
+
+use axum::{
+    middleware::{from_extractor_with_state, from_fn_with_state},
+    Router,
+};
+use http::header::{HeaderName, AUTHORIZATION, CONTENT_TYPE};
 use listenfd::ListenFd;
 use sqlx::PgPool;
 use tokio::net::TcpListener;
-use tower_http::cors::CorsLayer;
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use utoipa::openapi::path::{ParameterBuilder, ParameterIn};
+use utoipa::openapi::schema::{ObjectBuilder, Type};
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::openapi::ResponseBuilder;
+use utoipa::{Modify, OpenApi};
 
+mod batches;
+mod contacts;
 mod customers;
+mod imports;
+mod invoices;
+mod orders;
+mod proof_of_delivery;
+mod quotes;
+mod rate_cards;
+mod route_plans;
+mod settlements;
+mod vehicle_positions;
+mod vehicle_routes;
+mod vehicles;
 mod vendors;
 
+mod api_docs;
 mod forbidden;
+mod graphql;
+mod health;
 mod index;
 mod me;
+mod metrics;
+
+use super::{
+    auth::{scope_tenant, RequireAuth, SigningKeys},
+    config::Config,
+    idempotency::{replay_idempotent, IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED},
+    jobs::{address_geocoding, vehicle_position_retention},
+    metrics::{track_requests, Metrics},
+    rate_limit::{limit_requests, RateLimiter},
+    telemetry::{on_response, request_span},
+    utils::app_state::AppState,
+};
+use crate::infrastructure::geocoding;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "TSM API",
+        description = "Customers, vendors, quotes, route planning, delivery and billing."
+    ),
+    servers((url = "/v1/api")),
+    modifiers(&BearerAuth),
+    security(("bearer" = []))
+)]
+struct ApiDoc;
+
+/// Every operation takes an Entra ID access token for the API as a bearer token.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        let mut scheme = Http::new(HttpAuthScheme::Bearer);
+        scheme.bearer_format = Some("JWT".to_string());
+        components.add_security_scheme("bearer", SecurityScheme::Http(scheme));
+    }
+}
+
+/// Every POST may be sent with an `Idempotency-Key`, see [`replay_idempotent`].
+struct IdempotencyKeys;
+
+impl Modify for IdempotencyKeys {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            let Some(operation) = item.post.as_mut() else {
+                continue;
+            };
+
+            operation.parameters.get_or_insert_with(Vec::new).push(
+                ParameterBuilder::new()
+                    .name("Idempotency-Key")
+                    .parameter_in(ParameterIn::Header)
+                    .description(Some(
+                        "Answers a retry of the same request with this key with the first response",
+                    ))
+                    .schema(Some(ObjectBuilder::new().schema_type(Type::String)))
+                    .build(),
+            );
+            operation
+                .responses
+                .responses
+                .entry("409".to_string())
+                .or_insert_with(|| {
+                    ResponseBuilder::new()
+                        .description("The Idempotency-Key is in use by another request")
+                        .build()
+                        .into()
+                });
+        }
+    }
+}
+
+/// The OpenAPI document of every route under `/v1/api`, served at `/v1/api/openapi.json`.
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi()
+        .merge_from(me::ApiDoc::openapi())
+        .merge_from(customers::ApiDoc::openapi())
+        .merge_from(vendors::ApiDoc::openapi())
+        .merge_from(imports::ApiDoc::openapi())
+        .merge_from(batches::ApiDoc::openapi())
+        .merge_from(contacts::ApiDoc::openapi())
+        .merge_from(orders::ApiDoc::openapi())
+        .merge_from(proof_of_delivery::ApiDoc::openapi())
+        .merge_from(invoices::ApiDoc::openapi())
+        .merge_from(route_plans::ApiDoc::openapi())
+        .merge_from(rate_cards::ApiDoc::openapi())
+        .merge_from(quotes::ApiDoc::openapi())
+        .merge_from(vehicles::ApiDoc::openapi())
+        .merge_from(vehicle_positions::ApiDoc::openapi())
+        .merge_from(vehicle_routes::ApiDoc::openapi())
+        .merge_from(settlements::ApiDoc::openapi());
+
+    // After merging, so the operations of every module are there.
+    IdempotencyKeys.modify(&mut openapi);
+
+    openapi
+}
+
+pub async fn serve(db: PgPool, config: Config) -> anyhow::Result<()> {
+    let geocoder = geocoding::providers(
+        db.clone(),
+        config.geocoder.url.as_ref(),
+        config.geocoder.gazetteer_path.as_deref(),
+    )?;
+
+    let shutdown = CancellationToken::new();
+
+    let jobs = [
+        vehicle_position_retention::spawn(
+            db.clone(),
+            config.vehicle_positions.retention_days,
+            shutdown.clone(),
+        ),
+        address_geocoding::spawn(db.clone(), geocoder.clone(), shutdown.clone()),
+    ];
 
-use super::{auth::RequireAuth, utils::app_state::AppState};
+    let rate_limiter = RateLimiter::new(db.clone(), config.rate_limit.clone());
 
-pub async fn serve(db: PgPool) -> anyhow::Result<()> {
-    let app = create_app(db);
+    let bind_address = config.server.bind_address;
+    let shutdown_timeout = config.server.shutdown_timeout;
+    let app = create_app(AppState {
+        db_pool: db.clone(),
+        geocoder,
+        config: Arc::new(config),
+        metrics: Arc::new(Metrics::default()),
+        signing_keys: Arc::new(SigningKeys::default()),
+        rate_limiter: Arc::new(rate_limiter),
+        shutdown: shutdown.clone(),
+    });
 
     let mut listenfd = ListenFd::from_env();
     let listener = match listenfd.take_tcp_listener(0).unwrap() {
@@ -26,7 +181,7 @@ pub async fn serve(db: PgPool) -> anyhow::Result<()> {
             TcpListener::from_std(listener).unwrap()
         }
         // otherwise fall back to local listening
-        None => TcpListener::bind("127.0.0.1:3000")
+        None => TcpListener::bind(bind_address)
             .await
             .context("failed to bind TcpListener")
             .unwrap(),
@@ -40,35 +195,138 @@ pub async fn serve(db: PgPool) -> anyhow::Result<()> {
             .unwrap()
     );
 
-    axum::serve(listener, app)
-        .await
-        .context("failed to serve API")
+    // Stops accepting connections once cancelled and finishes when the open requests are answered.
+    let mut server = tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown.clone().cancelled_owned())
+            .into_future(),
+    );
+
+    tokio::select! {
+        result = &mut server => return result?.context("failed to serve API"),
+        _ = shutdown_signal() => {}
+    }
+
+    tracing::info!("shutting down, draining open requests");
+    shutdown.cancel();
+
+    let deadline = tokio::time::Instant::now() + shutdown_timeout;
+
+    match tokio::time::timeout_at(deadline, server).await {
+        Ok(result) => result?.context("failed to serve API")?,
+        Err(_) => tracing::warn!(
+            "requests still open after {:?}, closing them",
+            shutdown_timeout
+        ),
+    }
+
+    for job in jobs {
+        if tokio::time::timeout_at(deadline, job).await.is_err() {
+            tracing::warn!("a background job did not stop in time");
+        }
+    }
+
+    db.close().await;
+    tracing::info!("shut down");
+
+    Ok(())
 }
 
-fn create_app(db: PgPool) -> Router {
-    let app_state = AppState { db_pool: db };
+/// Resolves on Ctrl+C, or on SIGTERM where there is one.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Taken from the client when sent, so a request can be followed from the frontend or a proxy.
+const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The API with its routes and middleware, without the background jobs `serve` starts.
+pub fn create_app(app_state: AppState) -> Router {
+    let cors = &app_state.config.cors;
+    // see https://docs.rs/tower-http/latest/tower_http/cors/index.html
+    // for more details
+    //
+    // pay attention that for some request types like posting content-type: application/json
+    // it is required to add ".allow_headers([http::header::CONTENT_TYPE])"
+    // or see this issue https://github.com/tokio-rs/axum/issues/849
+    let cors_layer = CorsLayer::new()
+        .allow_origin(AllowOrigin::list(cors.allowed_origins.clone()))
+        .allow_methods(cors.allowed_methods.clone())
+        .allow_headers([CONTENT_TYPE, AUTHORIZATION, X_REQUEST_ID, IDEMPOTENCY_KEY])
+        .expose_headers([X_REQUEST_ID, IDEMPOTENT_REPLAYED]);
 
     let api_routes = Router::new()
         .merge(me::router())
         .merge(customers::router())
         .merge(vendors::router())
-        .route_layer(from_extractor::<RequireAuth>());
+        .merge(imports::router())
+        .merge(batches::router())
+        .merge(contacts::router())
+        .merge(orders::router())
+        .merge(proof_of_delivery::router())
+        .merge(invoices::router())
+        .merge(route_plans::router())
+        .merge(rate_cards::router())
+        .merge(quotes::router())
+        .merge(vehicles::router())
+        .merge(vehicle_positions::router())
+        .merge(vehicle_routes::router())
+        .merge(settlements::router())
+        .route_layer(from_fn_with_state(app_state.clone(), replay_idempotent))
+        .route_layer(from_fn_with_state(app_state.clone(), scope_tenant))
+        // Inside the authentication, so callers without a valid token are turned away first.
+        .route_layer(from_fn_with_state(app_state.clone(), limit_requests))
+        .route_layer(from_extractor_with_state::<RequireAuth, _>(
+            app_state.clone(),
+        ))
+        .merge(api_docs::router());
+
+    // Behind the same authentication, rate limits and tenant scope as the REST API.
+    let graphql_routes = graphql::router()
+        .route_layer(from_fn_with_state(app_state.clone(), scope_tenant))
+        .route_layer(from_fn_with_state(app_state.clone(), limit_requests))
+        .route_layer(from_extractor_with_state::<RequireAuth, _>(
+            app_state.clone(),
+        ));
 
     Router::new()
         .merge(index::router())
         .merge(forbidden::router())
+        .merge(health::router())
+        .merge(metrics::router())
         .nest("/v1/api", api_routes)
+        .merge(graphql_routes)
+        .layer(from_fn_with_state(
+            app_state.metrics.clone(),
+            track_requests,
+        ))
         .with_state(app_state)
+        .layer(cors_layer)
+        .layer(PropagateRequestIdLayer::new(X_REQUEST_ID))
         .layer(
-            // see https://docs.rs/tower-http/latest/tower_http/cors/index.html
-            // for more details
-            //
-            // pay attention that for some request types like posting content-type: application/json
-            // it is required to add ".allow_headers([http::header::CONTENT_TYPE])"
-            // or see this issue https://github.com/tokio-rs/axum/issues/849
-            CorsLayer::new()
-                .allow_origin("http://localhost:5173".parse::<HeaderValue>().unwrap())
-                .allow_methods([Method::GET, Method::POST])
-                .allow_headers([http::header::CONTENT_TYPE]),
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_response(on_response),
         )
+        .layer(SetRequestIdLayer::new(X_REQUEST_ID, MakeRequestUuid))
 }
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use utoipa_scalar::Scalar;
+
+use crate::application::utils::app_state::AppState;
+
+/// The OpenAPI document and a Scalar reference page to browse and try it. Both are public, the
+/// operations they describe still require a bearer token.
+pub fn router() -> Router<AppState> {
+    let spec = super::openapi();
+    let html = Arc::new(Scalar::new(spec.clone()).to_html());
+    let spec = Arc::new(spec);
+
+    Router::new()
+        .route(
+            "/openapi.json",
+            get(move || async move { Json(spec.as_ref().clone()) }),
+        )
+        .route(
+            "/docs",
+            get(move || async move { Html(html.as_ref().clone()).into_response() }),
+        )
+}
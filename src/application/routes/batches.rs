@@ -0,0 +1,411 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::Json;
+use axum::{async_trait, extract::State, response::IntoResponse, routing::post, Router};
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::imports::validation_messages;
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::{customer::Customer, item::Item, vendor::Vendor};
+use crate::domain::value_objects::address::Address;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::repositories::customer_repository::{
+    CustomerRepository, Repository as _,
+};
+use crate::infrastructure::repositories::item_repository::{ItemRepository, Repository as _};
+use crate::infrastructure::repositories::vendor_repository::{Repository as _, VendorRepository};
+use crate::models::address_dto::AddressDto;
+use crate::models::batch_dto::{BatchReportDto, BatchRequest, BatchResultDto, BatchStatus};
+use crate::models::customer_dto::CreateCustomerRequest;
+use crate::models::item_dto::ItemRequest;
+use crate::models::vendor_dto::CreateVendorRequest;
+
+const MAX_OPERATIONS: usize = 1_000;
+
+// The batch handler is generic over the entity, so its path is documented once per entity.
+macro_rules! batch_docs {
+    ($docs:ident, $tag:literal, $path:literal, $request:ty) => {
+        #[allow(dead_code)]
+        mod $docs {
+            #[allow(unused_imports)]
+            use crate::models::batch_dto::{BatchReportDto, BatchRequest};
+            #[allow(unused_imports)]
+            use crate::models::{
+                customer_dto::CreateCustomerRequest, item_dto::ItemRequest,
+                vendor_dto::CreateVendorRequest,
+            };
+
+            /// Creates the operations without an id and updates the others, all in one transaction
+            /// when atomic. Addresses are not geocoded here, the address geocoding job locates them.
+            #[utoipa::path(
+                post,
+                path = $path,
+                tag = $tag,
+                request_body = BatchRequest<$request>,
+                responses(
+                    (status = 200, description = "The outcome of each operation", body = BatchReportDto),
+                    (status = 422, description = "Too many operations, or an atomic batch that was not saved", body = BatchReportDto),
+                )
+            )]
+            pub fn batch() {}
+        }
+    };
+}
+
+batch_docs!(
+    customer_docs,
+    "customers",
+    "/customers:batch",
+    CreateCustomerRequest
+);
+batch_docs!(
+    vendor_docs,
+    "vendors",
+    "/vendors:batch",
+    CreateVendorRequest
+);
+
+batch_docs!(item_docs, "items", "/items:batch", ItemRequest);
+
+#[derive(OpenApi)]
+#[openapi(paths(customer_docs::batch, vendor_docs::batch, item_docs::batch))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/customers:batch", post(batch_handler::<Customers>))
+        .route("/vendors:batch", post(batch_handler::<Vendors>))
+        .route("/items:batch", post(batch_handler::<Items>))
+}
+
+/// A customer or vendor read from a valid operation, with id 0 to create it.
+struct Party {
+    id: i32,
+    name: String,
+    email: String,
+    address: Address,
+    contact_number: Option<String>,
+}
+
+// Customers, vendors and items share the batch handler, these read and save the operations
+// through the entity's repository.
+#[async_trait]
+trait Records: Send + 'static {
+    type Request: DeserializeOwned + Validate + Send + 'static;
+    type Record: Send + Sync;
+    type Repository: Send + Sync;
+
+    fn repository(db_pool: PgPool, tenant: TenantId) -> Self::Repository;
+
+    /// Checks an operation with the same rules as the create and update endpoints.
+    fn record(id: i32, request: Self::Request) -> Result<Self::Record, Vec<String>>;
+
+    /// Readies the update of the record saved under the operation's id, `false` when there is no
+    /// such record.
+    async fn prepare_update(
+        repository: &Self::Repository,
+        record: &mut Self::Record,
+    ) -> Result<bool>;
+
+    /// Creates or updates the record on its own, returning its id or `None` when it vanished.
+    async fn save(repository: &Self::Repository, record: &Self::Record) -> Result<Option<i32>>;
+
+    /// Creates or updates every record, all or none.
+    async fn import(repository: &Self::Repository, records: &[Self::Record]) -> Result<Vec<i32>>;
+}
+
+fn validate(request: &impl Validate, address: &AddressDto) -> Result<Address, Vec<String>> {
+    request.validate().map_err(|e| validation_messages(&e))?;
+
+    address.to_domain().map_err(|e| vec![e.to_string()])
+}
+
+/// Updates keep the coordinates when the address is unchanged, like the imports. `false` when
+/// there is no `current` record.
+fn keep_coordinates(party: &mut Party, current: Option<Address>) -> bool {
+    let Some(current) = current else {
+        return false;
+    };
+
+    if party.address.coordinates.is_none() && current.with_coordinates(None) == party.address {
+        party.address = current;
+    }
+
+    true
+}
+
+/// `None` for the error of a repository's `by_id` when the record does not exist.
+fn found<T>(record: Result<T>) -> Result<Option<T>> {
+    match record {
+        Ok(record) => Ok(Some(record)),
+        Err(e) if matches!(e.downcast_ref(), Some(sqlx::Error::RowNotFound)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+struct Customers;
+
+#[async_trait]
+impl Records for Customers {
+    type Request = CreateCustomerRequest;
+    type Record = Party;
+    type Repository = CustomerRepository;
+
+    fn repository(db_pool: PgPool, tenant: TenantId) -> Self::Repository {
+        CustomerRepository::new(db_pool, tenant)
+    }
+
+    fn record(id: i32, request: Self::Request) -> Result<Party, Vec<String>> {
+        let address = validate(&request, &request.address)?;
+
+        Ok(Party {
+            id,
+            name: request.name,
+            email: request.email,
+            address,
+            contact_number: request.contact_number,
+        })
+    }
+
+    async fn prepare_update(repository: &Self::Repository, party: &mut Party) -> Result<bool> {
+        let current = found(repository.by_id(party.id).await)?.map(|c| c.address.clone());
+
+        Ok(keep_coordinates(party, current))
+    }
+
+    async fn save(repository: &Self::Repository, party: &Party) -> Result<Option<i32>> {
+        let customer = customer(party);
+
+        match party.id {
+            0 => Ok(Some(repository.create(&customer).await?)),
+            id => Ok(repository.update(&customer).await?.then_some(id)),
+        }
+    }
+
+    async fn import(repository: &Self::Repository, parties: &[Party]) -> Result<Vec<i32>> {
+        let customers: Vec<Customer> = parties.iter().map(customer).collect();
+
+        repository.import(&customers).await
+    }
+}
+
+fn customer(p: &Party) -> Customer {
+    Customer::new(
+        p.id,
+        &p.name,
+        &p.email,
+        p.address.clone(),
+        p.contact_number.as_deref(),
+    )
+}
+
+struct Vendors;
+
+#[async_trait]
+impl Records for Vendors {
+    type Request = CreateVendorRequest;
+    type Record = Party;
+    type Repository = VendorRepository;
+
+    fn repository(db_pool: PgPool, tenant: TenantId) -> Self::Repository {
+        VendorRepository::new(db_pool, tenant)
+    }
+
+    fn record(id: i32, request: Self::Request) -> Result<Party, Vec<String>> {
+        let address = validate(&request, &request.address)?;
+
+        Ok(Party {
+            id,
+            name: request.name,
+            email: request.email,
+            address,
+            contact_number: request.contact_number,
+        })
+    }
+
+    async fn prepare_update(repository: &Self::Repository, party: &mut Party) -> Result<bool> {
+        let current = found(repository.by_id(party.id).await)?.map(|v| v.address.clone());
+
+        Ok(keep_coordinates(party, current))
+    }
+
+    async fn save(repository: &Self::Repository, party: &Party) -> Result<Option<i32>> {
+        let vendor = vendor(party);
+
+        match party.id {
+            0 => Ok(Some(repository.create(&vendor).await?)),
+            id => Ok(repository.update(&vendor).await?.then_some(id)),
+        }
+    }
+
+    async fn import(repository: &Self::Repository, parties: &[Party]) -> Result<Vec<i32>> {
+        let vendors: Vec<Vendor> = parties.iter().map(vendor).collect();
+
+        repository.import(&vendors).await
+    }
+}
+
+fn vendor(p: &Party) -> Vendor {
+    Vendor::new(
+        p.id,
+        &p.name,
+        &p.email,
+        p.address.clone(),
+        p.contact_number.as_deref(),
+    )
+}
+
+struct Items;
+
+#[async_trait]
+impl Records for Items {
+    type Request = ItemRequest;
+    type Record = Item;
+    type Repository = ItemRepository;
+
+    fn repository(db_pool: PgPool, tenant: TenantId) -> Self::Repository {
+        ItemRepository::new(db_pool, tenant)
+    }
+
+    fn record(id: i32, request: Self::Request) -> Result<Item, Vec<String>> {
+        request.validate().map_err(|e| validation_messages(&e))?;
+
+        Ok(Item::new(
+            id,
+            &request.name,
+            request.description.as_deref(),
+            request.quantity_available,
+            request.unit_price,
+        ))
+    }
+
+    async fn prepare_update(repository: &Self::Repository, item: &mut Item) -> Result<bool> {
+        Ok(found(repository.by_id(item.id).await)?.is_some())
+    }
+
+    async fn save(repository: &Self::Repository, item: &Item) -> Result<Option<i32>> {
+        match item.id {
+            0 => Ok(Some(repository.create(item).await?)),
+            id => Ok(repository.update(item).await?.then_some(id)),
+        }
+    }
+
+    async fn import(repository: &Self::Repository, items: &[Item]) -> Result<Vec<i32>> {
+        repository.import(items).await
+    }
+}
+
+/// Creates the operations without an id and updates the others, each on its own or, when atomic,
+/// all in one transaction. Addresses are not geocoded here, the address geocoding job locates them.
+async fn batch_handler<P: Records>(
+    Path(suffix): Path<String>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(batch): Json<BatchRequest<P::Request>>,
+) -> Result<impl IntoResponse, AppError> {
+    // The router reads `:batch` as a parameter taking the rest of the segment, `/customersfoo`
+    // included, so only the literal suffix is this endpoint.
+    if suffix != ":batch" {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    if batch.operations.len() > MAX_OPERATIONS {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "A batch cannot have more than {} operations.",
+                MAX_OPERATIONS
+            ),
+        )
+            .into_response());
+    }
+
+    let repository = P::repository(db_pool, tenant);
+
+    let mut results: Vec<BatchResultDto> = Vec::with_capacity(batch.operations.len());
+    let mut records: Vec<(usize, P::Record)> = Vec::new();
+
+    for (index, operation) in batch.operations.into_iter().enumerate() {
+        let mut result = BatchResultDto {
+            index,
+            status: BatchStatus::Created,
+            id: operation.id,
+            errors: Vec::new(),
+        };
+
+        let mut record = match P::record(operation.id.unwrap_or_default(), operation.fields) {
+            Ok(record) => record,
+            Err(errors) => {
+                result.status = BatchStatus::Invalid;
+                result.errors = errors;
+                results.push(result);
+                continue;
+            }
+        };
+
+        if operation.id.is_some() {
+            if !P::prepare_update(&repository, &mut record).await? {
+                result.status = BatchStatus::NotFound;
+                results.push(result);
+                continue;
+            }
+            result.status = BatchStatus::Updated;
+        }
+
+        results.push(result);
+        records.push((index, record));
+    }
+
+    if batch.atomic {
+        if records.len() < results.len() {
+            for (index, _) in &records {
+                results[*index].status = BatchStatus::RolledBack;
+            }
+        } else if !records.is_empty() {
+            let (indexes, records): (Vec<usize>, Vec<P::Record>) = records.into_iter().unzip();
+
+            let ids = P::import(&repository, &records).await?;
+            for (index, id) in indexes.into_iter().zip(ids) {
+                results[index].id = Some(id);
+            }
+        }
+    } else {
+        for (index, record) in records {
+            let result = &mut results[index];
+
+            match P::save(&repository, &record).await {
+                Ok(Some(id)) => result.id = Some(id),
+                Ok(None) => result.status = BatchStatus::NotFound,
+                Err(e) => {
+                    tracing::warn!("Batch operation {} failed: {:#}", index, e);
+                    result.status = BatchStatus::Failed;
+                    result
+                        .errors
+                        .push("The operation could not be saved.".to_string());
+                }
+            }
+        }
+    }
+
+    let count = |status: BatchStatus| results.iter().filter(|r| r.status == status).count() as i32;
+    let (created, updated) = (count(BatchStatus::Created), count(BatchStatus::Updated));
+
+    let report = BatchReportDto {
+        atomic: batch.atomic,
+        created,
+        updated,
+        rejected: results.len() as i32 - created - updated,
+        results,
+    };
+
+    let status = match batch.atomic && report.rejected > 0 {
+        true => StatusCode::UNPROCESSABLE_ENTITY,
+        false => StatusCode::OK,
+    };
+
+    Ok((status, Json(report)).into_response())
+}
@@ -0,0 +1,377 @@
+use anyhow::Result;
+use axum::extract::{Path, Query};
+use axum::Json;
+use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::contact::{Contact, ContactOwner, ContactRole};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::contact_queries::{
+    get_contact_by_id, list_contact_recipients, list_contacts,
+};
+use crate::infrastructure::queries::{
+    customer_queries::get_customer_by_id, vendor_queries::get_vendor_by_id,
+};
+use crate::infrastructure::repositories::contact_repository::{ContactRepository, Repository};
+use crate::models::contact_dto::{
+    ContactDto, ContactRecipientsQuery, ContactsQuery, CreateContactRequest,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/customers/:id/contacts",
+            get(contacts_list_handler::<Customers>).post(create_contact_handler::<Customers>),
+        )
+        .route(
+            "/customers/:id/contacts/recipients",
+            get(contact_recipients_handler::<Customers>),
+        )
+        .route(
+            "/customers/:id/contacts/:contact_id",
+            get(contact_handler::<Customers>)
+                .put(update_contact_handler::<Customers>)
+                .delete(delete_contact_handler::<Customers>),
+        )
+        .route(
+            "/vendors/:id/contacts",
+            get(contacts_list_handler::<Vendors>).post(create_contact_handler::<Vendors>),
+        )
+        .route(
+            "/vendors/:id/contacts/recipients",
+            get(contact_recipients_handler::<Vendors>),
+        )
+        .route(
+            "/vendors/:id/contacts/:contact_id",
+            get(contact_handler::<Vendors>)
+                .put(update_contact_handler::<Vendors>)
+                .delete(delete_contact_handler::<Vendors>),
+        )
+}
+
+// The handlers are generic over the owner, so their paths are documented once per owner.
+macro_rules! contact_docs {
+    ($docs:ident, $tag:literal, $owner:literal, $list:literal, $recipients:literal, $item:literal) => {
+        #[allow(dead_code)]
+        mod $docs {
+            #[allow(unused_imports)]
+            use crate::models::contact_dto::{ContactDto, ContactRecipientsQuery, ContactsQuery, CreateContactRequest};
+
+            #[utoipa::path(
+                get,
+                path = $list,
+                tag = $tag,
+                params(("id" = i32, Path, description = $owner), ContactsQuery),
+                responses(
+                    (status = 200, body = [ContactDto]),
+                    (status = 404, description = "Unknown owner"),
+                    (status = 422, description = "Validation errors by field", body = Object),
+                )
+            )]
+            pub fn list() {}
+
+            #[utoipa::path(
+                post,
+                path = $list,
+                tag = $tag,
+                params(("id" = i32, Path, description = $owner)),
+                request_body = CreateContactRequest,
+                responses(
+                    (status = 201, body = ContactDto, headers(("Location" = String))),
+                    (status = 404, description = "Unknown owner"),
+                    (status = 422, description = "Validation errors by field", body = Object),
+                )
+            )]
+            pub fn create() {}
+
+            /// The reachable contacts to notify for a role, the general contacts when it has none.
+            #[utoipa::path(
+                get,
+                path = $recipients,
+                tag = $tag,
+                params(("id" = i32, Path, description = $owner), ContactRecipientsQuery),
+                responses(
+                    (status = 200, body = [ContactDto]),
+                    (status = 404, description = "Unknown owner"),
+                    (status = 422, description = "Validation errors by field", body = Object),
+                )
+            )]
+            pub fn recipients() {}
+
+            #[utoipa::path(
+                get,
+                path = $item,
+                tag = $tag,
+                params(
+                    ("id" = i32, Path, description = $owner),
+                    ("contact_id" = i32, Path, description = "Contact id"),
+                ),
+                responses(
+                    (status = 200, body = ContactDto),
+                    (status = 404, description = "Unknown contact"),
+                )
+            )]
+            pub fn get() {}
+
+            #[utoipa::path(
+                put,
+                path = $item,
+                tag = $tag,
+                params(
+                    ("id" = i32, Path, description = $owner),
+                    ("contact_id" = i32, Path, description = "Contact id"),
+                ),
+                request_body = CreateContactRequest,
+                responses(
+                    (status = 200, body = ContactDto),
+                    (status = 404, description = "Unknown contact"),
+                    (status = 422, description = "Validation errors by field", body = Object),
+                )
+            )]
+            pub fn update() {}
+
+            #[utoipa::path(
+                delete,
+                path = $item,
+                tag = $tag,
+                params(
+                    ("id" = i32, Path, description = $owner),
+                    ("contact_id" = i32, Path, description = "Contact id"),
+                ),
+                responses(
+                    (status = 204, description = "Deleted"),
+                    (status = 404, description = "Unknown contact"),
+                )
+            )]
+            pub fn delete() {}
+        }
+    };
+}
+
+contact_docs!(
+    customer_docs,
+    "customers",
+    "Customer id",
+    "/customers/{id}/contacts",
+    "/customers/{id}/contacts/recipients",
+    "/customers/{id}/contacts/{contact_id}"
+);
+contact_docs!(
+    vendor_docs,
+    "vendors",
+    "Vendor id",
+    "/vendors/{id}/contacts",
+    "/vendors/{id}/contacts/recipients",
+    "/vendors/{id}/contacts/{contact_id}"
+);
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    customer_docs::list,
+    customer_docs::create,
+    customer_docs::recipients,
+    customer_docs::get,
+    customer_docs::update,
+    customer_docs::delete,
+    vendor_docs::list,
+    vendor_docs::create,
+    vendor_docs::recipients,
+    vendor_docs::get,
+    vendor_docs::update,
+    vendor_docs::delete,
+))]
+pub struct ApiDoc;
+
+// Customers and vendors share the contact handlers, these pick the owner from the path id.
+trait Owner: Send + 'static {
+    const PATH: &'static str;
+
+    fn of(id: i32) -> ContactOwner;
+}
+
+struct Customers;
+
+impl Owner for Customers {
+    const PATH: &'static str = "customers";
+
+    fn of(id: i32) -> ContactOwner {
+        ContactOwner::Customer(id)
+    }
+}
+
+struct Vendors;
+
+impl Owner for Vendors {
+    const PATH: &'static str = "vendors";
+
+    fn of(id: i32) -> ContactOwner {
+        ContactOwner::Vendor(id)
+    }
+}
+
+async fn owner_exists(db_pool: PgPool, tenant: &TenantId, owner: ContactOwner) -> Result<bool> {
+    Ok(match owner {
+        ContactOwner::Customer(id) => get_customer_by_id(db_pool, tenant, id).await?.is_some(),
+        ContactOwner::Vendor(id) => get_vendor_by_id(db_pool, tenant, id).await?.is_some(),
+    })
+}
+
+async fn contacts_list_handler<O: Owner>(
+    Path(id): Path<i32>,
+    Query(query): Query<ContactsQuery>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = query.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let owner = O::of(id);
+
+    if !owner_exists(db_pool.clone(), &tenant, owner).await? {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let role = query.role.map(|r| r.parse::<ContactRole>()).transpose()?;
+
+    let contacts = list_contacts(db_pool, &tenant, owner, role).await?;
+
+    Ok(Json(contacts).into_response())
+}
+
+async fn contact_recipients_handler<O: Owner>(
+    Path(id): Path<i32>,
+    Query(query): Query<ContactRecipientsQuery>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = query.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let owner = O::of(id);
+
+    if !owner_exists(db_pool.clone(), &tenant, owner).await? {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let recipients = list_contact_recipients(db_pool, &tenant, owner, query.role.parse()?).await?;
+
+    Ok(Json(recipients).into_response())
+}
+
+async fn contact_handler<O: Owner>(
+    Path((id, contact_id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let contact = get_contact_by_id(db_pool, &tenant, O::of(id), contact_id).await?;
+
+    match contact {
+        Some(c) => Ok((StatusCode::OK, Json(c)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+async fn create_contact_handler<O: Owner>(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateContactRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let owner = O::of(id);
+
+    if !owner_exists(db_pool.clone(), &tenant, owner).await? {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let repo = ContactRepository::new(db_pool, tenant);
+
+    let mut contact_domain = Contact::new(
+        0,
+        owner,
+        &req.name,
+        req.role.parse()?,
+        req.email.as_deref(),
+        req.phone.as_deref(),
+        req.preferred_channel.parse()?,
+    );
+    contact_domain.set_primary(req.is_primary);
+
+    let contact_id = repo.create(&contact_domain).await?;
+
+    let dto = contact_dto(contact_id, req);
+
+    let location_header = [(
+        LOCATION,
+        format!("/v1/api/{}/{}/contacts/{}", O::PATH, id, contact_id),
+    )];
+
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
+}
+
+async fn update_contact_handler<O: Owner>(
+    Path((id, contact_id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateContactRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = ContactRepository::new(db_pool, tenant);
+
+    let Some(mut contact) = repo.by_id(O::of(id), contact_id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    contact.update(
+        &req.name,
+        req.role.parse()?,
+        req.email.as_deref(),
+        req.phone.as_deref(),
+        req.preferred_channel.parse()?,
+        req.is_primary,
+    );
+
+    if !repo.update(&contact).await? {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    Ok(Json(contact_dto(contact.id(), req)).into_response())
+}
+
+async fn delete_contact_handler<O: Owner>(
+    Path((id, contact_id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let repo = ContactRepository::new(db_pool, tenant);
+
+    if !repo.delete(O::of(id), contact_id).await? {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    Ok((StatusCode::NO_CONTENT).into_response())
+}
+
+fn contact_dto(id: i32, req: CreateContactRequest) -> ContactDto {
+    ContactDto {
+        id,
+        name: req.name,
+        role: req.role,
+        email: req.email,
+        phone: req.phone,
+        preferred_channel: req.preferred_channel,
+        is_primary: req.is_primary,
+    }
+}
@@ -1,16 +1,37 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use axum::extract::Path;
 use axum::Json;
 use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
-use http::{HeaderName, StatusCode};
+use http::StatusCode;
 use sqlx::PgPool;
-
-use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{
+    app_state::AppState,
+    export::{list_response, FormatQuery, ListFormat},
+    geocoding::locate_customer,
+    http_utils::AppError,
+};
 use crate::domain::aggregates::customer::Customer;
-use crate::infrastructure::queries::customer_queries::{get_customer_by_id, list_customers};
+use crate::domain::services::geocoder::Geocoder;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::customer_queries::{get_customer_by_id, stream_customers};
 use crate::infrastructure::repositories::customer_repository::{CustomerRepository, Repository};
+use crate::models::address_dto::AddressDto;
 use crate::models::customer_dto::{CreateCustomerRequest, CustomerDto};
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    customers_list_handler,
+    create_customer_handler,
+    customer_handler,
+    update_customer_handler
+))]
+pub struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route(
@@ -23,19 +44,44 @@ pub fn router() -> Router<AppState> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/customers",
+    tag = "customers",
+    params(FormatQuery),
+    responses(
+        (status = 200, description = "Customers by name, as JSON, CSV or XLSX", content(
+            (Vec<CustomerDto> = "application/json"),
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        )),
+        (status = 400, description = "Unknown format"),
+    )
+)]
 async fn customers_list_handler(
+    format: ListFormat,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
 ) -> Result<impl IntoResponse, AppError> {
-    let customers = list_customers(db_pool).await?;
-
-    Ok(Json(customers))
+    Ok(list_response(format, "customers", stream_customers(db_pool, tenant)).await?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/customers/{id}",
+    tag = "customers",
+    params(("id" = i32, Path, description = "Customer id")),
+    responses(
+        (status = 200, body = CustomerDto),
+        (status = 404, description = "Unknown customer"),
+    )
+)]
 async fn customer_handler(
     Path(id): Path<i32>,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
 ) -> Result<impl IntoResponse, AppError> {
-    let customer = get_customer_by_id(db_pool, id).await?;
+    let customer = get_customer_by_id(db_pool, &tenant, id).await?;
 
     match customer {
         Some(c) => Ok((StatusCode::OK, Json(c)).into_response()),
@@ -43,12 +89,32 @@ async fn customer_handler(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/customers/{id}",
+    tag = "customers",
+    params(("id" = i32, Path, description = "Customer id")),
+    request_body = CreateCustomerRequest,
+    responses(
+        (status = 200, body = CustomerDto),
+        (status = 404, description = "Unknown customer"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
 async fn update_customer_handler(
     Path(id): Path<i32>,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
+    State(geocoder): State<Arc<dyn Geocoder>>,
     Json(req): Json<CreateCustomerRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let repo = CustomerRepository::new(db_pool);
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let address = req.address.to_domain()?;
+
+    let repo = CustomerRepository::new(db_pool.clone(), tenant.clone());
 
     let mut customer = repo
         .by_id(id)
@@ -59,55 +125,74 @@ async fn update_customer_handler(
     customer.update(
         &req.name,
         &req.email,
-        &req.address,
+        address,
         req.contact_number.as_deref(),
     );
 
     repo.update(&customer).await?;
 
+    if customer.address.coordinates.is_none() {
+        locate_customer(geocoder, db_pool, tenant, id);
+    }
+
     let dto = CustomerDto {
         id: customer.id(),
         name: req.name,
-        address: req.address,
+        address: AddressDto::from(&customer.address),
         contact_number: req.contact_number,
         email: req.email,
     };
 
-    Ok(Json(dto))
+    Ok(Json(dto).into_response())
 }
 
+#[utoipa::path(
+    post,
+    path = "/customers",
+    tag = "customers",
+    request_body = CreateCustomerRequest,
+    responses(
+        (status = 201, body = CustomerDto, headers(("Location" = String))),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
 async fn create_customer_handler(
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
+    State(geocoder): State<Arc<dyn Geocoder>>,
     Json(req): Json<CreateCustomerRequest>,
-) -> Result<
-    (
-        StatusCode,
-        [(HeaderName, std::string::String); 1],
-        axum::Json<CustomerDto>,
-    ),
-    AppError,
-> {
-    let repo = CustomerRepository::new(db_pool);
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let address = req.address.to_domain()?;
+
+    let repo = CustomerRepository::new(db_pool.clone(), tenant.clone());
 
     let customer_domain = Customer::new(
         0,
         &req.name,
         &req.email,
-        &req.address,
+        address,
         req.contact_number.as_deref(),
     );
 
     let id = repo.create(&customer_domain).await?;
 
+    if customer_domain.address.coordinates.is_none() {
+        locate_customer(geocoder, db_pool, tenant, id);
+    }
+
     let dto = CustomerDto {
         id,
         name: req.name,
-        address: req.address,
+        address: AddressDto::from(&customer_domain.address),
         contact_number: req.contact_number,
         email: req.email,
     };
 
     let location_header = [(LOCATION, format!("/v1/api/customers/{}", id))];
 
-    Ok((StatusCode::CREATED, location_header, Json(dto)))
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
 }
@@ -0,0 +1,23 @@
+use axum::{extract::State, routing::post, Extension, Json, Router};
+use sqlx::PgPool;
+
+use crate::application::graphql::{execute, schema, ApiSchema};
+use crate::application::utils::app_state::AppState;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/graphql", post(graphql_handler))
+        .layer(Extension(schema()))
+}
+
+/// Reads customers, vendors, orders, vehicles and routes with their relations in one request.
+/// Errors are reported in the body next to the data, as GraphQL does, with a 200.
+async fn graphql_handler(
+    Extension(schema): Extension<ApiSchema>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(execute(&schema, request, db_pool, tenant).await)
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use axum::{extract::State, response::IntoResponse, routing::get, Json, Router};
+use http::StatusCode;
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+
+use crate::application::auth::SigningKeys;
+use crate::application::config::Config;
+use crate::application::metrics::Metrics;
+use crate::application::utils::app_state::AppState;
+use crate::infrastructure::queries::health_queries::{ping, unapplied_migrations};
+use crate::models::health_dto::{CheckDto, ReadinessDto};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/healthz", get(liveness_handler))
+        .route("/readyz", get(readiness_handler))
+}
+
+/// The process is up and answering, nothing else is checked so a slow dependency never gets the
+/// instance restarted.
+async fn liveness_handler() -> impl IntoResponse {
+    "ok"
+}
+
+/// Whether this instance should get traffic: the database answers, every migration of this build is
+/// applied and the identity provider publishes its signing keys, reusing the keys cached for token
+/// verification while they are fresh. Fails as soon as a shutdown starts so load balancers move
+/// away while open requests drain.
+async fn readiness_handler(
+    State(db_pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(metrics): State<Arc<Metrics>>,
+    State(signing_keys): State<Arc<SigningKeys>>,
+    State(shutdown): State<CancellationToken>,
+) -> impl IntoResponse {
+    let (database, migrations, jwks) = tokio::join!(
+        check("database", ping(db_pool.clone())),
+        check("migrations", async {
+            match unapplied_migrations(db_pool.clone()).await?.as_slice() {
+                [] => Ok(()),
+                unapplied => Err(anyhow!("{}.", unapplied.join(", "))),
+            }
+        }),
+        check("jwks", async {
+            match signing_keys.get(&config.auth, &metrics).await?.len() {
+                0 => Err(anyhow!("{} has no signing keys.", config.auth.jwks_url)),
+                _ => Ok(()),
+            }
+        }),
+    );
+
+    let mut checks = BTreeMap::from([
+        ("database".to_string(), database),
+        ("migrations".to_string(), migrations),
+        ("jwks".to_string(), jwks),
+    ]);
+
+    if shutdown.is_cancelled() {
+        checks.insert(
+            "shutdown".to_string(),
+            CheckDto {
+                status: "fail".to_string(),
+                output: Some("Shutting down.".to_string()),
+            },
+        );
+    }
+
+    let ready = checks.values().all(|c| c.status == "pass");
+    let status = match ready {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    let report = ReadinessDto {
+        status: if ready { "pass" } else { "fail" }.to_string(),
+        checks,
+    };
+
+    (status, Json(report))
+}
+
+async fn check(name: &str, probe: impl Future<Output = Result<()>>) -> CheckDto {
+    let result = match tokio::time::timeout(CHECK_TIMEOUT, probe).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow!("No answer within {:?}.", CHECK_TIMEOUT)),
+    };
+
+    match result {
+        Ok(()) => CheckDto {
+            status: "pass".to_string(),
+            output: None,
+        },
+        Err(e) => {
+            tracing::warn!("Readiness check {} failed: {:#}", name, e);
+            CheckDto {
+                status: "fail".to_string(),
+                output: Some(format!("{:#}", e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Path, Query};
+use axum::Json;
+use axum::{
+    extract::State,
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, LOCATION},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::application::auth::RequireAuth;
+use crate::application::imports::{import_csv, ImportOutcome};
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::import::ImportEntity;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::import_queries::{get_import_by_id, get_import_error_file};
+use crate::models::import_dto::{ImportDto, ImportQuery};
+
+const MAX_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+// The import handler is generic over the entity, so its path is documented once per entity.
+macro_rules! import_docs {
+    ($docs:ident, $tag:literal, $path:literal) => {
+        #[allow(dead_code)]
+        mod $docs {
+            #[allow(unused_imports)]
+            use crate::models::import_dto::{ImportQuery, ImportReportDto};
+
+            /// Validates a CSV file row by row and, in commit mode, creates or updates by email every
+            /// valid row in one transaction.
+            #[utoipa::path(
+                post,
+                path = $path,
+                tag = $tag,
+                params(ImportQuery),
+                request_body(content = String, content_type = "text/csv"),
+                responses(
+                    (status = 200, description = "Dry run report", body = ImportReportDto),
+                    (status = 201, description = "Committed import", body = ImportReportDto, headers(("Location" = String))),
+                    (status = 409, description = "Records matched by email changed during the import"),
+                    (status = 415, description = "The body is not text/csv"),
+                    (status = 422, description = "Unreadable header, missing columns or too many rows"),
+                )
+            )]
+            pub fn import() {}
+        }
+    };
+}
+
+import_docs!(customer_docs, "customers", "/customers/import");
+import_docs!(vendor_docs, "vendors", "/vendors/import");
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    customer_docs::import,
+    vendor_docs::import,
+    import_summary_handler,
+    import_errors_handler
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/customers/import", post(import_handler::<Customers>))
+        .route("/vendors/import", post(import_handler::<Vendors>))
+        .layer(DefaultBodyLimit::max(MAX_FILE_SIZE))
+        .route("/imports/:id", get(import_summary_handler))
+        .route("/imports/:id/errors", get(import_errors_handler))
+}
+
+// The import handler is generic over the entity, which these stand for.
+trait Imported: Send + 'static {
+    const ENTITY: ImportEntity;
+}
+
+struct Customers;
+
+impl Imported for Customers {
+    const ENTITY: ImportEntity = ImportEntity::Customers;
+}
+
+struct Vendors;
+
+impl Imported for Vendors {
+    const ENTITY: ImportEntity = ImportEntity::Vendors;
+}
+
+/// Validates a CSV file row by row and, in commit mode, creates or updates by email every valid row
+/// in one transaction, see [`import_csv`].
+async fn import_handler<E: Imported>(
+    claims: RequireAuth,
+    Query(query): Query<ImportQuery>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let is_csv = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/csv"));
+    if !is_csv {
+        return Ok((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Imports must be sent as text/csv.",
+        )
+            .into_response());
+    }
+
+    let outcome = import_csv(
+        E::ENTITY,
+        db_pool,
+        tenant,
+        &body,
+        query.mode,
+        claims.username(),
+    )
+    .await?;
+
+    let report = match outcome {
+        ImportOutcome::Rejected(message) => {
+            return Ok((StatusCode::UNPROCESSABLE_ENTITY, message).into_response())
+        }
+        ImportOutcome::Conflict(message) => {
+            return Ok((StatusCode::CONFLICT, message).into_response())
+        }
+        ImportOutcome::Report(report) => report,
+    };
+
+    match report.import_id {
+        None => Ok(Json(report).into_response()),
+        Some(id) => {
+            let location_header = [(LOCATION, format!("/v1/api/imports/{}", id))];
+
+            Ok((StatusCode::CREATED, location_header, Json(report)).into_response())
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/imports/{id}",
+    tag = "imports",
+    params(("id" = i32, Path, description = "Import id")),
+    responses(
+        (status = 200, body = ImportDto),
+        (status = 404, description = "Unknown import"),
+    )
+)]
+async fn import_summary_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    match get_import_by_id(db_pool, &tenant, id).await? {
+        Some(i) => Ok((StatusCode::OK, Json(i)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+/// The rejected rows of a committed import, as uploaded plus the line and the reasons.
+#[utoipa::path(
+    get,
+    path = "/imports/{id}/errors",
+    tag = "imports",
+    params(("id" = i32, Path, description = "Import id")),
+    responses(
+        (status = 200, description = "The rejected rows", content_type = "text/csv", body = String),
+        (status = 404, description = "Unknown import or no rejected rows"),
+    )
+)]
+async fn import_errors_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(error_file) = get_import_error_file(db_pool, &tenant, id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    let headers = [
+        (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"import-{}-errors.csv\"", id),
+        ),
+    ];
+
+    Ok((StatusCode::OK, headers, error_file).into_response())
+}
@@ -11,5 +11,5 @@ pub fn router() -> Router<AppState> {
 }
 
 async fn index_handler(claims: RequireAuth) -> impl IntoResponse {
-    Html(format!("Hey {}. You're logged in!\nYou may now access <a href='/protected'>Protected</a>.\nLog out with <a href='/logout'>Logout</a>.", claims.name))
+    Html(format!("Hey {}. You're logged in!\nYou may now access <a href='/protected'>Protected</a>.\nLog out with <a href='/logout'>Logout</a>.", claims.name.as_deref().unwrap_or(claims.username())))
 }
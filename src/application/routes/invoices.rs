@@ -0,0 +1,424 @@
+use anyhow::{anyhow, Result};
+use axum::extract::Path;
+use axum::Json;
+use axum::{
+    extract::State,
+    http::header::LOCATION,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::invoice::Invoice;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::{
+    customer_queries::get_customer_by_id, invoice_queries::list_invoices_by_customer,
+};
+use crate::infrastructure::repositories::invoice_repository::{InvoiceRepository, Repository};
+use crate::models::invoice_dto::{
+    CreateCreditNoteRequest, GenerateInvoiceRequest, InvoiceDto, InvoiceLineDto, InvoiceSummaryDto,
+    VoidInvoiceRequest,
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    invoices_list_handler,
+    generate_invoice_handler,
+    invoice_handler,
+    issue_invoice_handler,
+    void_invoice_handler,
+    create_credit_note_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/customers/:id/invoices",
+            get(invoices_list_handler).post(generate_invoice_handler),
+        )
+        .route("/invoices/:id", get(invoice_handler))
+        .route("/invoices/:id/issue", post(issue_invoice_handler))
+        .route("/invoices/:id/void", post(void_invoice_handler))
+        .route(
+            "/invoices/:id/credit-notes",
+            post(create_credit_note_handler),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/customers/{id}/invoices",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Customer id")),
+    responses(
+        (status = 200, body = [InvoiceSummaryDto]),
+        (status = 404, description = "Unknown customer"),
+    )
+)]
+async fn invoices_list_handler(
+    Path(customer_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if get_customer_by_id(db_pool.clone(), &tenant, customer_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let invoices = list_invoices_by_customer(db_pool, &tenant, customer_id).await?;
+
+    Ok(Json(invoices).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/invoices/{id}",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Invoice id")),
+    responses(
+        (status = 200, body = InvoiceDto),
+        (status = 404, description = "Unknown invoice"),
+    )
+)]
+async fn invoice_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    match InvoiceRepository::new(db_pool, tenant).by_id(id).await? {
+        Some(i) => Ok((StatusCode::OK, Json(invoice_dto(&i))).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+/// Drafts an invoice billing the customer's delivered orders at their order line prices.
+#[utoipa::path(
+    post,
+    path = "/customers/{id}/invoices",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Customer id")),
+    request_body = GenerateInvoiceRequest,
+    responses(
+        (status = 201, body = InvoiceDto, headers(("Location" = String))),
+        (status = 404, description = "Unknown customer"),
+        (status = 409, description = "The orders have just been invoiced"),
+        (status = 422, description = "Validation errors, or no delivered order left to invoice"),
+    )
+)]
+async fn generate_invoice_handler(
+    Path(customer_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<GenerateInvoiceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    if get_customer_by_id(db_pool.clone(), &tenant, customer_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let repo = InvoiceRepository::new(db_pool, tenant);
+
+    let lines = repo
+        .uninvoiced_lines(customer_id, req.order_ids.clone())
+        .await?;
+
+    if lines.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "There are no delivered orders left to invoice.",
+        )
+            .into_response());
+    }
+
+    let invoice = Invoice::draft(customer_id, lines);
+
+    // Orders asked for explicitly must all be billable.
+    if let Some(order_ids) = &req.order_ids {
+        let billable = invoice.order_ids();
+        let not_billable: Vec<i32> = order_ids
+            .iter()
+            .copied()
+            .filter(|id| !billable.contains(id))
+            .collect();
+
+        if !not_billable.is_empty() {
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!(
+                    "Orders are unknown, not delivered or already invoiced: {:?}.",
+                    not_billable
+                ),
+            )
+                .into_response());
+        }
+    }
+
+    let Some(id) = repo.create(&invoice).await? else {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Some of these orders have just been invoiced.",
+        )
+            .into_response());
+    };
+
+    let invoice = repo
+        .by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("Invoice {} vanished after being created.", id))?;
+
+    let location_header = [(LOCATION, format!("/v1/api/invoices/{}", id))];
+
+    Ok((
+        StatusCode::CREATED,
+        location_header,
+        Json(invoice_dto(&invoice)),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/invoices/{id}/issue",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Invoice id")),
+    responses(
+        (status = 200, body = InvoiceDto),
+        (status = 404, description = "Unknown invoice"),
+        (status = 409, description = "Only drafts with lines can be issued"),
+    )
+)]
+async fn issue_invoice_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let repo = InvoiceRepository::new(db_pool, tenant);
+
+    let Some(invoice) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !invoice.can_issue() {
+        return Ok((
+            StatusCode::CONFLICT,
+            format!(
+                "Only draft invoices with lines can be issued, this invoice is {}.",
+                invoice.invoice_status
+            ),
+        )
+            .into_response());
+    }
+
+    if repo.issue(&invoice, Utc::now()).await?.is_none() {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This invoice has already been issued.",
+        )
+            .into_response());
+    }
+
+    match repo.by_id(id).await? {
+        Some(i) => Ok((StatusCode::OK, Json(invoice_dto(&i))).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/invoices/{id}/void",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Invoice id")),
+    request_body = VoidInvoiceRequest,
+    responses(
+        (status = 200, body = InvoiceDto),
+        (status = 404, description = "Unknown invoice"),
+        (status = 409, description = "The invoice cannot be voided"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn void_invoice_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<VoidInvoiceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = InvoiceRepository::new(db_pool, tenant);
+
+    let Some(mut invoice) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    let has_credit_notes = repo.has_credit_notes(id).await?;
+
+    if !invoice.can_void(has_credit_notes) {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Void and credited invoices cannot be voided.",
+        )
+            .into_response());
+    }
+
+    invoice.void(&req.reason, Utc::now(), has_credit_notes);
+
+    if !repo.void(&invoice).await? {
+        return Ok((StatusCode::CONFLICT, "This invoice has changed meanwhile.").into_response());
+    }
+
+    Ok(Json(invoice_dto(&invoice)).into_response())
+}
+
+/// Credits the given quantities of an issued invoice, or everything not credited yet.
+#[utoipa::path(
+    post,
+    path = "/invoices/{id}/credit-notes",
+    tag = "invoices",
+    params(("id" = i32, Path, description = "Invoice id")),
+    request_body = CreateCreditNoteRequest,
+    responses(
+        (status = 201, body = InvoiceDto, headers(("Location" = String))),
+        (status = 404, description = "Unknown invoice"),
+        (status = 409, description = "The invoice cannot be credited this much"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn create_credit_note_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateCreditNoteRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = InvoiceRepository::new(db_pool, tenant);
+
+    let Some(invoice) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !invoice.can_credit() {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Only issued invoices can be credited.",
+        )
+            .into_response());
+    }
+
+    let credited = repo.credited_quantities(id).await?;
+
+    let quantities: Vec<(i32, i32)> = match &req.lines {
+        Some(lines) => lines
+            .iter()
+            .map(|l| (l.invoice_line_id, l.quantity))
+            .collect(),
+        None => invoice
+            .lines
+            .iter()
+            .map(|l| {
+                (
+                    l.id,
+                    l.quantity - credited.get(&l.id).copied().unwrap_or_default(),
+                )
+            })
+            .filter(|(_, quantity)| *quantity > 0)
+            .collect(),
+    };
+
+    let unknown: Vec<i32> = quantities
+        .iter()
+        .map(|(line_id, _)| *line_id)
+        .filter(|line_id| !invoice.lines.iter().any(|l| l.id == *line_id))
+        .collect();
+
+    if !unknown.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Lines are not on this invoice: {:?}.", unknown),
+        )
+            .into_response());
+    }
+
+    if quantities.is_empty() {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This invoice has been fully credited.",
+        )
+            .into_response());
+    }
+
+    let credit_note = invoice.credit_note(&req.reason, &quantities);
+
+    let Some(credit_note_id) = repo.create_credit_note(&credit_note, Utc::now()).await? else {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Lines cannot be credited more than was invoiced.",
+        )
+            .into_response());
+    };
+
+    let credit_note = repo.by_id(credit_note_id).await?.ok_or_else(|| {
+        anyhow!(
+            "Credit note {} vanished after being created.",
+            credit_note_id
+        )
+    })?;
+
+    let location_header = [(LOCATION, format!("/v1/api/invoices/{}", credit_note_id))];
+
+    Ok((
+        StatusCode::CREATED,
+        location_header,
+        Json(invoice_dto(&credit_note)),
+    )
+        .into_response())
+}
+
+fn invoice_dto(invoice: &Invoice) -> InvoiceDto {
+    InvoiceDto {
+        id: invoice.id(),
+        customer_id: invoice.customer_id,
+        invoice_type: invoice.invoice_type.to_string(),
+        status: invoice.invoice_status.to_string(),
+        invoice_number: invoice.invoice_number.clone(),
+        credited_invoice_id: invoice.credited_invoice_id,
+        reason: invoice.reason.clone(),
+        issued_at: invoice.issued_at,
+        voided_at: invoice.voided_at,
+        void_reason: invoice.void_reason.clone(),
+        total: invoice.total(),
+        lines: invoice
+            .lines
+            .iter()
+            .map(|l| InvoiceLineDto {
+                id: l.id,
+                order_id: l.order_id,
+                item_id: l.item_id,
+                credited_line_id: l.credited_line_id,
+                description: l.description.clone(),
+                quantity: l.quantity,
+                unit_price: l.unit_price,
+                line_total: l.total(),
+            })
+            .collect(),
+    }
+}
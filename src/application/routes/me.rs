@@ -4,28 +4,41 @@ use axum::{
     Router,
 };
 use serde::Serialize;
+use utoipa::{OpenApi, ToSchema};
 
 use crate::application::auth::RequireAuth;
 use crate::application::utils::app_state::AppState;
 
+#[derive(OpenApi)]
+#[openapi(paths(me_handler))]
+pub struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new().route("/me", get(me_handler))
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct Me {
     id: String,
-    display_name: String,
+    /// Absent for an application signed in as itself.
+    display_name: Option<String>,
     username: String,
     roles: Vec<String>,
 }
 
+/// The signed in user or application, from the claims of the access token.
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "me",
+    responses((status = 200, body = Me))
+)]
 async fn me_handler(claims: RequireAuth) -> impl IntoResponse {
     Json(Me {
-        id: claims.oid,
+        id: claims.principal().to_string(),
+        username: claims.username().to_string(),
         display_name: claims.name,
-        username: claims.preferred_username,
         roles: claims.roles,
     })
 }
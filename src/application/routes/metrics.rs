@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse, routing::get, Router};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization},
+    TypedHeader,
+};
+use http::header::CONTENT_TYPE;
+use sqlx::PgPool;
+
+use crate::application::config::Config;
+use crate::application::metrics::{Gauge, Metrics};
+use crate::application::utils::{app_state::AppState, http_utils::AuthError};
+use crate::infrastructure::queries::metrics_queries::{
+    count_orders_by_status, count_vehicles_by_availability,
+};
+use crate::infrastructure::tenancy;
+
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/metrics", get(metrics_handler))
+}
+
+/// Everything Prometheus scrapes, with the pool and business gauges read now. A gauge whose query
+/// fails is left out rather than failing the scrape, the request counters are still worth having.
+/// The business gauges count the rows of every tenant.
+async fn metrics_handler(
+    authorization: Option<TypedHeader<Authorization<Bearer>>>,
+    State(db_pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(metrics): State<Arc<Metrics>>,
+) -> Result<impl IntoResponse, AuthError> {
+    if let Some(token) = &config.metrics.token {
+        match authorization {
+            Some(TypedHeader(Authorization(bearer))) if bearer.token() == token => {}
+            _ => return Err(AuthError),
+        }
+    }
+
+    let mut gauges = pool_gauges(&db_pool);
+
+    match tenancy::across_tenants(count_orders_by_status(db_pool.clone())).await {
+        Ok(counts) => gauges.push(Gauge {
+            name: "tsm_orders",
+            help: "Orders, by status.",
+            samples: counts
+                .into_iter()
+                .map(|(status, orders)| (vec![("status", status)], orders as f64))
+                .collect(),
+        }),
+        Err(e) => tracing::warn!("Cannot count orders for metrics: {:#}", e),
+    }
+
+    match tenancy::across_tenants(count_vehicles_by_availability(db_pool)).await {
+        Ok((available, unavailable)) => gauges.push(Gauge {
+            name: "tsm_vehicles",
+            help: "Vehicles, by availability.",
+            samples: vec![
+                (
+                    vec![("availability", "available".to_string())],
+                    available as f64,
+                ),
+                (
+                    vec![("availability", "unavailable".to_string())],
+                    unavailable as f64,
+                ),
+            ],
+        }),
+        Err(e) => tracing::warn!("Cannot count vehicles for metrics: {:#}", e),
+    }
+
+    Ok(([(CONTENT_TYPE, TEXT_FORMAT)], metrics.render(&gauges)))
+}
+
+fn pool_gauges(db_pool: &PgPool) -> Vec<Gauge> {
+    let size = db_pool.size();
+    let idle = db_pool.num_idle() as u32;
+
+    vec![
+        Gauge {
+            name: "tsm_db_pool_connections",
+            help: "Open database connections, by state.",
+            samples: vec![
+                (vec![("state", "idle".to_string())], f64::from(idle)),
+                (
+                    vec![("state", "in_use".to_string())],
+                    f64::from(size.saturating_sub(idle)),
+                ),
+            ],
+        },
+        Gauge {
+            name: "tsm_db_pool_max_connections",
+            help: "The most database connections the pool opens.",
+            samples: vec![(vec![], f64::from(db_pool.options().get_max_connections()))],
+        },
+    ]
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use axum::extract::{Path, Query};
+use axum::Json;
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    routing::{get, put},
+    Router,
+};
+use chrono::Utc;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::{Validate, ValidationErrors};
+
+use crate::application::utils::{
+    app_state::AppState,
+    export::{list_response, FormatQuery, ListFormat},
+    http_utils::AppError,
+};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::order_queries::{list_orders_by_ids, stream_orders};
+use crate::infrastructure::repositories::order_repository::{OrderRepository, Repository as _};
+use crate::models::order_dto::{window_error, OrderDto, OrdersQuery, ScheduleOrderRequest};
+
+#[derive(OpenApi)]
+#[openapi(paths(orders_list_handler, order_handler, schedule_order_handler))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/orders", get(orders_list_handler))
+        .route("/orders/:id", get(order_handler))
+        .route("/orders/:id/time-windows", put(schedule_order_handler))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders",
+    tag = "orders",
+    params(OrdersQuery, FormatQuery),
+    responses(
+        (status = 200, description = "Orders newest first, as JSON, CSV or XLSX", content(
+            (Vec<OrderDto> = "application/json"),
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        )),
+        (status = 400, description = "Unknown format"),
+    )
+)]
+async fn orders_list_handler(
+    Query(query): Query<OrdersQuery>,
+    format: ListFormat,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let orders = stream_orders(db_pool, tenant, query.status, query.due);
+
+    Ok(list_response(format, "orders", orders).await?)
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{id}",
+    tag = "orders",
+    params(("id" = i32, Path, description = "Order id")),
+    responses(
+        (status = 200, body = OrderDto),
+        (status = 404, description = "Unknown order"),
+    )
+)]
+async fn order_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let order = list_orders_by_ids(db_pool, &tenant, &[id]).await?.pop();
+
+    match order {
+        Some(o) => Ok((StatusCode::OK, Json(o)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+/// Windows must not have opened yet, and the pickup window can neither open nor close after the
+/// delivery window. Route plans deliver within the delivery window and leave the depot once the
+/// goods can be collected.
+#[utoipa::path(
+    put,
+    path = "/orders/{id}/time-windows",
+    tag = "orders",
+    params(("id" = i32, Path, description = "Order id")),
+    request_body = ScheduleOrderRequest,
+    responses(
+        (status = 200, body = OrderDto),
+        (status = 404, description = "Unknown order"),
+        (status = 409, description = "The order is already on its way, delivered or cancelled"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn schedule_order_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<ScheduleOrderRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = OrderRepository::new(db_pool.clone(), tenant.clone());
+
+    let Some(mut order) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    let not_scheduled = (
+        StatusCode::CONFLICT,
+        "Only pending or confirmed orders can be scheduled.",
+    );
+
+    if !order.can_schedule() {
+        return Ok(not_scheduled.into_response());
+    }
+
+    if let Err(e) = order.schedule(req.to_domain()?, Utc::now()) {
+        let mut errors = ValidationErrors::new();
+        errors.add("__all__", window_error(&e));
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    if !repo.schedule(&order).await? {
+        return Ok(not_scheduled.into_response());
+    }
+
+    let order = list_orders_by_ids(db_pool, &tenant, &[id]).await?.pop();
+
+    Ok(Json(order).into_response())
+}
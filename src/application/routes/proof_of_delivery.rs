@@ -0,0 +1,279 @@
+use anyhow::Result;
+use axum::extract::{DefaultBodyLimit, Multipart, Path};
+use axum::response::Response;
+use axum::Json;
+use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
+use chrono::{DateTime, Utc};
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::proof_of_delivery::{
+    DeliveryFile, DeliveryFileKind, ProofOfDelivery,
+};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::proof_of_delivery_queries::{
+    get_proof_of_delivery_by_order_id, get_proof_of_delivery_file,
+};
+use crate::infrastructure::repositories::order_repository::{OrderRepository, Repository as _};
+use crate::infrastructure::repositories::proof_of_delivery_repository::{
+    ProofOfDeliveryRepository, Repository as _,
+};
+use crate::models::proof_of_delivery_dto::{
+    ProofOfDeliveryDto, ProofOfDeliveryForm, RecordProofOfDeliveryRequest,
+};
+
+// Photos straight off a phone camera are well above axum's 2MB default.
+const MAX_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    proof_of_delivery_handler,
+    record_proof_of_delivery_handler,
+    proof_of_delivery_file_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/orders/:id/proof-of-delivery",
+            get(proof_of_delivery_handler).post(record_proof_of_delivery_handler),
+        )
+        .route(
+            "/orders/:id/proof-of-delivery/:kind",
+            get(proof_of_delivery_file_handler),
+        )
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{id}/proof-of-delivery",
+    tag = "orders",
+    params(("id" = i32, Path, description = "Order id")),
+    responses(
+        (status = 200, body = ProofOfDeliveryDto),
+        (status = 404, description = "No proof of delivery for this order"),
+    )
+)]
+async fn proof_of_delivery_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let proof_of_delivery = get_proof_of_delivery_by_order_id(db_pool, &tenant, id).await?;
+
+    match proof_of_delivery {
+        Some(p) => Ok((StatusCode::OK, Json(p)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{id}/proof-of-delivery/{kind}",
+    tag = "orders",
+    params(("id" = i32, Path, description = "Order id"), ("kind" = String, Path, description = "`signature` or `photo`")),
+    responses(
+        (status = 200, description = "The uploaded image", content_type = "image/*"),
+        (status = 404, description = "No such file"),
+    )
+)]
+async fn proof_of_delivery_file_handler(
+    Path((id, kind)): Path<(i32, String)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let kind = match kind.as_str() {
+        "signature" => DeliveryFileKind::Signature,
+        "photo" => DeliveryFileKind::Photo,
+        _ => return Ok((StatusCode::NOT_FOUND).into_response()),
+    };
+
+    let file = get_proof_of_delivery_file(db_pool, &tenant, id, kind.as_str()).await?;
+
+    match file {
+        Some(f) => Ok((
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, f.content_type),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("inline; filename=\"{}\"", f.file_name.replace('"', "")),
+                ),
+            ],
+            f.content,
+        )
+            .into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/orders/{id}/proof-of-delivery",
+    tag = "orders",
+    params(("id" = i32, Path, description = "Order id")),
+    request_body(content = ProofOfDeliveryForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, body = ProofOfDeliveryDto, headers(("Location" = String))),
+        (status = 400, description = "Malformed form"),
+        (status = 404, description = "Unknown order"),
+        (status = 409, description = "The order is not in transit"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn record_proof_of_delivery_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let order_repo = OrderRepository::new(db_pool.clone(), tenant.clone());
+
+    let Some(mut order) = order_repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !order.can_record_delivery() {
+        return Ok(order_not_in_transit(&order.order_status.to_string()));
+    }
+
+    let (req, files) = match read_proof_of_delivery_form(multipart).await {
+        Ok(form) => form,
+        Err(response) => return Ok(response),
+    };
+
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let mut proof_of_delivery = ProofOfDelivery::new(
+        0,
+        order.id(),
+        &req.recipient_name,
+        req.delivered_at,
+        req.latitude,
+        req.longitude,
+        req.notes.as_deref(),
+    );
+
+    for file in files {
+        proof_of_delivery.attach(file);
+    }
+
+    order.mark_delivered();
+
+    let repo = ProofOfDeliveryRepository::new(db_pool.clone(), tenant.clone());
+
+    if repo.create(&order, &proof_of_delivery).await?.is_none() {
+        return Ok(order_not_in_transit("no longer in transit"));
+    }
+
+    let dto = get_proof_of_delivery_by_order_id(db_pool, &tenant, id).await?;
+
+    let location_header = [(LOCATION, format!("/v1/api/orders/{}/proof-of-delivery", id))];
+
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
+}
+
+fn order_not_in_transit(status: &str) -> Response {
+    (
+        StatusCode::CONFLICT,
+        format!(
+            "Proof of delivery can only be recorded for in-transit orders, this order is {}.",
+            status
+        ),
+    )
+        .into_response()
+}
+
+async fn read_proof_of_delivery_form(
+    mut multipart: Multipart,
+) -> Result<(RecordProofOfDeliveryRequest, Vec<DeliveryFile>), Response> {
+    let mut recipient_name = None;
+    let mut delivered_at = None;
+    let mut latitude = None;
+    let mut longitude = None;
+    let mut notes = None;
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(IntoResponse::into_response)?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+
+        let kind = match name.as_str() {
+            "signature" => Some(DeliveryFileKind::Signature),
+            "photo" => Some(DeliveryFileKind::Photo),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            let file_name = field.file_name().unwrap_or(kind.as_str()).to_string();
+            let content_type = field.content_type().unwrap_or_default().to_string();
+
+            if !content_type.starts_with("image/") {
+                return Err(bad_request(&format!("{} must be an image.", name)));
+            }
+
+            let content = field.bytes().await.map_err(IntoResponse::into_response)?;
+
+            files.push(DeliveryFile {
+                kind,
+                file_name,
+                content_type,
+                content: content.to_vec(),
+            });
+
+            continue;
+        }
+
+        let value = field.text().await.map_err(IntoResponse::into_response)?;
+
+        match name.as_str() {
+            "recipientName" => recipient_name = Some(value),
+            "deliveredAt" => {
+                let parsed = DateTime::parse_from_rfc3339(&value)
+                    .map_err(|_| bad_request("deliveredAt must be an RFC 3339 timestamp."))?;
+                delivered_at = Some(parsed.with_timezone(&Utc));
+            }
+            "latitude" => {
+                latitude = Some(
+                    value
+                        .parse::<f64>()
+                        .map_err(|_| bad_request("latitude must be a number."))?,
+                );
+            }
+            "longitude" => {
+                longitude = Some(
+                    value
+                        .parse::<f64>()
+                        .map_err(|_| bad_request("longitude must be a number."))?,
+                );
+            }
+            "notes" if !value.is_empty() => notes = Some(value),
+            _ => (),
+        }
+    }
+
+    let req = RecordProofOfDeliveryRequest {
+        recipient_name: recipient_name.ok_or_else(|| bad_request("recipientName is required."))?,
+        delivered_at: delivered_at.ok_or_else(|| bad_request("deliveredAt is required."))?,
+        latitude: latitude.ok_or_else(|| bad_request("latitude is required."))?,
+        longitude: longitude.ok_or_else(|| bad_request("longitude is required."))?,
+        notes,
+    };
+
+    Ok((req, files))
+}
+
+fn bad_request(message: &str) -> Response {
+    (StatusCode::BAD_REQUEST, message.to_string()).into_response()
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use axum::Json;
+use axum::{extract::State, response::IntoResponse, routing::post, Router};
+use chrono::Utc;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::{
+    route_queries::get_route_distance, vehicle_queries::list_available_vehicles,
+};
+use crate::infrastructure::repositories::rate_card_repository::{RateCardRepository, Repository};
+use crate::models::rate_card_dto::{QuoteDto, QuoteRequest};
+
+#[derive(OpenApi)]
+#[openapi(paths(create_quotes_handler,))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/quotes", post(create_quotes_handler))
+}
+
+/// Prices the shipment against the rate card of every vendor with an available vehicle of the
+/// card's type big enough for the weight, cheapest first.
+#[utoipa::path(
+    post,
+    path = "/quotes",
+    tag = "quotes",
+    request_body = QuoteRequest,
+    responses(
+        (status = 200, description = "Quotes cheapest first", body = [QuoteDto]),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn create_quotes_handler(
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<QuoteRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let distance = match (req.route_id, req.distance) {
+        (_, Some(distance)) => distance,
+        (Some(route_id), None) => match get_route_distance(db_pool.clone(), &tenant, route_id)
+            .await?
+        {
+            Some(distance) => distance,
+            None => return Ok((StatusCode::UNPROCESSABLE_ENTITY, "Unknown route.").into_response()),
+        },
+        (None, None) => {
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Either a route or a distance is required.",
+            )
+                .into_response())
+        }
+    };
+
+    let ship_date = req.ship_date.unwrap_or_else(|| Utc::now().date_naive());
+
+    let rate_cards = RateCardRepository::new(db_pool.clone(), tenant.clone())
+        .effective_on(ship_date)
+        .await?;
+
+    let vehicles = list_available_vehicles(db_pool, &tenant, None).await?;
+
+    let mut quotes: Vec<QuoteDto> = rate_cards
+        .iter()
+        .filter(|c| {
+            req.vehicle_type
+                .as_ref()
+                .is_none_or(|t| t.eq_ignore_ascii_case(&c.vehicle_type))
+        })
+        .filter(|c| {
+            vehicles.iter().any(|v| {
+                v.vendor_id == c.vendor_id
+                    && v.vehicle_type.eq_ignore_ascii_case(&c.vehicle_type)
+                    && v.capacity >= req.weight
+            })
+        })
+        .filter_map(|c| {
+            let charge = c.price(distance, req.weight)?;
+
+            Some(QuoteDto {
+                rank: 0,
+                vendor_id: c.vendor_id,
+                rate_card_id: c.id(),
+                rate_card_name: c.name.clone(),
+                vehicle_type: c.vehicle_type.clone(),
+                distance,
+                weight: req.weight,
+                amount: charge.amount,
+                minimum_charge_applied: charge.minimum_charge_applied,
+            })
+        })
+        .collect();
+
+    quotes.sort_by(|a, b| {
+        (a.amount, a.vendor_id, a.rate_card_id).cmp(&(b.amount, b.vendor_id, b.rate_card_id))
+    });
+
+    for (index, quote) in quotes.iter_mut().enumerate() {
+        quote.rank = index + 1;
+    }
+
+    Ok(Json(quotes).into_response())
+}
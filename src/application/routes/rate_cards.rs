@@ -0,0 +1,256 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::Json;
+use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::rate_card::{Rate, RateCard};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::vendor_queries::get_vendor_by_id;
+use crate::infrastructure::repositories::rate_card_repository::{RateCardRepository, Repository};
+use crate::models::rate_card_dto::{CreateRateCardRequest, RateCardDto, RateDto};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    rate_cards_list_handler,
+    create_rate_card_handler,
+    rate_card_handler,
+    update_rate_card_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/vendors/:id/rate-cards",
+            get(rate_cards_list_handler).post(create_rate_card_handler),
+        )
+        .route(
+            "/vendors/:id/rate-cards/:rate_card_id",
+            get(rate_card_handler).put(update_rate_card_handler),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}/rate-cards",
+    tag = "rate cards",
+    params(("id" = i32, Path, description = "Vendor id")),
+    responses(
+        (status = 200, body = [RateCardDto]),
+        (status = 404, description = "Unknown vendor"),
+    )
+)]
+async fn rate_cards_list_handler(
+    Path(vendor_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let rate_cards: Vec<RateCardDto> = RateCardRepository::new(db_pool, tenant)
+        .by_vendor(vendor_id)
+        .await?
+        .iter()
+        .map(rate_card_dto)
+        .collect();
+
+    Ok(Json(rate_cards).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}/rate-cards/{rate_card_id}",
+    tag = "rate cards",
+    params(("id" = i32, Path, description = "Vendor id"), ("rate_card_id" = i32, Path, description = "Rate card id")),
+    responses(
+        (status = 200, body = RateCardDto),
+        (status = 404, description = "Unknown rate card"),
+    )
+)]
+async fn rate_card_handler(
+    Path((vendor_id, id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    match RateCardRepository::new(db_pool, tenant).by_id(id).await? {
+        Some(c) if c.vendor_id == vendor_id => {
+            Ok((StatusCode::OK, Json(rate_card_dto(&c))).into_response())
+        }
+        _ => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/vendors/{id}/rate-cards",
+    tag = "rate cards",
+    params(("id" = i32, Path, description = "Vendor id")),
+    request_body = CreateRateCardRequest,
+    responses(
+        (status = 201, body = RateCardDto, headers(("Location" = String))),
+        (status = 404, description = "Unknown vendor"),
+        (status = 409, description = "Another card for the vehicle type is in effect over these dates"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn create_rate_card_handler(
+    Path(vendor_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateRateCardRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let repo = RateCardRepository::new(db_pool, tenant);
+
+    let mut rate_card_domain = RateCard::new(
+        0,
+        vendor_id,
+        &req.name,
+        &req.vehicle_type,
+        req.minimum_charge,
+        req.effective_from,
+        req.effective_to,
+    );
+    rate_card_domain.set_rates(rates(&req.rates));
+
+    if overlaps_existing(&repo, &rate_card_domain).await? {
+        return Ok(overlap_response().into_response());
+    }
+
+    let Some(id) = repo.create(&rate_card_domain).await? else {
+        return Ok(overlap_response().into_response());
+    };
+
+    let mut dto = rate_card_dto(&rate_card_domain);
+    dto.id = id;
+
+    let location_header = [(
+        LOCATION,
+        format!("/v1/api/vendors/{}/rate-cards/{}", vendor_id, id),
+    )];
+
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/vendors/{id}/rate-cards/{rate_card_id}",
+    tag = "rate cards",
+    params(("id" = i32, Path, description = "Vendor id"), ("rate_card_id" = i32, Path, description = "Rate card id")),
+    request_body = CreateRateCardRequest,
+    responses(
+        (status = 200, body = RateCardDto),
+        (status = 404, description = "Unknown rate card"),
+        (status = 409, description = "Another card for the vehicle type is in effect over these dates"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn update_rate_card_handler(
+    Path((vendor_id, id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateRateCardRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = RateCardRepository::new(db_pool, tenant);
+
+    let mut rate_card = match repo.by_id(id).await? {
+        Some(c) if c.vendor_id == vendor_id => c,
+        _ => return Ok((StatusCode::NOT_FOUND).into_response()),
+    };
+
+    rate_card.update(
+        &req.name,
+        &req.vehicle_type,
+        req.minimum_charge,
+        req.effective_from,
+        req.effective_to,
+    );
+    rate_card.set_rates(rates(&req.rates));
+
+    if overlaps_existing(&repo, &rate_card).await? {
+        return Ok(overlap_response().into_response());
+    }
+
+    if !repo.update(&rate_card).await? {
+        return Ok(overlap_response().into_response());
+    }
+
+    Ok(Json(rate_card_dto(&rate_card)).into_response())
+}
+
+// Only one card per vehicle type can be in effect for a vendor on any day. The repository has
+// the final say, this only saves a round trip in the common case.
+async fn overlaps_existing(repo: &RateCardRepository, rate_card: &RateCard) -> Result<bool> {
+    Ok(repo.by_vendor(rate_card.vendor_id).await?.iter().any(|c| {
+        c.id() != rate_card.id()
+            && c.vehicle_type.eq_ignore_ascii_case(&rate_card.vehicle_type)
+            && c.overlaps(rate_card)
+    }))
+}
+
+fn overlap_response() -> impl IntoResponse {
+    (
+        StatusCode::CONFLICT,
+        "Another rate card for this vehicle type is in effect over these dates.",
+    )
+}
+
+fn rates(rates: &[RateDto]) -> Vec<Rate> {
+    rates
+        .iter()
+        .map(|r| Rate {
+            min_distance: r.min_distance,
+            min_weight: r.min_weight,
+            base_charge: r.base_charge,
+            rate_per_km: r.rate_per_km,
+            rate_per_kg: r.rate_per_kg,
+        })
+        .collect()
+}
+
+fn rate_card_dto(rate_card: &RateCard) -> RateCardDto {
+    RateCardDto {
+        id: rate_card.id(),
+        vendor_id: rate_card.vendor_id,
+        name: rate_card.name.clone(),
+        vehicle_type: rate_card.vehicle_type.clone(),
+        minimum_charge: rate_card.minimum_charge,
+        effective_from: rate_card.effective_from,
+        effective_to: rate_card.effective_to,
+        rates: rate_card
+            .rates
+            .iter()
+            .map(|r| RateDto {
+                min_distance: r.min_distance,
+                min_weight: r.min_weight,
+                base_charge: r.base_charge,
+                rate_per_km: r.rate_per_km,
+                rate_per_kg: r.rate_per_kg,
+            })
+            .collect(),
+    }
+}
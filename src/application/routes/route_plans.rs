@@ -0,0 +1,391 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use axum::extract::Path;
+use axum::response::Response;
+use axum::Json;
+use axum::{
+    extract::State,
+    http::header::LOCATION,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::Duration;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::order::{OrderStatus, OrderWindows};
+use crate::domain::aggregates::route_plan::RoutePlan;
+use crate::domain::services::route_planner::{
+    plan_routes, to_distance, to_travel_time, PlanningProblem, PlanningVehicle, Stop,
+};
+use crate::domain::value_objects::coordinates::Coordinates;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::domain::value_objects::time_window::TimeWindow;
+use crate::infrastructure::queries::{
+    order_queries::list_delivery_coordinates, vehicle_queries::list_available_vehicles,
+};
+use crate::infrastructure::repositories::order_repository::{OrderRepository, Repository as _};
+use crate::infrastructure::repositories::route_plan_repository::{
+    Repository as _, RoutePlanRepository,
+};
+use crate::models::route_plan_dto::{
+    CoordinatesDto, PlanRoutesRequest, PlannedVehicleRouteDto, RouteDto, RouteLegDto, RoutePlanDto,
+};
+
+const DEFAULT_AVERAGE_SPEED_KMH: f64 = 50.0;
+const DEFAULT_SERVICE_MINUTES: i64 = 10;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_route_plan_handler,
+    route_plan_handler,
+    accept_route_plan_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/route-plans", post(create_route_plan_handler))
+        .route("/route-plans/:id", get(route_plan_handler))
+        .route("/route-plans/:id/accept", post(accept_route_plan_handler))
+}
+
+#[utoipa::path(
+    get,
+    path = "/route-plans/{id}",
+    tag = "route plans",
+    params(("id" = i32, Path, description = "Route plan id")),
+    responses(
+        (status = 200, body = RoutePlanDto),
+        (status = 404, description = "Unknown route plan"),
+    )
+)]
+async fn route_plan_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let repo = RoutePlanRepository::new(db_pool, tenant);
+
+    match repo.by_id(id).await? {
+        Some(p) => Ok((StatusCode::OK, Json(route_plan_dto(&p))).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/route-plans",
+    tag = "route plans",
+    request_body = PlanRoutesRequest,
+    responses(
+        (status = 201, body = RoutePlanDto, headers(("Location" = String))),
+        (status = 409, description = "No vehicles are available, or an order is not confirmed"),
+        (status = 422, description = "Validation errors, or orders without a location"),
+    )
+)]
+async fn create_route_plan_handler(
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<PlanRoutesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let order_ids: Vec<i32> = req.orders.iter().map(|o| o.order_id).collect();
+
+    if order_ids.iter().collect::<HashSet<_>>().len() != order_ids.len() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Each order can only be planned once.",
+        )
+            .into_response());
+    }
+
+    if let Some(response) = check_orders_confirmed(db_pool.clone(), &tenant, &order_ids).await? {
+        return Ok(response);
+    }
+
+    // Orders keep the windows they were scheduled with, a window in the request overrides them.
+    let windows: HashMap<i32, OrderWindows> = OrderRepository::new(db_pool.clone(), tenant.clone())
+        .by_ids(&order_ids)
+        .await?
+        .into_iter()
+        .filter_map(|o| Some((o.id(), o.windows.clone()?)))
+        .collect();
+
+    let customer_locations = list_delivery_coordinates(
+        db_pool.clone(),
+        &tenant,
+        req.orders
+            .iter()
+            .filter(|o| o.location.is_none())
+            .map(|o| o.order_id)
+            .collect(),
+    )
+    .await?;
+
+    let mut locations = HashMap::new();
+    let mut not_located = Vec::new();
+
+    for order in &req.orders {
+        match order
+            .location
+            .or_else(|| customer_locations.get(&order.order_id).copied())
+        {
+            Some(l) => {
+                locations.insert(order.order_id, Coordinates::new(l.latitude, l.longitude));
+            }
+            None => not_located.push(order.order_id),
+        }
+    }
+
+    if !not_located.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "No location given and the customer address is not geocoded for orders: {:?}.",
+                not_located
+            ),
+        )
+            .into_response());
+    }
+
+    let vehicles = list_available_vehicles(db_pool.clone(), &tenant, req.vehicle_ids).await?;
+
+    if vehicles.is_empty() {
+        return Ok((StatusCode::CONFLICT, "No vehicles are available.").into_response());
+    }
+
+    let depot = Coordinates::new(req.depot.latitude, req.depot.longitude);
+
+    let problem = PlanningProblem {
+        depot,
+        departure_at: req.departure_at,
+        average_speed_kmh: req.average_speed_kmh.unwrap_or(DEFAULT_AVERAGE_SPEED_KMH),
+        stops: req
+            .orders
+            .iter()
+            .map(|o| Stop {
+                order_id: o.order_id,
+                location: locations[&o.order_id],
+                load: o.load,
+                pickup_window: windows.get(&o.order_id).and_then(|w| w.pickup),
+                window: o
+                    .delivery_window
+                    .map(|w| TimeWindow {
+                        earliest: w.earliest,
+                        latest: w.latest,
+                    })
+                    .or_else(|| windows.get(&o.order_id).and_then(|w| w.delivery)),
+                service_time: Duration::minutes(
+                    o.service_minutes.unwrap_or(DEFAULT_SERVICE_MINUTES),
+                ),
+            })
+            .collect(),
+        vehicles: vehicles
+            .iter()
+            .map(|v| PlanningVehicle {
+                vehicle_id: v.id,
+                capacity: v.capacity,
+            })
+            .collect(),
+    };
+
+    // The solver is CPU bound, keep it off the async workers.
+    let plan = tokio::task::spawn_blocking(move || plan_routes(&problem)).await?;
+
+    let route_plan = RoutePlan::draft(depot, req.departure_at, plan);
+
+    let repo = RoutePlanRepository::new(db_pool, tenant);
+
+    let id = repo.create(&route_plan).await?;
+
+    let mut dto = route_plan_dto(&route_plan);
+    dto.id = id;
+
+    let location_header = [(LOCATION, format!("/v1/api/route-plans/{}", id))];
+
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
+}
+
+/// Writes the routes, assigns the orders and takes the vehicles out of service until their route
+/// is completed.
+#[utoipa::path(
+    post,
+    path = "/route-plans/{id}/accept",
+    tag = "route plans",
+    params(("id" = i32, Path, description = "Route plan id")),
+    responses(
+        (status = 200, body = RoutePlanDto),
+        (status = 404, description = "Unknown route plan"),
+        (status = 409, description = "The plan is stale or already accepted"),
+    )
+)]
+async fn accept_route_plan_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let repo = RoutePlanRepository::new(db_pool.clone(), tenant.clone());
+
+    let Some(mut route_plan) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !route_plan.can_accept() {
+        return Ok((
+            StatusCode::CONFLICT,
+            format!(
+                "Only draft plans with at least one route can be accepted, this plan is {}.",
+                route_plan.plan_status
+            ),
+        )
+            .into_response());
+    }
+
+    // Orders and vehicles may have moved on since the draft was proposed.
+    if let Some(response) =
+        check_orders_confirmed(db_pool.clone(), &tenant, &route_plan.order_ids()).await?
+    {
+        return Ok(response);
+    }
+
+    let available: HashSet<i32> =
+        list_available_vehicles(db_pool.clone(), &tenant, Some(route_plan.vehicle_ids()))
+            .await?
+            .into_iter()
+            .map(|v| v.id)
+            .collect();
+
+    if route_plan
+        .vehicle_ids()
+        .iter()
+        .any(|v| !available.contains(v))
+    {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Some vehicles in this plan are no longer available.",
+        )
+            .into_response());
+    }
+
+    route_plan.accept();
+
+    if !repo.accept(&route_plan).await? {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This plan has already been accepted, or another plan took some of its orders or vehicles.",
+        )
+            .into_response());
+    }
+
+    match repo.by_id(id).await? {
+        Some(p) => Ok((StatusCode::OK, Json(route_plan_dto(&p))).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+async fn check_orders_confirmed(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_ids: &[i32],
+) -> Result<Option<Response>, AppError> {
+    let orders = OrderRepository::new(db_pool, tenant.clone())
+        .by_ids(order_ids)
+        .await?;
+
+    let found: HashSet<i32> = orders.iter().map(|o| o.id()).collect();
+    let missing: Vec<i32> = order_ids
+        .iter()
+        .copied()
+        .filter(|id| !found.contains(id))
+        .collect();
+
+    if !missing.is_empty() {
+        return Ok(Some(
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Unknown orders: {:?}.", missing),
+            )
+                .into_response(),
+        ));
+    }
+
+    let not_confirmed: Vec<i32> = orders
+        .iter()
+        .filter(|o| o.order_status != OrderStatus::Confirmed)
+        .map(|o| o.id())
+        .collect();
+
+    if !not_confirmed.is_empty() {
+        return Ok(Some(
+            (
+                StatusCode::CONFLICT,
+                format!("Only confirmed orders can be planned: {:?}.", not_confirmed),
+            )
+                .into_response(),
+        ));
+    }
+
+    Ok(None)
+}
+
+fn route_plan_dto(route_plan: &RoutePlan) -> RoutePlanDto {
+    let routes = route_plan
+        .plan
+        .routes
+        .iter()
+        .map(|r| {
+            let legs = r.legs();
+
+            PlannedVehicleRouteDto {
+                vehicle_id: r.vehicle_id,
+                load: r.load,
+                route: RouteDto {
+                    id: route_plan.route_id_for(r.vehicle_id),
+                    origin: legs[0].origin.clone(),
+                    destination: legs[legs.len() - 1].destination.clone(),
+                    distance: to_distance(r.distance_km),
+                    estimated_travel_time: to_travel_time(r.duration_seconds()),
+                    departure_at: r.departure_at,
+                    finish_at: r.finish_at,
+                },
+                legs: legs
+                    .iter()
+                    .map(|leg| {
+                        let stop = r.stops.iter().find(|s| Some(s.order_id) == leg.order_id);
+
+                        RouteLegDto {
+                            order_id: leg.order_id,
+                            origin: leg.origin.clone(),
+                            destination: leg.destination.clone(),
+                            distance: leg.distance(),
+                            estimated_travel_time: leg.estimated_travel_time(),
+                            arrival_at: stop.map(|s| s.arrival_at),
+                            departure_at: stop.map(|s| s.departure_at),
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    RoutePlanDto {
+        id: route_plan.id(),
+        status: route_plan.plan_status.to_string(),
+        depot: CoordinatesDto {
+            latitude: route_plan.depot.latitude,
+            longitude: route_plan.depot.longitude,
+        },
+        departure_at: route_plan.departure_at,
+        routes,
+        unassigned_order_ids: route_plan.plan.unassigned_order_ids.clone(),
+    }
+}
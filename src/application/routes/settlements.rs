@@ -0,0 +1,412 @@
+use anyhow::{anyhow, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use axum::{
+    extract::State,
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, LOCATION},
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::auth::RequireAuth;
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::settlement::Settlement;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::settlement_queries::{
+    get_settlement_statement, list_approved_payables, list_settlements_by_vendor,
+};
+use crate::infrastructure::queries::vendor_queries::get_vendor_by_id;
+use crate::infrastructure::repositories::rate_card_repository::{
+    RateCardRepository, Repository as _,
+};
+use crate::infrastructure::repositories::settlement_repository::{
+    Repository as _, SettlementRepository,
+};
+use crate::models::settlement_dto::{
+    CreateSettlementRequest, PayablesExportQuery, RejectSettlementRequest, SettlementDraftDto,
+    SettlementDto, SettlementLineDto, SettlementStatementDto, SettlementSummaryDto,
+    UnpricedRouteDto,
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    settlements_list_handler,
+    create_settlement_handler,
+    settlement_handler,
+    settlement_statement_handler,
+    approve_settlement_handler,
+    reject_settlement_handler,
+    export_payables_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/vendors/:id/settlements",
+            get(settlements_list_handler).post(create_settlement_handler),
+        )
+        .route("/settlements/:id", get(settlement_handler))
+        .route(
+            "/settlements/:id/statement",
+            get(settlement_statement_handler),
+        )
+        .route("/settlements/:id/approve", post(approve_settlement_handler))
+        .route("/settlements/:id/reject", post(reject_settlement_handler))
+        .route("/payables/export", get(export_payables_handler))
+}
+
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}/settlements",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Vendor id")),
+    responses(
+        (status = 200, body = [SettlementSummaryDto]),
+        (status = 404, description = "Unknown vendor"),
+    )
+)]
+async fn settlements_list_handler(
+    Path(vendor_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let settlements = list_settlements_by_vendor(db_pool, &tenant, vendor_id).await?;
+
+    Ok(Json(settlements).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/settlements/{id}",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Settlement id")),
+    responses(
+        (status = 200, body = SettlementDto),
+        (status = 404, description = "Unknown settlement"),
+    )
+)]
+async fn settlement_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    match SettlementRepository::new(db_pool, tenant).by_id(id).await? {
+        Some(s) => Ok((StatusCode::OK, Json(settlement_dto(&s))).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/settlements/{id}/statement",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Settlement id")),
+    responses(
+        (status = 200, body = SettlementStatementDto),
+        (status = 404, description = "Unknown settlement"),
+    )
+)]
+async fn settlement_statement_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    match get_settlement_statement(db_pool, &tenant, id).await? {
+        Some(s) => Ok((StatusCode::OK, Json(s)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+/// Settles the routes the vendor's vehicles completed over the period at its agreed rates.
+#[utoipa::path(
+    post,
+    path = "/vendors/{id}/settlements",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Vendor id")),
+    request_body = CreateSettlementRequest,
+    responses(
+        (status = 201, body = SettlementDraftDto, headers(("Location" = String))),
+        (status = 404, description = "Unknown vendor"),
+        (status = 409, description = "Some routes have just been settled"),
+        (status = 422, description = "Validation errors, or no route could be priced", body = SettlementDraftDto),
+    )
+)]
+async fn create_settlement_handler(
+    Path(vendor_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateSettlementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let repo = SettlementRepository::new(db_pool.clone(), tenant.clone());
+
+    let routes = repo
+        .unsettled_routes(vendor_id, req.period_start, req.period_end)
+        .await?;
+
+    let rate_cards = RateCardRepository::new(db_pool, tenant)
+        .by_vendor(vendor_id)
+        .await?;
+
+    let (settlement, unpriced) = Settlement::draft(
+        vendor_id,
+        req.period_start,
+        req.period_end,
+        &routes,
+        &rate_cards,
+    );
+
+    let unpriced_routes: Vec<UnpricedRouteDto> = unpriced
+        .into_iter()
+        .map(|r| UnpricedRouteDto {
+            vehicle_id: r.vehicle_id,
+            route_id: r.route_id,
+            vehicle_type: r.vehicle_type,
+            completed_at: r.completed_at,
+        })
+        .collect();
+
+    if settlement.lines.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(SettlementDraftDto {
+                settlement: settlement_dto(&settlement),
+                unpriced_routes,
+            }),
+        )
+            .into_response());
+    }
+
+    let Some(id) = repo.create(&settlement).await? else {
+        return Ok((
+            StatusCode::CONFLICT,
+            "Some of these routes have just been settled.",
+        )
+            .into_response());
+    };
+
+    let settlement = repo
+        .by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("Settlement {} vanished after being created.", id))?;
+
+    let location_header = [(LOCATION, format!("/v1/api/settlements/{}", id))];
+
+    Ok((
+        StatusCode::CREATED,
+        location_header,
+        Json(SettlementDraftDto {
+            settlement: settlement_dto(&settlement),
+            unpriced_routes,
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/settlements/{id}/approve",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Settlement id")),
+    responses(
+        (status = 200, body = SettlementDto),
+        (status = 404, description = "Unknown settlement"),
+        (status = 409, description = "The settlement is not a draft"),
+    )
+)]
+async fn approve_settlement_handler(
+    claims: RequireAuth,
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let repo = SettlementRepository::new(db_pool, tenant);
+
+    let Some(mut settlement) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !settlement.can_review() {
+        return Ok(review_conflict(&settlement).into_response());
+    }
+
+    settlement.approve(claims.username(), Utc::now());
+
+    if !repo.review(&settlement).await? {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This settlement has just been reviewed.",
+        )
+            .into_response());
+    }
+
+    Ok(Json(settlement_dto(&settlement)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/settlements/{id}/reject",
+    tag = "settlements",
+    params(("id" = i32, Path, description = "Settlement id")),
+    request_body = RejectSettlementRequest,
+    responses(
+        (status = 200, body = SettlementDto),
+        (status = 404, description = "Unknown settlement"),
+        (status = 409, description = "The settlement is not a draft"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn reject_settlement_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<RejectSettlementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = SettlementRepository::new(db_pool, tenant);
+
+    let Some(mut settlement) = repo.by_id(id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !settlement.can_review() {
+        return Ok(review_conflict(&settlement).into_response());
+    }
+
+    settlement.reject(&req.reason, Utc::now());
+
+    if !repo.review(&settlement).await? {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This settlement has just been reviewed.",
+        )
+            .into_response());
+    }
+
+    Ok(Json(settlement_dto(&settlement)).into_response())
+}
+
+/// Approved settlements as CSV, one row per settlement, for the finance team to pay.
+#[utoipa::path(
+    get,
+    path = "/payables/export",
+    tag = "settlements",
+    params(PayablesExportQuery),
+    responses(
+        (status = 200, description = "Approved settlements, one per row", content_type = "text/csv", body = String),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn export_payables_handler(
+    Query(query): Query<PayablesExportQuery>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = query.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let payables = list_approved_payables(db_pool, &tenant, query.from, query.to).await?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for payable in &payables {
+        writer.serialize(payable)?;
+    }
+    // An empty export still gets its header row.
+    if payables.is_empty() {
+        writer.write_record(PAYABLE_COLUMNS)?;
+    }
+    let body = writer.into_inner().map_err(|e| anyhow!(e.to_string()))?;
+
+    let headers = [
+        (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"payables-{}-{}.csv\"",
+                query.from, query.to
+            ),
+        ),
+    ];
+
+    Ok((StatusCode::OK, headers, body).into_response())
+}
+
+const PAYABLE_COLUMNS: [&str; 10] = [
+    "settlement_id",
+    "vendor_id",
+    "vendor_name",
+    "vendor_email",
+    "period_start",
+    "period_end",
+    "approved_at",
+    "approved_by",
+    "route_count",
+    "amount",
+];
+
+fn review_conflict(settlement: &Settlement) -> impl IntoResponse {
+    (
+        StatusCode::CONFLICT,
+        format!(
+            "Only draft settlements can be reviewed, this settlement is {}.",
+            settlement.settlement_status
+        ),
+    )
+}
+
+fn settlement_dto(settlement: &Settlement) -> SettlementDto {
+    SettlementDto {
+        id: settlement.id(),
+        vendor_id: settlement.vendor_id,
+        period_start: settlement.period_start,
+        period_end: settlement.period_end,
+        status: settlement.settlement_status.to_string(),
+        approved_at: settlement.approved_at,
+        approved_by: settlement.approved_by.clone(),
+        rejected_at: settlement.rejected_at,
+        rejection_reason: settlement.rejection_reason.clone(),
+        total: settlement.total(),
+        lines: settlement
+            .lines
+            .iter()
+            .map(|l| SettlementLineDto {
+                vehicle_id: l.vehicle_id,
+                route_id: l.route_id,
+                rate_card_id: l.rate_card_id,
+                completed_at: l.completed_at,
+                distance: l.distance,
+                load: l.load,
+                amount: l.amount,
+                minimum_charge_applied: l.minimum_charge_applied,
+            })
+            .collect(),
+    }
+}
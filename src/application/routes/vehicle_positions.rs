@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::extract::{Path, Query};
+use axum::response::Response;
+use axum::Json;
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use chrono::{Duration, Utc};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::config::Config;
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::aggregates::vehicle::VehiclePosition;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::vehicle_queries::list_latest_vehicle_positions;
+use crate::infrastructure::repositories::vehicle_position_repository::{
+    Repository as _, VehiclePositionRepository,
+};
+use crate::infrastructure::repositories::vehicle_repository::{Repository as _, VehicleRepository};
+use crate::models::vehicle_dto::{
+    LatestVehiclePositionDto, LatestVehiclePositionsQuery, RecordVehiclePositionsRequest,
+    RecordVehiclePositionsResponse, VehiclePositionDto,
+};
+
+// Device clocks drift, so allow fixes slightly ahead of the server clock.
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    latest_vehicle_positions_handler,
+    record_vehicle_position_handler,
+    record_vehicle_positions_batch_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/vehicles/positions/latest",
+            get(latest_vehicle_positions_handler),
+        )
+        .route(
+            "/vehicles/:id/positions",
+            post(record_vehicle_position_handler),
+        )
+        .route(
+            "/vehicles/:id/positions/batch",
+            post(record_vehicle_positions_batch_handler),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/vehicles/positions/latest",
+    tag = "vehicles",
+    params(LatestVehiclePositionsQuery),
+    responses(
+        (status = 200, body = [LatestVehiclePositionDto]),
+    )
+)]
+async fn latest_vehicle_positions_handler(
+    Query(query): Query<LatestVehiclePositionsQuery>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let positions = list_latest_vehicle_positions(db_pool, &tenant, query.vendor_id).await?;
+
+    Ok(Json(positions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/vehicles/{id}/positions",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vehicle id")),
+    request_body = VehiclePositionDto,
+    responses(
+        (status = 202, body = RecordVehiclePositionsResponse),
+        (status = 404, description = "Unknown vehicle"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn record_vehicle_position_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Json(req): Json<VehiclePositionDto>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    record_positions(db_pool, tenant, &config, id, vec![req]).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/vehicles/{id}/positions/batch",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vehicle id")),
+    request_body = RecordVehiclePositionsRequest,
+    responses(
+        (status = 202, body = RecordVehiclePositionsResponse),
+        (status = 404, description = "Unknown vehicle"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn record_vehicle_positions_batch_handler(
+    Path(id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Json(req): Json<RecordVehiclePositionsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    record_positions(db_pool, tenant, &config, id, req.positions).await
+}
+
+async fn record_positions(
+    db_pool: PgPool,
+    tenant: TenantId,
+    config: &Config,
+    vehicle_id: i32,
+    positions: Vec<VehiclePositionDto>,
+) -> Result<Response, AppError> {
+    let retention_days = config.vehicle_positions.retention_days;
+    let now = Utc::now();
+    let oldest = now - Duration::days(i64::from(retention_days));
+    let newest = now + Duration::minutes(MAX_CLOCK_SKEW_MINUTES);
+
+    // Anything outside this window has no partition to land in.
+    if positions
+        .iter()
+        .any(|p| p.recorded_at < oldest || p.recorded_at > newest)
+    {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "recordedAt must be within the last {} days and not in the future.",
+                retention_days
+            ),
+        )
+            .into_response());
+    }
+
+    let vehicle_repo = VehicleRepository::new(db_pool.clone(), tenant.clone());
+
+    if vehicle_repo.by_id(vehicle_id).await?.is_none() {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let positions: Vec<VehiclePosition> = positions
+        .into_iter()
+        .map(|p| VehiclePosition {
+            recorded_at: p.recorded_at,
+            latitude: p.latitude,
+            longitude: p.longitude,
+            speed: p.speed,
+            heading: p.heading,
+        })
+        .collect();
+
+    let repo = VehiclePositionRepository::new(db_pool, tenant);
+
+    let stored = repo.create_many(vehicle_id, &positions).await?;
+
+    let response = RecordVehiclePositionsResponse {
+        received: positions.len(),
+        stored,
+    };
+
+    Ok((StatusCode::ACCEPTED, Json(response)).into_response())
+}
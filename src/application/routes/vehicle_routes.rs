@@ -0,0 +1,94 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::Json;
+use axum::{extract::State, response::IntoResponse, routing::post, Router};
+use chrono::Utc;
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::repositories::vehicle_route_repository::{
+    Repository, VehicleRouteRepository,
+};
+use crate::models::settlement_dto::{CompleteVehicleRouteRequest, VehicleRouteDto};
+
+#[derive(OpenApi)]
+#[openapi(paths(complete_vehicle_route_handler,))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/vehicles/:id/routes/:route_id/complete",
+        post(complete_vehicle_route_handler),
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/vehicles/{id}/routes/{route_id}/complete",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vehicle id"), ("route_id" = i32, Path, description = "Route id")),
+    request_body = CompleteVehicleRouteRequest,
+    responses(
+        (status = 200, body = VehicleRouteDto),
+        (status = 404, description = "The vehicle is not assigned this route"),
+        (status = 409, description = "The route has already been completed"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn complete_vehicle_route_handler(
+    Path((vehicle_id, route_id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CompleteVehicleRouteRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let completed_at = req.completed_at.unwrap_or_else(Utc::now);
+
+    if completed_at > Utc::now() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "A route cannot be completed in the future.",
+        )
+            .into_response());
+    }
+
+    let repo = VehicleRouteRepository::new(db_pool, tenant);
+
+    let Some(mut vehicle_route) = repo.by_id(vehicle_id, route_id).await? else {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    };
+
+    if !vehicle_route.can_complete() {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This route has already been completed.",
+        )
+            .into_response());
+    }
+
+    vehicle_route.complete(completed_at, req.load);
+
+    if !repo.complete(&vehicle_route).await? {
+        return Ok((
+            StatusCode::CONFLICT,
+            "This route has already been completed.",
+        )
+            .into_response());
+    }
+
+    let dto = VehicleRouteDto {
+        vehicle_id,
+        route_id,
+        load: vehicle_route.load,
+        completed_at: vehicle_route.completed_at,
+    };
+
+    Ok(Json(dto).into_response())
+}
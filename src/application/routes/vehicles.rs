@@ -0,0 +1,201 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::Json;
+use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
+use http::StatusCode;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{
+    app_state::AppState,
+    export::{list_response, FormatQuery, ListFormat},
+    http_utils::AppError,
+};
+use crate::domain::aggregates::vehicle::Vehicle;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::vehicle_queries::{
+    get_vehicle_by_id, stream_vehicles_by_vendor,
+};
+use crate::infrastructure::queries::vendor_queries::get_vendor_by_id;
+use crate::infrastructure::repositories::vehicle_repository::{Repository, VehicleRepository};
+use crate::models::vehicle_dto::{CreateVehicleRequest, VehicleDto};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    vehicles_list_handler,
+    create_vehicle_handler,
+    vehicle_handler,
+    update_vehicle_handler,
+))]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/vendors/:id/vehicles",
+            get(vehicles_list_handler).post(create_vehicle_handler),
+        )
+        .route(
+            "/vendors/:id/vehicles/:vehicle_id",
+            get(vehicle_handler).put(update_vehicle_handler),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}/vehicles",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vendor id"), FormatQuery),
+    responses(
+        (status = 200, description = "The vendor's fleet, as JSON, CSV or XLSX", content(
+            (Vec<VehicleDto> = "application/json"),
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        )),
+        (status = 400, description = "Unknown format"),
+        (status = 404, description = "Unknown vendor"),
+    )
+)]
+async fn vehicles_list_handler(
+    Path(vendor_id): Path<i32>,
+    format: ListFormat,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let vehicles = stream_vehicles_by_vendor(db_pool, tenant, vendor_id);
+    let file_stem = format!("vendor-{}-vehicles", vendor_id);
+
+    Ok(list_response(format, &file_stem, vehicles).await?)
+}
+
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}/vehicles/{vehicle_id}",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vendor id"), ("vehicle_id" = i32, Path, description = "Vehicle id")),
+    responses(
+        (status = 200, body = VehicleDto),
+        (status = 404, description = "Unknown vehicle"),
+    )
+)]
+async fn vehicle_handler(
+    Path((vendor_id, id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+) -> Result<impl IntoResponse, AppError> {
+    let vehicle = get_vehicle_by_id(db_pool, &tenant, vendor_id, id).await?;
+
+    match vehicle {
+        Some(v) => Ok((StatusCode::OK, Json(v)).into_response()),
+        None => Ok((StatusCode::NOT_FOUND).into_response()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/vendors/{id}/vehicles/{vehicle_id}",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vendor id"), ("vehicle_id" = i32, Path, description = "Vehicle id")),
+    request_body = CreateVehicleRequest,
+    responses(
+        (status = 200, body = VehicleDto),
+        (status = 404, description = "Unknown vehicle"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn update_vehicle_handler(
+    Path((vendor_id, id)): Path<(i32, i32)>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateVehicleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let repo = VehicleRepository::new(db_pool, tenant);
+
+    let mut vehicle = match repo.by_id(id).await? {
+        Some(v) if v.vendor_id == vendor_id => v,
+        _ => return Ok((StatusCode::NOT_FOUND).into_response()),
+    };
+
+    vehicle.update(&req.vehicle_type, req.capacity, req.availability_status);
+
+    repo.update(&vehicle).await?;
+
+    let dto = VehicleDto {
+        id: vehicle.id(),
+        vendor_id,
+        vehicle_type: req.vehicle_type,
+        capacity: req.capacity,
+        availability_status: req.availability_status,
+    };
+
+    Ok(Json(dto).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/vendors/{id}/vehicles",
+    tag = "vehicles",
+    params(("id" = i32, Path, description = "Vendor id")),
+    request_body = CreateVehicleRequest,
+    responses(
+        (status = 201, body = VehicleDto, headers(("Location" = String))),
+        (status = 404, description = "Unknown vendor"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
+async fn create_vehicle_handler(
+    Path(vendor_id): Path<i32>,
+    tenant: TenantId,
+    State(db_pool): State<PgPool>,
+    Json(req): Json<CreateVehicleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    if get_vendor_by_id(db_pool.clone(), &tenant, vendor_id)
+        .await?
+        .is_none()
+    {
+        return Ok((StatusCode::NOT_FOUND).into_response());
+    }
+
+    let repo = VehicleRepository::new(db_pool, tenant);
+
+    let vehicle_domain = Vehicle::new(
+        0,
+        vendor_id,
+        &req.vehicle_type,
+        req.capacity,
+        req.availability_status,
+    );
+
+    let id = repo.create(&vehicle_domain).await?;
+
+    let dto = VehicleDto {
+        id,
+        vendor_id,
+        vehicle_type: req.vehicle_type,
+        capacity: req.capacity,
+        availability_status: req.availability_status,
+    };
+
+    let location_header = [(
+        LOCATION,
+        format!("/v1/api/vendors/{}/vehicles/{}", vendor_id, id),
+    )];
+
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
+}
@@ -1,16 +1,37 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use axum::extract::Path;
 use axum::Json;
 use axum::{extract::State, http::header::LOCATION, response::IntoResponse, routing::get, Router};
-use http::{HeaderName, StatusCode};
+use http::StatusCode;
 use sqlx::PgPool;
-
-use crate::application::utils::{app_state::AppState, http_utils::AppError};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::application::utils::{
+    app_state::AppState,
+    export::{list_response, FormatQuery, ListFormat},
+    geocoding::locate_vendor,
+    http_utils::AppError,
+};
 use crate::domain::aggregates::vendor::Vendor;
-use crate::infrastructure::queries::vendor_queries::{get_vendor_by_id, list_vendors};
+use crate::domain::services::geocoder::Geocoder;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::vendor_queries::{get_vendor_by_id, stream_vendors};
 use crate::infrastructure::repositories::vendor_repository::{Repository, VendorRepository};
+use crate::models::address_dto::AddressDto;
 use crate::models::vendor_dto::{CreateVendorRequest, VendorDto};
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    vendors_list_handler,
+    create_vendor_handler,
+    vendor_handler,
+    update_vendor_handler,
+))]
+pub struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route(
@@ -23,19 +44,44 @@ pub fn router() -> Router<AppState> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/vendors",
+    tag = "vendors",
+    params(FormatQuery),
+    responses(
+        (status = 200, description = "Vendors by name, as JSON, CSV or XLSX", content(
+            (Vec<VendorDto> = "application/json"),
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        )),
+        (status = 400, description = "Unknown format"),
+    )
+)]
 async fn vendors_list_handler(
+    format: ListFormat,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
 ) -> Result<impl IntoResponse, AppError> {
-    let vendors = list_vendors(db_pool).await?;
-
-    Ok(Json(vendors))
+    Ok(list_response(format, "vendors", stream_vendors(db_pool, tenant)).await?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/vendors/{id}",
+    tag = "vendors",
+    params(("id" = i32, Path, description = "Vendor id")),
+    responses(
+        (status = 200, body = VendorDto),
+        (status = 404, description = "Unknown vendor"),
+    )
+)]
 async fn vendor_handler(
     Path(id): Path<i32>,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
 ) -> Result<impl IntoResponse, AppError> {
-    let vendor = get_vendor_by_id(db_pool, id).await?;
+    let vendor = get_vendor_by_id(db_pool, &tenant, id).await?;
 
     match vendor {
         Some(c) => Ok((StatusCode::OK, Json(c)).into_response()),
@@ -43,12 +89,32 @@ async fn vendor_handler(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/vendors/{id}",
+    tag = "vendors",
+    params(("id" = i32, Path, description = "Vendor id")),
+    request_body = CreateVendorRequest,
+    responses(
+        (status = 200, body = VendorDto),
+        (status = 404, description = "Unknown vendor"),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
 async fn update_vendor_handler(
     Path(id): Path<i32>,
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
+    State(geocoder): State<Arc<dyn Geocoder>>,
     Json(req): Json<CreateVendorRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let repo = VendorRepository::new(db_pool);
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let address = req.address.to_domain()?;
+
+    let repo = VendorRepository::new(db_pool.clone(), tenant.clone());
 
     let mut vendor = repo
         .by_id(id)
@@ -59,55 +125,74 @@ async fn update_vendor_handler(
     vendor.update(
         &req.name,
         &req.email,
-        &req.address,
+        address,
         req.contact_number.as_deref(),
     );
 
     repo.update(&vendor).await?;
 
+    if vendor.address.coordinates.is_none() {
+        locate_vendor(geocoder, db_pool, tenant, id);
+    }
+
     let dto = VendorDto {
         id: vendor.id(),
         name: req.name,
-        address: req.address,
+        address: AddressDto::from(&vendor.address),
         contact_number: req.contact_number,
         email: req.email,
     };
 
-    Ok(Json(dto))
+    Ok(Json(dto).into_response())
 }
 
+#[utoipa::path(
+    post,
+    path = "/vendors",
+    tag = "vendors",
+    request_body = CreateVendorRequest,
+    responses(
+        (status = 201, body = VendorDto, headers(("Location" = String))),
+        (status = 422, description = "Validation errors by field", body = Object),
+    )
+)]
 async fn create_vendor_handler(
+    tenant: TenantId,
     State(db_pool): State<PgPool>,
+    State(geocoder): State<Arc<dyn Geocoder>>,
     Json(req): Json<CreateVendorRequest>,
-) -> Result<
-    (
-        StatusCode,
-        [(HeaderName, std::string::String); 1],
-        axum::Json<VendorDto>,
-    ),
-    AppError,
-> {
-    let repo = VendorRepository::new(db_pool);
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response());
+    }
+
+    let address = req.address.to_domain()?;
+
+    let repo = VendorRepository::new(db_pool.clone(), tenant.clone());
 
     let vendor_domain = Vendor::new(
         0,
         &req.name,
         &req.email,
-        &req.address,
+        address,
         req.contact_number.as_deref(),
     );
 
     let id = repo.create(&vendor_domain).await?;
 
+    if vendor_domain.address.coordinates.is_none() {
+        locate_vendor(geocoder, db_pool, tenant, id);
+    }
+
     let dto = VendorDto {
         id,
         name: req.name,
-        address: req.address,
+        address: AddressDto::from(&vendor_domain.address),
         contact_number: req.contact_number,
         email: req.email,
     };
 
     let location_header = [(LOCATION, format!("/v1/api/vendors/{}", id))];
 
-    Ok((StatusCode::CREATED, location_header, Json(dto)))
+    Ok((StatusCode::CREATED, location_header, Json(dto)).into_response())
 }
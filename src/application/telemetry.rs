@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    response::Response,
+};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tower_http::request_id::RequestId;
+use tracing::{field, Span};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use super::config::{LogFormat, TelemetryConfig};
+
+const SERVICE_NAME: &str = "tsm";
+
+/// Flushes the spans not yet exported when shut down.
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+/// Logs to stdout in the configured format, at the levels in `RUST_LOG` or `info`, and exports
+/// spans to the OTLP collector when one is configured.
+pub fn init(config: &TelemetryConfig) -> Result<Telemetry> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let logs = match config.log_format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .boxed(),
+    };
+
+    let tracer_provider = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let exporter = SpanExporter::builder()
+                .with_http()
+                .with_endpoint(format!(
+                    "{}/v1/traces",
+                    endpoint.as_str().trim_end_matches('/')
+                ))
+                .build()
+                .context("failed to create the OTLP exporter")?;
+
+            Some(
+                SdkTracerProvider::builder()
+                    .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+                    .with_batch_exporter(exporter)
+                    .build(),
+            )
+        }
+        None => None,
+    };
+
+    let spans = tracer_provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME)));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(logs)
+        .with(spans)
+        .try_init()
+        .context("failed to install the tracing subscriber")?;
+
+    Ok(Telemetry { tracer_provider })
+}
+
+impl Telemetry {
+    pub fn shutdown(self) {
+        if let Some(provider) = self.tracer_provider {
+            if let Err(e) = provider.shutdown_with_timeout(Duration::from_secs(5)) {
+                eprintln!("failed to export the last spans: {}", e);
+            }
+        }
+    }
+}
+
+/// The span every request is handled in, so its log lines and those of its queries carry the
+/// request id.
+pub fn request_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(request.uri().path(), |path| path.as_str());
+
+    tracing::info_span!(
+        "request",
+        request_id,
+        method = %request.method(),
+        route,
+        status = field::Empty,
+    )
+}
+
+pub fn on_response(response: &Response, latency: Duration, span: &Span) {
+    span.record("status", response.status().as_u16());
+    tracing::info!(latency_ms = latency.as_millis() as u64, "answered");
+}
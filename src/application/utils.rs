@@ -1,2 +1,4 @@
 pub mod app_state;
+pub mod export;
+pub mod geocoding;
 pub mod http_utils;
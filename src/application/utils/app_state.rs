@@ -1,9 +1,25 @@
+use std::sync::Arc;
+
 use axum::extract::FromRef;
 use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+
+use crate::application::auth::SigningKeys;
+use crate::application::config::Config;
+use crate::application::metrics::Metrics;
+use crate::application::rate_limit::RateLimiter;
+use crate::domain::services::geocoder::Geocoder;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db_pool: PgPool,
+    pub geocoder: Arc<dyn Geocoder>,
+    pub config: Arc<Config>,
+    pub metrics: Arc<Metrics>,
+    pub signing_keys: Arc<SigningKeys>,
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Cancelled when the server starts shutting down.
+    pub shutdown: CancellationToken,
 }
 
 impl FromRef<AppState> for PgPool {
@@ -11,3 +27,39 @@ impl FromRef<AppState> for PgPool {
         state.db_pool.clone()
     }
 }
+
+impl FromRef<AppState> for Arc<dyn Geocoder> {
+    fn from_ref(state: &AppState) -> Self {
+        state.geocoder.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Config> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Metrics> {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SigningKeys> {
+    fn from_ref(state: &AppState) -> Self {
+        state.signing_keys.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<RateLimiter> {
+    fn from_ref(state: &AppState) -> Self {
+        state.rate_limiter.clone()
+    }
+}
+
+impl FromRef<AppState> for CancellationToken {
+    fn from_ref(state: &AppState) -> Self {
+        state.shutdown.clone()
+    }
+}
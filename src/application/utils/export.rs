@@ -0,0 +1,224 @@
+use std::io::{Seek, SeekFrom};
+
+use anyhow::{anyhow, Result};
+use axum::{
+    async_trait,
+    body::{Body, Bytes},
+    extract::{FromRequestParts, Query},
+    http::header::{ACCEPT, CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::{stream, stream::BoxStream, StreamExt, TryStreamExt};
+use http::{request::Parts, StatusCode};
+use rust_decimal::prelude::ToPrimitive;
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_util::io::ReaderStream;
+use utoipa::IntoParams;
+
+use crate::models::export_dto::{ExportRow, ExportValue};
+
+const CSV: &str = "text/csv";
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+/// Rows queued for the thread writing the workbook before the query is held back.
+const XLSX_ROW_BUFFER: usize = 256;
+
+/// How a list endpoint answers, from the `format` query parameter or else the `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Json,
+    Csv,
+    Xlsx,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FormatQuery {
+    /// `json`, `csv` or `xlsx`, takes precedence over the `Accept` header.
+    format: Option<String>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ListFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let format = Query::<FormatQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(q)| q.format);
+
+        if let Some(format) = format {
+            return match format.trim_start_matches('.') {
+                "json" => Ok(ListFormat::Json),
+                "csv" => Ok(ListFormat::Csv),
+                "xlsx" => Ok(ListFormat::Xlsx),
+                other => Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Unknown format '{}', use json, csv or xlsx.", other),
+                )
+                    .into_response()),
+            };
+        }
+
+        // The first media type we can produce wins, anything else falls back to JSON.
+        let accept = parts
+            .headers
+            .get(ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+
+        let format = accept
+            .split(',')
+            .map(|media_type| media_type.split(';').next().unwrap_or_default().trim())
+            .find_map(|media_type| match media_type {
+                "application/json" => Some(ListFormat::Json),
+                CSV => Some(ListFormat::Csv),
+                XLSX => Some(ListFormat::Xlsx),
+                _ => None,
+            })
+            .unwrap_or(ListFormat::Json);
+
+        Ok(format)
+    }
+}
+
+/// Answers a list endpoint with a JSON array, or a CSV or XLSX attachment named after `file_stem`.
+///
+/// CSV is streamed a row at a time as the query yields it. XLSX cannot be sent before the archive
+/// is complete, so the workbook is written to a temporary file, instead of in memory, and the file
+/// is streamed once saved.
+pub async fn list_response<T: ExportRow + Serialize>(
+    format: ListFormat,
+    file_stem: &str,
+    rows: BoxStream<'static, Result<T>>,
+) -> Result<Response> {
+    match format {
+        ListFormat::Json => {
+            let rows: Vec<T> = rows.try_collect().await?;
+            Ok(Json(rows).into_response())
+        }
+        ListFormat::Csv => export_csv(file_stem, rows).await,
+        ListFormat::Xlsx => export_xlsx(file_stem, rows).await,
+    }
+}
+
+async fn export_csv<T: ExportRow>(
+    file_stem: &str,
+    mut rows: BoxStream<'static, Result<T>>,
+) -> Result<Response> {
+    // Fails before the status is sent when the query cannot start.
+    let first = rows.try_next().await?;
+
+    let header = csv_line(T::COLUMNS.iter().map(|c| c.to_string()));
+    let lines = stream::iter(first.map(Ok))
+        .chain(rows)
+        .map(|row| row.and_then(|row| csv_line(row.values().iter().map(ExportValue::to_text))))
+        .inspect_err(|e| tracing::error!("CSV export failed midway: {:#}", e));
+
+    let body = Body::from_stream(stream::once(async { header }).chain(lines));
+
+    Ok((
+        StatusCode::OK,
+        attachment_headers(CSV_CONTENT_TYPE, file_stem, "csv"),
+        body,
+    )
+        .into_response())
+}
+
+fn csv_line(fields: impl Iterator<Item = String>) -> Result<Bytes> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+
+    Ok(Bytes::from(writer.into_inner()?))
+}
+
+async fn export_xlsx<T: ExportRow>(
+    file_stem: &str,
+    mut rows: BoxStream<'static, Result<T>>,
+) -> Result<Response> {
+    // Writing the workbook is blocking file IO, so it runs on its own thread fed by the query.
+    let (sender, receiver) = mpsc::channel(XLSX_ROW_BUFFER);
+    let writer = tokio::task::spawn_blocking(move || write_xlsx(T::COLUMNS, receiver));
+
+    while let Some(row) = rows.try_next().await? {
+        // The writer has failed, its error is returned below.
+        if sender.send(row.values()).await.is_err() {
+            break;
+        }
+    }
+    drop(sender);
+
+    let file = tokio::fs::File::from_std(writer.await??);
+
+    Ok((
+        StatusCode::OK,
+        attachment_headers(XLSX, file_stem, "xlsx"),
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+/// Writes the rows to a workbook saved in an unnamed temporary file, removed once it is closed,
+/// and rewinds the file for reading.
+fn write_xlsx(
+    columns: &[&str],
+    mut rows: mpsc::Receiver<Vec<ExportValue>>,
+) -> Result<std::fs::File> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+
+    let bold = Format::new().set_bold();
+    for (col, name) in columns.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *name, &bold)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+
+    let mut row_number: u32 = 1;
+    while let Some(values) = rows.blocking_recv() {
+        for (col, value) in values.into_iter().enumerate() {
+            let col = col as u16;
+            match value {
+                ExportValue::Empty => continue,
+                ExportValue::Text(value) => worksheet.write_string(row_number, col, value)?,
+                ExportValue::Integer(value) => {
+                    worksheet.write_number(row_number, col, value as f64)?
+                }
+                ExportValue::Float(value) => worksheet.write_number(row_number, col, value)?,
+                ExportValue::Decimal(value) => {
+                    let value = value
+                        .to_f64()
+                        .ok_or_else(|| anyhow!("{} does not fit a spreadsheet number.", value))?;
+                    worksheet.write_number(row_number, col, value)?
+                }
+                ExportValue::Bool(value) => worksheet.write_boolean(row_number, col, value)?,
+            };
+        }
+        row_number += 1;
+    }
+
+    let mut file = tempfile::tempfile()?;
+    workbook.save_to_writer(&mut file)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    Ok(file)
+}
+
+fn attachment_headers(
+    content_type: &str,
+    file_stem: &str,
+    extension: &str,
+) -> [(http::HeaderName, String); 2] {
+    [
+        (CONTENT_TYPE, content_type.to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.{}\"", file_stem, extension),
+        ),
+    ]
+}
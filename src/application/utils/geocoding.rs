@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+
+use crate::domain::{services::geocoder::Geocoder, value_objects::tenant_id::TenantId};
+use crate::infrastructure::repositories::{
+    customer_repository::{CustomerRepository, Repository as _},
+    vendor_repository::{Repository as _, VendorRepository},
+};
+use crate::infrastructure::tenancy;
+
+/// Fills in the coordinates of a customer saved without them, off the request path since
+/// providers are rate limited. A failure or a miss is left to the re-geocode job, and an address
+/// changed in the meantime is not overwritten.
+pub fn locate_customer(
+    geocoder: Arc<dyn Geocoder>,
+    db_pool: PgPool,
+    tenant: TenantId,
+    id: i32,
+) -> JoinHandle<()> {
+    tokio::spawn(tenancy::in_tenant(tenant.clone(), async move {
+        let result: Result<()> = async {
+            let repo = CustomerRepository::new(db_pool, tenant);
+            let mut customer = repo.by_id(id).await?;
+
+            if let Some(coordinates) = geocoder.geocode(&customer.address).await? {
+                customer.locate(coordinates);
+                repo.update_coordinates(&customer).await?;
+            }
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            tracing::warn!("Geocoding customer {} failed: {:#}", id, err);
+        }
+    }))
+}
+
+/// Fills in the coordinates of a vendor saved without them, see `locate_customer`.
+pub fn locate_vendor(
+    geocoder: Arc<dyn Geocoder>,
+    db_pool: PgPool,
+    tenant: TenantId,
+    id: i32,
+) -> JoinHandle<()> {
+    tokio::spawn(tenancy::in_tenant(tenant.clone(), async move {
+        let result: Result<()> = async {
+            let repo = VendorRepository::new(db_pool, tenant);
+            let mut vendor = repo.by_id(id).await?;
+
+            if let Some(coordinates) = geocoder.geocode(&vendor.address).await? {
+                vendor.locate(coordinates);
+                repo.update_coordinates(&vendor).await?;
+            }
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            tracing::warn!("Geocoding vendor {} failed: {:#}", id, err);
+        }
+    }))
+}
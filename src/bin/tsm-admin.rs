@@ -0,0 +1,301 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use dotenvy::dotenv;
+use sqlx::PgPool;
+
+use tsm::application::config::Config;
+use tsm::application::demo_data::{self, DemoDataOptions};
+use tsm::application::imports::{import_csv, ImportOutcome};
+use tsm::application::jobs::invoicing;
+use tsm::application::reference_data;
+use tsm::domain::aggregates::import::ImportEntity;
+use tsm::domain::aggregates::user::User;
+use tsm::domain::value_objects::tenant_id::TenantId;
+use tsm::infrastructure::queries::health_queries::unapplied_migrations;
+use tsm::infrastructure::repositories::user_repository::{Repository as _, UserRepository};
+use tsm::infrastructure::{migrate, reindex, tenancy};
+use tsm::models::import_dto::ImportMode;
+use tsm::models::item_dto::ReferenceData;
+
+/// Operations tasks for a tsm database, configured like the server.
+#[derive(Parser)]
+#[command(name = "tsm-admin", version)]
+struct Cli {
+    /// The tenant to work in, DEFAULT_TENANT_ID when absent.
+    #[arg(long, global = true)]
+    tenant: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Applies the pending migrations.
+    Migrate {
+        /// Only lists the pending migrations, failing when there are any.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Creates or updates the reference data of a JSON file, matching items by name.
+    Seed { file: PathBuf },
+    /// Imports customers or vendors from a CSV file, like `POST /customers/import`.
+    Import {
+        entity: Entity,
+        file: PathBuf,
+        /// Validates the file without saving anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Manages the users known to the tenant outside Entra ID.
+    #[command(subcommand)]
+    Users(UsersCommand),
+    /// Rebuilds the database indexes.
+    Reindex,
+    /// Drafts an invoice per customer for the delivered orders not invoiced yet.
+    Invoice {
+        /// Issues the drafts as well.
+        #[arg(long)]
+        issue: bool,
+    },
+    /// Generates fake customers, vendors with their fleets, items, orders and routes.
+    DemoData {
+        /// The same seed gives the same data.
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        #[arg(long, default_value_t = 20)]
+        customers: usize,
+        #[arg(long, default_value_t = 4)]
+        vendors: usize,
+        #[arg(long, default_value_t = 3)]
+        vehicles_per_vendor: usize,
+        #[arg(long, default_value_t = 60)]
+        orders: usize,
+        /// When today's routes leave the depot, 08:00 UTC today when absent.
+        #[arg(long)]
+        departure_at: Option<DateTime<Utc>>,
+    },
+}
+
+#[derive(Subcommand)]
+enum UsersCommand {
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        role: String,
+        #[arg(long)]
+        contact_number: Option<String>,
+    },
+    /// Gives an existing user another role.
+    Grant {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        role: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Entity {
+    Customers,
+    Vendors,
+}
+
+impl From<Entity> for ImportEntity {
+    fn from(entity: Entity) -> Self {
+        match entity {
+            Entity::Customers => ImportEntity::Customers,
+            Entity::Vendors => ImportEntity::Vendors,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    let config = Config::load()?;
+
+    let db = tenancy::pool_options()
+        .max_connections(2)
+        .connect(&config.database.url)
+        .await
+        .context("failed to connect to DATABASE_URL")?;
+
+    let default_tenant_id = config.tenancy.default_tenant_id.as_deref();
+
+    let tenant = cli
+        .tenant
+        .as_deref()
+        .or(default_tenant_id)
+        .map(TenantId::new);
+    let tenant = || {
+        tenant
+            .clone()
+            .context("--tenant is required without DEFAULT_TENANT_ID")
+    };
+
+    match cli.command {
+        Command::Migrate { check: true } => {
+            let pending = unapplied_migrations(db).await?;
+            if !pending.is_empty() {
+                bail!("Migrations are pending: {}", pending.join(", "));
+            }
+            println!("The database is up to date.");
+        }
+        Command::Migrate { check: false } => {
+            migrate(&db, default_tenant_id).await?;
+            println!("Migrations applied.");
+        }
+        Command::Reindex => {
+            let tables = reindex(&db).await?;
+            println!("Reindexed {} tables.", tables.len());
+        }
+        command => {
+            let tenant = tenant()?;
+            tenancy::in_tenant(tenant.clone(), run(command, db, tenant)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The commands working on the rows of one tenant.
+async fn run(command: Command, db: PgPool, tenant: TenantId) -> Result<()> {
+    match command {
+        Command::Seed { file } => {
+            let data: ReferenceData = serde_json::from_str(&read(&file)?)
+                .with_context(|| format!("{} is not valid reference data", file.display()))?;
+
+            let summary = reference_data::seed(db, tenant, &data).await?;
+            println!(
+                "Items: {} created, {} updated.",
+                summary.created, summary.updated
+            );
+        }
+        Command::Import {
+            entity,
+            file,
+            dry_run,
+        } => {
+            let mode = match dry_run {
+                true => ImportMode::DryRun,
+                false => ImportMode::Commit,
+            };
+            let csv = read(&file)?;
+
+            let report =
+                match import_csv(entity.into(), db, tenant, csv.as_bytes(), mode, "tsm-admin")
+                    .await?
+                {
+                    ImportOutcome::Rejected(reason) | ImportOutcome::Conflict(reason) => {
+                        bail!(reason)
+                    }
+                    ImportOutcome::Report(report) => report,
+                };
+
+            for row in report.rows.iter().filter(|r| !r.errors.is_empty()) {
+                eprintln!("line {}: {}", row.line, row.errors.join("; "));
+            }
+            println!(
+                "{} rows: {} created, {} updated, {} rejected.",
+                report.total_rows, report.created, report.updated, report.rejected
+            );
+        }
+        Command::Users(UsersCommand::Create {
+            name,
+            email,
+            role,
+            contact_number,
+        }) => {
+            let repository = UserRepository::new(db, tenant);
+            if repository.by_email(&email).await?.is_some() {
+                bail!("There is already a user with the email {}.", email);
+            }
+
+            let user = User::new(0, &name, &email, &role, contact_number.as_deref());
+            let id = repository.create(&user).await?;
+            println!("Created user {}.", id);
+        }
+        Command::Users(UsersCommand::Grant { email, role }) => {
+            let repository = UserRepository::new(db, tenant);
+            let Some(mut user) = repository.by_email(&email).await? else {
+                bail!("There is no user with the email {}.", email);
+            };
+
+            user.grant(&role);
+            repository.update(&user).await?;
+            println!("{} is now {}.", user.email, user.role);
+        }
+        Command::Invoice { issue } => {
+            let run = invoicing::run(db, tenant, issue).await?;
+
+            for invoice in &run.invoices {
+                println!(
+                    "Invoice {} for customer {}: {} {}",
+                    invoice.invoice_number.as_deref().unwrap_or("(draft)"),
+                    invoice.customer_id,
+                    invoice.total(),
+                    invoice.invoice_status.as_str()
+                );
+            }
+            for customer_id in &run.skipped {
+                eprintln!(
+                    "Customer {} was skipped, their orders were just invoiced.",
+                    customer_id
+                );
+            }
+            println!("{} invoices.", run.invoices.len());
+        }
+        Command::DemoData {
+            seed,
+            customers,
+            vendors,
+            vehicles_per_vendor,
+            orders,
+            departure_at,
+        } => {
+            let departure_at = departure_at.unwrap_or_else(|| {
+                Utc::now()
+                    .date_naive()
+                    .and_time(NaiveTime::from_hms_opt(8, 0, 0).unwrap())
+                    .and_utc()
+            });
+            let options = DemoDataOptions {
+                customers,
+                vendors,
+                vehicles_per_vendor,
+                orders,
+                ..DemoDataOptions::new(seed, departure_at)
+            };
+
+            let summary = demo_data::generate(db, tenant, &options).await?;
+            println!(
+                "Generated {} customers, {} vendors with {} vehicles, {} items, {} orders and {} routes.",
+                summary.customers,
+                summary.vendors,
+                summary.vehicles,
+                summary.items,
+                summary.orders,
+                summary.routes
+            );
+        }
+        Command::Migrate { .. } | Command::Reindex => unreachable!("runs across tenants"),
+    }
+
+    Ok(())
+}
+
+fn read(file: &PathBuf) -> Result<String> {
+    std::fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))
+}
@@ -1 +1,3 @@
 pub mod aggregates;
+pub mod services;
+pub mod value_objects;
@@ -1,2 +1,14 @@
+pub mod contact;
 pub mod customer;
+pub mod import;
+pub mod invoice;
+pub mod item;
+pub mod order;
+pub mod proof_of_delivery;
+pub mod rate_card;
+pub mod route_plan;
+pub mod settlement;
+pub mod user;
+pub mod vehicle;
+pub mod vehicle_route;
 pub mod vendor;
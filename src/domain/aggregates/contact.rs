@@ -0,0 +1,163 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ContactRole {
+    General,
+    Billing,
+    Operations,
+    AfterHours,
+}
+
+impl ContactRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactRole::General => "general",
+            ContactRole::Billing => "billing",
+            ContactRole::Operations => "operations",
+            ContactRole::AfterHours => "after_hours",
+        }
+    }
+}
+
+impl fmt::Display for ContactRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContactRole {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "general" => Ok(ContactRole::General),
+            "billing" => Ok(ContactRole::Billing),
+            "operations" => Ok(ContactRole::Operations),
+            "after_hours" => Ok(ContactRole::AfterHours),
+            other => Err(anyhow!("Unknown contact role '{}'.", other)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContactChannel {
+    Email,
+    Phone,
+    Sms,
+}
+
+impl ContactChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactChannel::Email => "email",
+            ContactChannel::Phone => "phone",
+            ContactChannel::Sms => "sms",
+        }
+    }
+}
+
+impl fmt::Display for ContactChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContactChannel {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "email" => Ok(ContactChannel::Email),
+            "phone" => Ok(ContactChannel::Phone),
+            "sms" => Ok(ContactChannel::Sms),
+            other => Err(anyhow!("Unknown contact channel '{}'.", other)),
+        }
+    }
+}
+
+/// The customer or vendor a contact belongs to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContactOwner {
+    Customer(i32),
+    Vendor(i32),
+}
+
+impl ContactOwner {
+    pub fn customer_id(&self) -> Option<i32> {
+        match self {
+            ContactOwner::Customer(id) => Some(*id),
+            ContactOwner::Vendor(_) => None,
+        }
+    }
+
+    pub fn vendor_id(&self) -> Option<i32> {
+        match self {
+            ContactOwner::Customer(_) => None,
+            ContactOwner::Vendor(id) => Some(*id),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Contact {
+    pub id: i32,
+    pub owner: ContactOwner,
+    pub name: String,
+    pub role: ContactRole,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub preferred_channel: ContactChannel,
+    pub is_primary: bool,
+}
+
+impl Contact {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// New contacts are not primary until `set_primary` says so.
+    pub fn new(
+        id: i32,
+        owner: ContactOwner,
+        name: &str,
+        role: ContactRole,
+        email: Option<&str>,
+        phone: Option<&str>,
+        preferred_channel: ContactChannel,
+    ) -> Self {
+        Self {
+            id,
+            owner,
+            name: name.to_string(),
+            role,
+            email: email.map(str::to_string),
+            phone: phone.map(str::to_string),
+            preferred_channel,
+            is_primary: false,
+        }
+    }
+
+    pub fn set_primary(&mut self, is_primary: bool) {
+        self.is_primary = is_primary;
+    }
+
+    pub fn update(
+        &mut self,
+        name: &str,
+        role: ContactRole,
+        email: Option<&str>,
+        phone: Option<&str>,
+        preferred_channel: ContactChannel,
+        is_primary: bool,
+    ) {
+        self.name = name.to_string();
+        self.role = role;
+        self.email = email.map(str::to_string);
+        self.phone = phone.map(str::to_string);
+        self.preferred_channel = preferred_channel;
+        self.is_primary = is_primary;
+    }
+}
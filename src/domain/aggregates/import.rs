@@ -0,0 +1,81 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImportEntity {
+    Customers,
+    Vendors,
+}
+
+impl ImportEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportEntity::Customers => "customers",
+            ImportEntity::Vendors => "vendors",
+        }
+    }
+}
+
+impl fmt::Display for ImportEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImportEntity {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "customers" => Ok(ImportEntity::Customers),
+            "vendors" => Ok(ImportEntity::Vendors),
+            other => Err(anyhow!("Unknown import entity '{}'.", other)),
+        }
+    }
+}
+
+/// The record of a committed CSV import.
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Import {
+    pub id: i32,
+    pub entity: ImportEntity,
+    pub imported_by: String,
+    pub total_rows: i32,
+    pub created: i32,
+    pub updated: i32,
+    pub rejected: i32,
+    pub error_file: Option<String>,
+}
+
+impl Import {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        entity: ImportEntity,
+        imported_by: &str,
+        created: i32,
+        updated: i32,
+        rejected: i32,
+        error_file: Option<String>,
+    ) -> Self {
+        if rejected > 0 && error_file.is_none() {
+            panic!("An import with rejected rows must keep its error file.");
+        }
+
+        Self {
+            id,
+            entity,
+            imported_by: imported_by.to_string(),
+            total_rows: created + updated + rejected,
+            created,
+            updated,
+            rejected,
+            error_file,
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvoiceType {
+    Invoice,
+    CreditNote,
+}
+
+impl InvoiceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvoiceType::Invoice => "invoice",
+            InvoiceType::CreditNote => "credit_note",
+        }
+    }
+}
+
+impl fmt::Display for InvoiceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for InvoiceType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "invoice" => Ok(InvoiceType::Invoice),
+            "credit_note" => Ok(InvoiceType::CreditNote),
+            other => Err(anyhow!("Unknown invoice type '{}'.", other)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvoiceStatus {
+    Draft,
+    Issued,
+    Void,
+}
+
+impl InvoiceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvoiceStatus::Draft => "draft",
+            InvoiceStatus::Issued => "issued",
+            InvoiceStatus::Void => "void",
+        }
+    }
+}
+
+impl fmt::Display for InvoiceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for InvoiceStatus {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "draft" => Ok(InvoiceStatus::Draft),
+            "issued" => Ok(InvoiceStatus::Issued),
+            "void" => Ok(InvoiceStatus::Void),
+            other => Err(anyhow!("Unknown invoice status '{}'.", other)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InvoiceLine {
+    pub id: i32,
+    // Set on lines billed from an order, empty on credit note lines.
+    pub order_id: Option<i32>,
+    pub item_id: Option<i32>,
+    // The invoice line a credit note line gives back.
+    pub credited_line_id: Option<i32>,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+impl InvoiceLine {
+    pub fn total(&self) -> Decimal {
+        Decimal::from(self.quantity) * self.unit_price
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Invoice {
+    pub id: i32,
+    pub customer_id: i32,
+    pub invoice_type: InvoiceType,
+    pub invoice_status: InvoiceStatus,
+    pub invoice_number: Option<String>,
+    pub credited_invoice_id: Option<i32>,
+    pub reason: Option<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+    pub lines: Vec<InvoiceLine>,
+}
+
+impl Invoice {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i32,
+        customer_id: i32,
+        invoice_type: InvoiceType,
+        invoice_status: InvoiceStatus,
+        invoice_number: Option<&str>,
+        credited_invoice_id: Option<i32>,
+        reason: Option<&str>,
+        issued_at: Option<DateTime<Utc>>,
+        voided_at: Option<DateTime<Utc>>,
+        void_reason: Option<&str>,
+        lines: Vec<InvoiceLine>,
+    ) -> Self {
+        Self {
+            id,
+            customer_id,
+            invoice_type,
+            invoice_status,
+            invoice_number: invoice_number.map(str::to_string),
+            credited_invoice_id,
+            reason: reason.map(str::to_string),
+            issued_at,
+            voided_at,
+            void_reason: void_reason.map(str::to_string),
+            lines,
+        }
+    }
+
+    /// A new unnumbered invoice, billing the given order lines.
+    pub fn draft(customer_id: i32, lines: Vec<InvoiceLine>) -> Self {
+        Self::new(
+            0,
+            customer_id,
+            InvoiceType::Invoice,
+            InvoiceStatus::Draft,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            lines,
+        )
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.lines.iter().map(InvoiceLine::total).sum()
+    }
+
+    pub fn order_ids(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.lines.iter().filter_map(|l| l.order_id).collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// Issuing hands out the next number of the gapless sequence, so it happens in the repository.
+    pub fn can_issue(&self) -> bool {
+        self.invoice_status == InvoiceStatus::Draft && !self.lines.is_empty()
+    }
+
+    /// Drafts and issued invoices can be voided, issued ones keep their number. An invoice that
+    /// has been credited must be settled through credit notes instead.
+    pub fn can_void(&self, has_credit_notes: bool) -> bool {
+        self.invoice_status != InvoiceStatus::Void && !has_credit_notes
+    }
+
+    pub fn void(&mut self, reason: &str, voided_at: DateTime<Utc>, has_credit_notes: bool) {
+        if !self.can_void(has_credit_notes) {
+            panic!("This invoice cannot be voided.");
+        }
+
+        self.invoice_status = InvoiceStatus::Void;
+        self.voided_at = Some(voided_at);
+        self.void_reason = Some(reason.to_string());
+    }
+
+    pub fn can_credit(&self) -> bool {
+        self.invoice_type == InvoiceType::Invoice && self.invoice_status == InvoiceStatus::Issued
+    }
+
+    /// A draft credit note giving back `quantity` of each listed line, at negative prices.
+    pub fn credit_note(&self, reason: &str, quantities: &[(i32, i32)]) -> Invoice {
+        if !self.can_credit() {
+            panic!("Only issued invoices can be credited.");
+        }
+
+        let lines = quantities
+            .iter()
+            .filter_map(|(line_id, quantity)| {
+                self.lines
+                    .iter()
+                    .find(|l| l.id == *line_id)
+                    .map(|l| InvoiceLine {
+                        id: 0,
+                        order_id: None,
+                        item_id: None,
+                        credited_line_id: Some(l.id),
+                        description: format!("Credit: {}", l.description),
+                        quantity: *quantity,
+                        unit_price: -l.unit_price,
+                    })
+            })
+            .collect();
+
+        Self::new(
+            0,
+            self.customer_id,
+            InvoiceType::CreditNote,
+            InvoiceStatus::Draft,
+            None,
+            Some(self.id),
+            Some(reason),
+            None,
+            None,
+            None,
+            lines,
+        )
+    }
+}
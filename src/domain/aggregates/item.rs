@@ -0,0 +1,35 @@
+use rust_decimal::Decimal;
+
+/// A product of the catalogue that orders are made of.
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Item {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub quantity_available: i32,
+    /// The list price, order lines keep the price the item was ordered at.
+    pub unit_price: Decimal,
+}
+
+impl Item {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        name: &str,
+        description: Option<&str>,
+        quantity_available: i32,
+        unit_price: Decimal,
+    ) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            description: description.map(str::to_string),
+            quantity_available,
+            unit_price,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+
+use crate::domain::value_objects::time_window::{TimeWindow, TimeWindowError};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrderStatus {
+    Pending,
+    Confirmed,
+    /// On an accepted route plan, waiting to leave.
+    Assigned,
+    InTransit,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    pub const ALL: [OrderStatus; 6] = [
+        OrderStatus::Pending,
+        OrderStatus::Confirmed,
+        OrderStatus::Assigned,
+        OrderStatus::InTransit,
+        OrderStatus::Delivered,
+        OrderStatus::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Confirmed => "confirmed",
+            OrderStatus::Assigned => "assigned",
+            OrderStatus::InTransit => "in_transit",
+            OrderStatus::Delivered => "delivered",
+            OrderStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(OrderStatus::Pending),
+            "confirmed" => Ok(OrderStatus::Confirmed),
+            "assigned" => Ok(OrderStatus::Assigned),
+            "in_transit" => Ok(OrderStatus::InTransit),
+            "delivered" => Ok(OrderStatus::Delivered),
+            "cancelled" => Ok(OrderStatus::Cancelled),
+            other => Err(anyhow!("Unknown order status '{}'.", other)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[readonly::make]
+pub struct Order {
+    pub id: i32,
+    pub customer_id: i32,
+    pub order_status: OrderStatus,
+    pub windows: Option<OrderWindows>,
+}
+
+impl Order {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        customer_id: i32,
+        order_status: OrderStatus,
+        windows: Option<OrderWindows>,
+    ) -> Self {
+        Self {
+            id,
+            customer_id,
+            order_status,
+            windows,
+        }
+    }
+
+    /// A new order, pending until it is confirmed.
+    pub fn place(customer_id: i32) -> Self {
+        Self::new(0, customer_id, OrderStatus::Pending, None)
+    }
+
+    /// The windows only matter until the goods are on the road.
+    pub fn can_schedule(&self) -> bool {
+        matches!(
+            self.order_status,
+            OrderStatus::Pending | OrderStatus::Confirmed
+        )
+    }
+
+    /// Replaces the pickup and delivery windows. They must not have opened yet at `now`, and the
+    /// pickup window can neither open nor close after the delivery window.
+    pub fn schedule(
+        &mut self,
+        windows: OrderWindows,
+        now: DateTime<Utc>,
+    ) -> Result<(), TimeWindowError> {
+        if !self.can_schedule() {
+            panic!("Only pending or confirmed orders can be scheduled.");
+        }
+
+        let opened = |w: &Option<TimeWindow>| w.is_some_and(|w| w.earliest < now);
+        if opened(&windows.pickup) || opened(&windows.delivery) {
+            return Err(TimeWindowError::InThePast);
+        }
+
+        if let (Some(pickup), Some(delivery)) = (windows.pickup, windows.delivery) {
+            if pickup.earliest > delivery.earliest || pickup.latest > delivery.latest {
+                return Err(TimeWindowError::PickupAfterDelivery);
+            }
+        }
+
+        self.windows = Some(windows);
+
+        Ok(())
+    }
+
+    pub fn can_confirm(&self) -> bool {
+        self.order_status == OrderStatus::Pending
+    }
+
+    pub fn confirm(&mut self) {
+        if !self.can_confirm() {
+            panic!("Only pending orders can be confirmed.");
+        }
+
+        self.order_status = OrderStatus::Confirmed;
+    }
+
+    /// Only confirmed orders are planned, and each onto one accepted plan.
+    pub fn can_assign(&self) -> bool {
+        self.order_status == OrderStatus::Confirmed
+    }
+
+    pub fn assign(&mut self) {
+        if !self.can_assign() {
+            panic!("Only confirmed orders can be assigned to a route.");
+        }
+
+        self.order_status = OrderStatus::Assigned;
+    }
+
+    /// Only orders on an accepted plan can leave on a route.
+    pub fn can_dispatch(&self) -> bool {
+        self.order_status == OrderStatus::Assigned
+    }
+
+    pub fn dispatch(&mut self) {
+        if !self.can_dispatch() {
+            panic!("Only assigned orders can be dispatched.");
+        }
+
+        self.order_status = OrderStatus::InTransit;
+    }
+
+    /// Orders on the road can no longer be cancelled.
+    pub fn can_cancel(&self) -> bool {
+        matches!(
+            self.order_status,
+            OrderStatus::Pending | OrderStatus::Confirmed
+        )
+    }
+
+    pub fn cancel(&mut self) {
+        if !self.can_cancel() {
+            panic!("Only pending or confirmed orders can be cancelled.");
+        }
+
+        self.order_status = OrderStatus::Cancelled;
+    }
+
+    /// A proof of delivery can only be recorded while the goods are on the road.
+    pub fn can_record_delivery(&self) -> bool {
+        self.order_status == OrderStatus::InTransit
+    }
+
+    pub fn mark_delivered(&mut self) {
+        if !self.can_record_delivery() {
+            panic!("Only in-transit orders can be delivered.");
+        }
+
+        self.order_status = OrderStatus::Delivered;
+    }
+}
+
+/// A quantity of an item on an order, at the price it was ordered for.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OrderLine {
+    pub item_id: i32,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+/// When an order's goods may be collected and delivered, in the time zone of its sites.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OrderWindows {
+    pub time_zone: Tz,
+    pub pickup: Option<TimeWindow>,
+    pub delivery: Option<TimeWindow>,
+}
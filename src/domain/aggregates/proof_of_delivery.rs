@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeliveryFileKind {
+    Signature,
+    Photo,
+}
+
+impl DeliveryFileKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryFileKind::Signature => "signature",
+            DeliveryFileKind::Photo => "photo",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeliveryFile {
+    pub kind: DeliveryFileKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct ProofOfDelivery {
+    pub id: i32,
+    pub order_id: i32,
+    pub recipient_name: String,
+    pub delivered_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub notes: Option<String>,
+    pub files: Vec<DeliveryFile>,
+}
+
+impl ProofOfDelivery {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        order_id: i32,
+        recipient_name: &str,
+        delivered_at: DateTime<Utc>,
+        latitude: f64,
+        longitude: f64,
+        notes: Option<&str>,
+    ) -> Self {
+        Self {
+            id,
+            order_id,
+            recipient_name: recipient_name.to_string(),
+            delivered_at,
+            latitude,
+            longitude,
+            notes: notes.map(str::to_string),
+            files: Vec::new(),
+        }
+    }
+
+    /// Attaches the signature or photo, replacing any previous file of the same kind.
+    pub fn attach(&mut self, file: DeliveryFile) {
+        self.files.retain(|f| f.kind != file.kind);
+        self.files.push(file);
+    }
+
+    pub fn file(&self, kind: DeliveryFileKind) -> Option<&DeliveryFile> {
+        self.files.iter().find(|f| f.kind == kind)
+    }
+}
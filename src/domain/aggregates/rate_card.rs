@@ -0,0 +1,129 @@
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// One cell of the rate grid, applying from `min_distance` km and `min_weight` kg upwards until
+/// the next band or break.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rate {
+    pub min_distance: Decimal,
+    pub min_weight: Decimal,
+    pub base_charge: Decimal,
+    pub rate_per_km: Decimal,
+    pub rate_per_kg: Decimal,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FreightCharge {
+    pub rate: Rate,
+    pub amount: Decimal,
+    pub minimum_charge_applied: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct RateCard {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub name: String,
+    pub vehicle_type: String,
+    pub minimum_charge: Decimal,
+    pub effective_from: NaiveDate,
+    pub effective_to: Option<NaiveDate>,
+    pub rates: Vec<Rate>,
+}
+
+impl RateCard {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        vendor_id: i32,
+        name: &str,
+        vehicle_type: &str,
+        minimum_charge: Decimal,
+        effective_from: NaiveDate,
+        effective_to: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            id,
+            vendor_id,
+            name: name.to_string(),
+            vehicle_type: vehicle_type.to_string(),
+            minimum_charge,
+            effective_from,
+            effective_to,
+            rates: Vec::new(),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        name: &str,
+        vehicle_type: &str,
+        minimum_charge: Decimal,
+        effective_from: NaiveDate,
+        effective_to: Option<NaiveDate>,
+    ) {
+        self.name = name.to_string();
+        self.vehicle_type = vehicle_type.to_string();
+        self.minimum_charge = minimum_charge;
+        self.effective_from = effective_from;
+        self.effective_to = effective_to;
+    }
+
+    /// Replaces the grid, keeping it ordered by distance band then weight break.
+    pub fn set_rates(&mut self, mut rates: Vec<Rate>) {
+        rates.sort_by_key(|r| (r.min_distance, r.min_weight));
+        self.rates = rates;
+    }
+
+    pub fn is_effective_on(&self, date: NaiveDate) -> bool {
+        self.effective_from <= date && self.effective_to.is_none_or(|to| date <= to)
+    }
+
+    pub fn overlaps(&self, other: &RateCard) -> bool {
+        self.effective_from <= other.effective_to.unwrap_or(NaiveDate::MAX)
+            && other.effective_from <= self.effective_to.unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Prices a shipment from the highest distance band and then the highest weight break it
+    /// reaches, `None` when the grid does not start low enough to cover it or the amount is out of
+    /// range.
+    pub fn price(&self, distance: Decimal, weight: Decimal) -> Option<FreightCharge> {
+        let band = self
+            .rates
+            .iter()
+            .filter(|r| r.min_distance <= distance)
+            .map(|r| r.min_distance)
+            .max()?;
+
+        let rate = self
+            .rates
+            .iter()
+            .filter(|r| r.min_distance == band && r.min_weight <= weight)
+            .max_by_key(|r| r.min_weight)?;
+
+        let amount = rate
+            .rate_per_km
+            .checked_mul(distance)?
+            .checked_add(rate.rate_per_kg.checked_mul(weight)?)?
+            .checked_add(rate.base_charge)?
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+
+        Some(if amount < self.minimum_charge {
+            FreightCharge {
+                rate: *rate,
+                amount: self.minimum_charge,
+                minimum_charge_applied: true,
+            }
+        } else {
+            FreightCharge {
+                rate: *rate,
+                amount,
+                minimum_charge_applied: false,
+            }
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, Utc};
+
+use crate::domain::{services::route_planner::Plan, value_objects::coordinates::Coordinates};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoutePlanStatus {
+    Draft,
+    Accepted,
+}
+
+impl RoutePlanStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoutePlanStatus::Draft => "draft",
+            RoutePlanStatus::Accepted => "accepted",
+        }
+    }
+}
+
+impl fmt::Display for RoutePlanStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoutePlanStatus {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "draft" => Ok(RoutePlanStatus::Draft),
+            "accepted" => Ok(RoutePlanStatus::Accepted),
+            other => Err(anyhow!("Unknown route plan status '{}'.", other)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct RoutePlan {
+    pub id: i32,
+    pub plan_status: RoutePlanStatus,
+    pub depot: Coordinates,
+    pub departure_at: DateTime<Utc>,
+    pub plan: Plan,
+    // (vehicle id, main route id) pairs written when the plan was accepted.
+    pub accepted_routes: Vec<(i32, i32)>,
+}
+
+impl RoutePlan {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        plan_status: RoutePlanStatus,
+        depot: Coordinates,
+        departure_at: DateTime<Utc>,
+        plan: Plan,
+        accepted_routes: Vec<(i32, i32)>,
+    ) -> Self {
+        Self {
+            id,
+            plan_status,
+            depot,
+            departure_at,
+            plan,
+            accepted_routes,
+        }
+    }
+
+    pub fn draft(depot: Coordinates, departure_at: DateTime<Utc>, plan: Plan) -> Self {
+        Self::new(
+            0,
+            RoutePlanStatus::Draft,
+            depot,
+            departure_at,
+            plan,
+            Vec::new(),
+        )
+    }
+
+    pub fn order_ids(&self) -> Vec<i32> {
+        self.plan
+            .routes
+            .iter()
+            .flat_map(|r| r.stops.iter().map(|s| s.order_id))
+            .collect()
+    }
+
+    pub fn vehicle_ids(&self) -> Vec<i32> {
+        self.plan.routes.iter().map(|r| r.vehicle_id).collect()
+    }
+
+    pub fn can_accept(&self) -> bool {
+        self.plan_status == RoutePlanStatus::Draft && !self.plan.routes.is_empty()
+    }
+
+    pub fn accept(&mut self) {
+        if !self.can_accept() {
+            panic!("Only draft route plans with routes can be accepted.");
+        }
+
+        self.plan_status = RoutePlanStatus::Accepted;
+    }
+
+    pub fn route_id_for(&self, vehicle_id: i32) -> Option<i32> {
+        self.accepted_routes
+            .iter()
+            .find(|(v, _)| *v == vehicle_id)
+            .map(|(_, r)| *r)
+    }
+}
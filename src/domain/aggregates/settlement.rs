@@ -0,0 +1,206 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use crate::domain::aggregates::rate_card::RateCard;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettlementStatus {
+    Draft,
+    Approved,
+    Rejected,
+}
+
+impl SettlementStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettlementStatus::Draft => "draft",
+            SettlementStatus::Approved => "approved",
+            SettlementStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl fmt::Display for SettlementStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SettlementStatus {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "draft" => Ok(SettlementStatus::Draft),
+            "approved" => Ok(SettlementStatus::Approved),
+            "rejected" => Ok(SettlementStatus::Rejected),
+            other => Err(anyhow!("Unknown settlement status '{}'.", other)),
+        }
+    }
+}
+
+/// A completed vehicle route waiting to be settled.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompletedRoute {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    pub vehicle_type: String,
+    pub completed_at: DateTime<Utc>,
+    pub distance: Decimal,
+    pub load: Option<Decimal>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SettlementLine {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    pub rate_card_id: i32,
+    pub completed_at: DateTime<Utc>,
+    pub distance: Decimal,
+    pub load: Decimal,
+    pub amount: Decimal,
+    pub minimum_charge_applied: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Settlement {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub settlement_status: SettlementStatus,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub approved_by: Option<String>,
+    pub rejected_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub lines: Vec<SettlementLine>,
+}
+
+impl Settlement {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        vendor_id: i32,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        settlement_status: SettlementStatus,
+        lines: Vec<SettlementLine>,
+    ) -> Self {
+        Self {
+            id,
+            vendor_id,
+            period_start,
+            period_end,
+            settlement_status,
+            approved_at: None,
+            approved_by: None,
+            rejected_at: None,
+            rejection_reason: None,
+            lines,
+        }
+    }
+
+    /// Rebuilds the approval or rejection read back from storage.
+    pub fn restore_review(
+        &mut self,
+        approved_at: Option<DateTime<Utc>>,
+        approved_by: Option<&str>,
+        rejected_at: Option<DateTime<Utc>>,
+        rejection_reason: Option<&str>,
+    ) {
+        self.approved_at = approved_at;
+        self.approved_by = approved_by.map(str::to_string);
+        self.rejected_at = rejected_at;
+        self.rejection_reason = rejection_reason.map(str::to_string);
+    }
+
+    /// Prices each route from the vendor's rate card for its vehicle type in effect on the day it
+    /// completed (UTC). Routes without a matching card or rate are returned apart and stay
+    /// unsettled until one is set up.
+    pub fn draft(
+        vendor_id: i32,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        routes: &[CompletedRoute],
+        rate_cards: &[RateCard],
+    ) -> (Self, Vec<CompletedRoute>) {
+        let mut lines = Vec::new();
+        let mut unpriced = Vec::new();
+
+        for route in routes {
+            // Routes built outside the planner have no load, they are priced on distance alone.
+            let load = route.load.unwrap_or_default();
+
+            let priced = rate_cards
+                .iter()
+                .filter(|c| {
+                    c.vendor_id == vendor_id
+                        && c.vehicle_type.eq_ignore_ascii_case(&route.vehicle_type)
+                        && c.is_effective_on(route.completed_at.date_naive())
+                })
+                .find_map(|c| c.price(route.distance, load).map(|charge| (c, charge)));
+
+            match priced {
+                Some((rate_card, charge)) => lines.push(SettlementLine {
+                    vehicle_id: route.vehicle_id,
+                    route_id: route.route_id,
+                    rate_card_id: rate_card.id(),
+                    completed_at: route.completed_at,
+                    distance: route.distance,
+                    load,
+                    amount: charge.amount,
+                    minimum_charge_applied: charge.minimum_charge_applied,
+                }),
+                None => unpriced.push(route.clone()),
+            }
+        }
+
+        (
+            Self::new(
+                0,
+                vendor_id,
+                period_start,
+                period_end,
+                SettlementStatus::Draft,
+                lines,
+            ),
+            unpriced,
+        )
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.lines.iter().map(|l| l.amount).sum()
+    }
+
+    pub fn can_review(&self) -> bool {
+        self.settlement_status == SettlementStatus::Draft
+    }
+
+    pub fn approve(&mut self, approved_by: &str, approved_at: DateTime<Utc>) {
+        if !self.can_review() {
+            panic!("Only draft settlements can be approved.");
+        }
+
+        self.settlement_status = SettlementStatus::Approved;
+        self.approved_by = Some(approved_by.to_string());
+        self.approved_at = Some(approved_at);
+    }
+
+    /// Rejected settlements give their routes back, to be settled again once corrected.
+    pub fn reject(&mut self, reason: &str, rejected_at: DateTime<Utc>) {
+        if !self.can_review() {
+            panic!("Only draft settlements can be rejected.");
+        }
+
+        self.settlement_status = SettlementStatus::Rejected;
+        self.rejection_reason = Some(reason.to_string());
+        self.rejected_at = Some(rejected_at);
+    }
+}
@@ -0,0 +1,30 @@
+/// A person known to the tenant outside Entra ID, e.g. a driver orders are handed to.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[readonly::make]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub role: String,
+    pub contact_number: Option<String>,
+}
+
+impl User {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(id: i32, name: &str, email: &str, role: &str, contact_number: Option<&str>) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            email: email.to_string(),
+            role: role.to_string(),
+            contact_number: contact_number.map(str::to_string),
+        }
+    }
+
+    pub fn grant(&mut self, role: &str) {
+        self.role = role.to_string();
+    }
+}
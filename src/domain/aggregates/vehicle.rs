@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[readonly::make]
+pub struct Vehicle {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub vehicle_type: String,
+    pub capacity: Decimal,
+    pub availability_status: bool,
+}
+
+impl Vehicle {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn new(
+        id: i32,
+        vendor_id: i32,
+        vehicle_type: &str,
+        capacity: Decimal,
+        availability_status: bool,
+    ) -> Self {
+        Self {
+            id,
+            vendor_id,
+            vehicle_type: vehicle_type.to_string(),
+            capacity,
+            availability_status,
+        }
+    }
+
+    pub fn update(&mut self, vehicle_type: &str, capacity: Decimal, availability_status: bool) {
+        self.vehicle_type = vehicle_type.to_string();
+        self.capacity = capacity;
+        self.availability_status = availability_status;
+    }
+}
+
+// A single GPS fix reported by a vehicle.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct VehiclePosition {
+    pub recorded_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed: Option<f32>,
+    pub heading: Option<f32>,
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+/// A route assigned to a vehicle, which the vendor gets paid for once it is completed.
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct VehicleRoute {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    // Same unit as the vehicle capacity, set from the route plan or when completing.
+    pub load: Option<Decimal>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl VehicleRoute {
+    pub fn new(
+        vehicle_id: i32,
+        route_id: i32,
+        load: Option<Decimal>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            vehicle_id,
+            route_id,
+            load,
+            completed_at,
+        }
+    }
+
+    pub fn can_complete(&self) -> bool {
+        self.completed_at.is_none()
+    }
+
+    /// `load` replaces the planned load when the actual one is known.
+    pub fn complete(&mut self, completed_at: DateTime<Utc>, load: Option<Decimal>) {
+        if !self.can_complete() {
+            panic!("Vehicle route has already been completed.");
+        }
+
+        self.completed_at = Some(completed_at);
+        self.load = load.or(self.load);
+    }
+}
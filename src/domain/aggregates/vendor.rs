@@ -1,10 +1,12 @@
-#[derive(Clone, PartialEq, Eq, Debug)]
+use crate::domain::value_objects::{address::Address, coordinates::Coordinates};
+
+#[derive(Clone, PartialEq, Debug)]
 #[readonly::make]
 pub struct Vendor {
     pub id: i32,
     pub name: String,
     pub email: String,
-    pub address: String,
+    pub address: Address,
     pub contact_number: Option<String>,
 }
 
@@ -17,28 +19,32 @@ impl Vendor {
         id: i32,
         name: &str,
         email: &str,
-        address: &str,
+        address: Address,
         contact_number: Option<&str>,
     ) -> Self {
         Self {
             id,
             name: name.to_string(),
             email: email.to_string(),
-            address: address.to_string(),
-            contact_number: match contact_number {
-                None => None,
-                Some(str) => Some(str.to_string()),
-            },
+            address,
+            contact_number: contact_number.map(str::to_string),
         }
     }
 
-    pub fn update(&mut self, name: &str, email: &str, address: &str, contact_number: Option<&str>) {
+    pub fn update(
+        &mut self,
+        name: &str,
+        email: &str,
+        address: Address,
+        contact_number: Option<&str>,
+    ) {
         self.name = name.to_string();
         self.email = email.to_string();
-        self.address = address.to_string();
-        self.contact_number = match contact_number {
-            None => None,
-            Some(str) => Some(str.to_string()),
-        }
+        self.address = address;
+        self.contact_number = contact_number.map(str::to_string);
+    }
+
+    pub fn locate(&mut self, coordinates: Coordinates) {
+        self.address = self.address.with_coordinates(Some(coordinates));
     }
 }
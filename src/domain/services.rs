@@ -0,0 +1,2 @@
+pub mod geocoder;
+pub mod route_planner;
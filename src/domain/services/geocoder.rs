@@ -0,0 +1,12 @@
+use anyhow::Result;
+use axum::async_trait;
+
+use crate::domain::value_objects::{address::Address, coordinates::Coordinates};
+
+/// Resolves an address to a point, `None` when the provider does not know the address.
+#[async_trait]
+pub trait Geocoder: Send + Sync {
+    /// Short provider name, recorded next to cached results.
+    fn name(&self) -> &'static str;
+    async fn geocode(&self, address: &Address) -> Result<Option<Coordinates>>;
+}
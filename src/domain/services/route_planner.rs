@@ -0,0 +1,664 @@
+//! Heuristic vehicle routing: Clarke-Wright savings to build the initial routes, then 2-opt and
+//! relocate moves until no move shortens the plan. Distances are haversine, so it runs offline.
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::{coordinates::Coordinates, time_window::TimeWindow};
+
+const DEPOT_LABEL: &str = "Depot";
+const MAX_LOCAL_SEARCH_ROUNDS: usize = 50;
+// Ignore improvements smaller than a metre so floating point noise cannot loop forever.
+const MIN_IMPROVEMENT_KM: f64 = 0.001;
+
+#[derive(Clone, Debug)]
+pub struct Stop {
+    pub order_id: i32,
+    pub location: Coordinates,
+    pub load: Decimal,
+    /// When the goods can be loaded at the depot, a route leaves once all of its can.
+    pub pickup_window: Option<TimeWindow>,
+    /// When the goods can be delivered.
+    pub window: Option<TimeWindow>,
+    pub service_time: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlanningVehicle {
+    pub vehicle_id: i32,
+    pub capacity: Decimal,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlanningProblem {
+    pub depot: Coordinates,
+    /// The earliest the vehicles can leave, pickup windows may hold them back.
+    pub departure_at: DateTime<Utc>,
+    pub average_speed_kmh: f64,
+    pub stops: Vec<Stop>,
+    pub vehicles: Vec<PlanningVehicle>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PlannedStop {
+    pub order_id: i32,
+    pub distance_from_previous_km: f64,
+    pub travel_seconds_from_previous: i64,
+    pub arrival_at: DateTime<Utc>,
+    pub service_start_at: DateTime<Utc>,
+    pub departure_at: DateTime<Utc>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PlannedRoute {
+    pub vehicle_id: i32,
+    pub load: Decimal,
+    pub departure_at: DateTime<Utc>,
+    pub stops: Vec<PlannedStop>,
+    pub return_distance_km: f64,
+    pub return_travel_seconds: i64,
+    pub distance_km: f64,
+    pub finish_at: DateTime<Utc>,
+}
+
+impl PlannedRoute {
+    /// The depot-to-depot legs of the route, labelled the way they are stored in `routes`.
+    pub fn legs(&self) -> Vec<RouteLeg> {
+        let mut legs = Vec::with_capacity(self.stops.len() + 1);
+        let mut origin = DEPOT_LABEL.to_string();
+
+        for stop in &self.stops {
+            let destination = order_label(stop.order_id);
+
+            legs.push(RouteLeg {
+                order_id: Some(stop.order_id),
+                origin,
+                destination: destination.clone(),
+                distance_km: stop.distance_from_previous_km,
+                travel_seconds: stop.travel_seconds_from_previous,
+            });
+
+            origin = destination;
+        }
+
+        legs.push(RouteLeg {
+            order_id: None,
+            origin,
+            destination: DEPOT_LABEL.to_string(),
+            distance_km: self.return_distance_km,
+            travel_seconds: self.return_travel_seconds,
+        });
+
+        legs
+    }
+
+    pub fn duration_seconds(&self) -> i64 {
+        (self.finish_at - self.departure_at).num_seconds()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RouteLeg {
+    pub order_id: Option<i32>,
+    pub origin: String,
+    pub destination: String,
+    pub distance_km: f64,
+    pub travel_seconds: i64,
+}
+
+impl RouteLeg {
+    /// Distance rounded to the two decimals of `routes.distance`.
+    pub fn distance(&self) -> Decimal {
+        to_distance(self.distance_km)
+    }
+
+    pub fn estimated_travel_time(&self) -> NaiveTime {
+        to_travel_time(self.travel_seconds)
+    }
+}
+
+pub fn to_distance(distance_km: f64) -> Decimal {
+    Decimal::from_f64(distance_km)
+        .unwrap_or_default()
+        .round_dp(2)
+}
+
+/// `routes.estimated_travel_time` is a TIME column, so durations are capped just under a day.
+pub fn to_travel_time(seconds: i64) -> NaiveTime {
+    let seconds = seconds.clamp(0, 24 * 60 * 60 - 1) as u32;
+
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap_or_default()
+}
+
+fn order_label(order_id: i32) -> String {
+    format!("Order {}", order_id)
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Plan {
+    pub routes: Vec<PlannedRoute>,
+    pub unassigned_order_ids: Vec<i32>,
+}
+
+/// Proposes one route per used vehicle. Stops that cannot be served within capacity and their
+/// delivery windows are reported as unassigned instead of failing the whole plan.
+pub fn plan_routes(problem: &PlanningProblem) -> Plan {
+    let solver = Solver::new(problem);
+
+    let (mut routes, mut unassigned) = solver.savings();
+    solver.local_search(&mut routes);
+
+    routes.retain(|r| !r.stops.is_empty());
+    routes.sort_by_key(|r| problem.vehicles[r.vehicle].vehicle_id);
+
+    unassigned.sort_unstable();
+
+    Plan {
+        routes: routes.iter().map(|r| solver.describe(r)).collect(),
+        unassigned_order_ids: unassigned
+            .into_iter()
+            .map(|s| problem.stops[s - 1].order_id)
+            .collect(),
+    }
+}
+
+// Node 0 is the depot, node i is `problem.stops[i - 1]`.
+struct Solver<'a> {
+    problem: &'a PlanningProblem,
+    distances: Vec<Vec<f64>>,
+}
+
+#[derive(Clone, Debug)]
+struct Route {
+    vehicle: usize,
+    stops: Vec<usize>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(problem: &'a PlanningProblem) -> Self {
+        let locations: Vec<Coordinates> = std::iter::once(problem.depot)
+            .chain(problem.stops.iter().map(|s| s.location))
+            .collect();
+
+        let distances = locations
+            .iter()
+            .map(|from| locations.iter().map(|to| from.distance_km(to)).collect())
+            .collect();
+
+        Self { problem, distances }
+    }
+
+    fn stop(&self, node: usize) -> &Stop {
+        &self.problem.stops[node - 1]
+    }
+
+    fn travel_time(&self, distance_km: f64) -> Duration {
+        Duration::seconds((distance_km / self.problem.average_speed_kmh * 3600.0).round() as i64)
+    }
+
+    fn load(&self, stops: &[usize]) -> Decimal {
+        stops.iter().map(|&s| self.stop(s).load).sum()
+    }
+
+    fn distance(&self, stops: &[usize]) -> f64 {
+        let mut previous = 0;
+        let mut total = 0.0;
+
+        for &s in stops {
+            total += self.distances[previous][s];
+            previous = s;
+        }
+
+        total + self.distances[previous][0]
+    }
+
+    // The route waits at the depot for the last pickup window to open, `None` when another one has
+    // closed by then.
+    fn departure(&self, stops: &[usize]) -> Option<DateTime<Utc>> {
+        let mut pickups = stops.iter().filter_map(|&s| self.stop(s).pickup_window);
+
+        let departure = pickups
+            .clone()
+            .map(|w| w.earliest)
+            .fold(self.problem.departure_at, DateTime::max);
+
+        pickups.all(|w| departure <= w.latest).then_some(departure)
+    }
+
+    // Walks the route from the depot and checks that every pickup and delivery window is met.
+    fn is_on_time(&self, stops: &[usize]) -> bool {
+        let Some(mut now) = self.departure(stops) else {
+            return false;
+        };
+        let mut previous = 0;
+
+        for &s in stops {
+            let stop = self.stop(s);
+            let arrival = now + self.travel_time(self.distances[previous][s]);
+
+            let service_start = match stop.window {
+                Some(window) if arrival > window.latest => return false,
+                Some(window) => arrival.max(window.earliest),
+                None => arrival,
+            };
+
+            now = service_start + stop.service_time;
+            previous = s;
+        }
+
+        true
+    }
+
+    fn is_feasible(&self, stops: &[usize], capacity: Decimal) -> bool {
+        self.load(stops) <= capacity && self.is_on_time(stops)
+    }
+
+    fn savings(&self) -> (Vec<Route>, Vec<usize>) {
+        let stop_count = self.problem.stops.len();
+        let max_capacity = self
+            .problem
+            .vehicles
+            .iter()
+            .map(|v| v.capacity)
+            .max()
+            .unwrap_or_default();
+
+        let mut unassigned = Vec::new();
+        let mut routes: Vec<Vec<usize>> = Vec::new();
+        let mut route_of = vec![None; stop_count + 1];
+
+        for (node, route) in route_of.iter_mut().enumerate().skip(1) {
+            if self.is_feasible(&[node], max_capacity) {
+                *route = Some(routes.len());
+                routes.push(vec![node]);
+            } else {
+                unassigned.push(node);
+            }
+        }
+
+        let mut savings = Vec::new();
+
+        for i in 1..=stop_count {
+            for j in (i + 1)..=stop_count {
+                if route_of[i].is_some() && route_of[j].is_some() {
+                    let saving = self.distances[0][i] + self.distances[0][j] - self.distances[i][j];
+                    savings.push((saving, i, j));
+                }
+            }
+        }
+
+        savings.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+        for (saving, i, j) in savings {
+            if saving <= 0.0 {
+                break;
+            }
+
+            let (Some(ri), Some(rj)) = (route_of[i], route_of[j]) else {
+                continue;
+            };
+
+            if ri == rj {
+                continue;
+            }
+
+            // i and j must both sit next to the depot to be joined, try every orientation.
+            let (a, b) = (&routes[ri], &routes[rj]);
+            let mut candidates = Vec::new();
+
+            if a.last() == Some(&i) && b.first() == Some(&j) {
+                candidates.push(a.iter().chain(b.iter()).copied().collect::<Vec<_>>());
+            }
+            if b.last() == Some(&j) && a.first() == Some(&i) {
+                candidates.push(b.iter().chain(a.iter()).copied().collect());
+            }
+            if a.first() == Some(&i) && b.first() == Some(&j) {
+                candidates.push(a.iter().rev().chain(b.iter()).copied().collect());
+            }
+            if a.last() == Some(&i) && b.last() == Some(&j) {
+                candidates.push(a.iter().chain(b.iter().rev()).copied().collect());
+            }
+
+            let merged = candidates
+                .into_iter()
+                .filter(|c| self.is_feasible(c, max_capacity))
+                .min_by(|x, y| self.distance(x).total_cmp(&self.distance(y)));
+
+            if let Some(merged) = merged {
+                for &s in &merged {
+                    route_of[s] = Some(ri);
+                }
+
+                routes[ri] = merged;
+                routes[rj] = Vec::new();
+            }
+        }
+
+        let (routes, leftover) = self.assign_vehicles(routes);
+
+        let mut routes = routes;
+        for node in leftover {
+            if !self.insert(&mut routes, node) {
+                unassigned.push(node);
+            }
+        }
+
+        (routes, unassigned)
+    }
+
+    // Heaviest routes get the smallest vehicle that can carry them.
+    fn assign_vehicles(&self, mut stops: Vec<Vec<usize>>) -> (Vec<Route>, Vec<usize>) {
+        stops.retain(|s| !s.is_empty());
+        stops.sort_by(|a, b| self.load(b).cmp(&self.load(a)).then(a.cmp(b)));
+
+        let mut vehicles: Vec<usize> = (0..self.problem.vehicles.len()).collect();
+        vehicles.sort_by_key(|&v| {
+            let vehicle = &self.problem.vehicles[v];
+            (vehicle.capacity, vehicle.vehicle_id)
+        });
+
+        let mut used = vec![false; vehicles.len()];
+        let mut routes = Vec::new();
+        let mut leftover = Vec::new();
+
+        for route in stops {
+            let load = self.load(&route);
+            let vehicle = vehicles
+                .iter()
+                .copied()
+                .find(|&v| !used[v] && self.problem.vehicles[v].capacity >= load);
+
+            match vehicle {
+                Some(v) => {
+                    used[v] = true;
+                    routes.push(Route {
+                        vehicle: v,
+                        stops: route,
+                    });
+                }
+                None => leftover.extend(route),
+            }
+        }
+
+        for (v, in_use) in used.iter().enumerate() {
+            if !in_use {
+                routes.push(Route {
+                    vehicle: v,
+                    stops: Vec::new(),
+                });
+            }
+        }
+
+        (routes, leftover)
+    }
+
+    // Cheapest feasible insertion of a single stop into any route, idle vehicles included.
+    fn insert(&self, routes: &mut [Route], node: usize) -> bool {
+        let mut best: Option<(f64, usize, usize)> = None;
+
+        for (r, route) in routes.iter().enumerate() {
+            let capacity = self.problem.vehicles[route.vehicle].capacity;
+            let current = self.distance(&route.stops);
+
+            for position in 0..=route.stops.len() {
+                let mut candidate = route.stops.clone();
+                candidate.insert(position, node);
+
+                if !self.is_feasible(&candidate, capacity) {
+                    continue;
+                }
+
+                let delta = self.distance(&candidate) - current;
+                if best.is_none_or(|(d, _, _)| delta < d) {
+                    best = Some((delta, r, position));
+                }
+            }
+        }
+
+        match best {
+            Some((_, r, position)) => {
+                routes[r].stops.insert(position, node);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn local_search(&self, routes: &mut [Route]) {
+        for _ in 0..MAX_LOCAL_SEARCH_ROUNDS {
+            let mut improved = false;
+
+            for route in routes.iter_mut() {
+                improved |= self.two_opt(route);
+            }
+
+            improved |= self.relocate(routes);
+
+            if !improved {
+                break;
+            }
+        }
+    }
+
+    fn two_opt(&self, route: &mut Route) -> bool {
+        let capacity = self.problem.vehicles[route.vehicle].capacity;
+        let mut improved = false;
+        let mut current = self.distance(&route.stops);
+
+        for i in 0..route.stops.len() {
+            for j in (i + 1)..route.stops.len() {
+                let mut candidate = route.stops.clone();
+                candidate[i..=j].reverse();
+
+                let distance = self.distance(&candidate);
+                if distance < current - MIN_IMPROVEMENT_KM && self.is_feasible(&candidate, capacity)
+                {
+                    route.stops = candidate;
+                    current = distance;
+                    improved = true;
+                }
+            }
+        }
+
+        improved
+    }
+
+    // Moves one stop to its best position in any route when that shortens the plan.
+    fn relocate(&self, routes: &mut [Route]) -> bool {
+        let mut improved = false;
+
+        for from in 0..routes.len() {
+            let mut position = 0;
+
+            while position < routes[from].stops.len() {
+                let node = routes[from].stops[position];
+
+                let mut without = routes[from].stops.clone();
+                without.remove(position);
+
+                if !self.is_on_time(&without) {
+                    position += 1;
+                    continue;
+                }
+
+                let removal_gain = self.distance(&routes[from].stops) - self.distance(&without);
+                let mut best: Option<(f64, usize, usize)> = None;
+
+                for (to, route) in routes.iter().enumerate() {
+                    let capacity = self.problem.vehicles[route.vehicle].capacity;
+                    let base = if to == from { &without } else { &route.stops };
+                    let current = self.distance(base);
+
+                    for target in 0..=base.len() {
+                        if to == from && target == position {
+                            continue;
+                        }
+
+                        let mut candidate = base.clone();
+                        candidate.insert(target, node);
+
+                        let delta = self.distance(&candidate) - current - removal_gain;
+                        if delta < -MIN_IMPROVEMENT_KM
+                            && best.is_none_or(|(d, _, _)| delta < d)
+                            && self.is_feasible(&candidate, capacity)
+                        {
+                            best = Some((delta, to, target));
+                        }
+                    }
+                }
+
+                match best {
+                    Some((_, to, target)) => {
+                        routes[from].stops = without;
+                        routes[to].stops.insert(target, node);
+                        improved = true;
+                    }
+                    None => position += 1,
+                }
+            }
+        }
+
+        improved
+    }
+
+    fn describe(&self, route: &Route) -> PlannedRoute {
+        let departure_at = self
+            .departure(&route.stops)
+            .unwrap_or(self.problem.departure_at);
+        let mut now = departure_at;
+        let mut previous = 0;
+        let mut stops = Vec::with_capacity(route.stops.len());
+
+        for &s in &route.stops {
+            let stop = self.stop(s);
+            let distance = self.distances[previous][s];
+            let travel = self.travel_time(distance);
+            let arrival = now + travel;
+            let service_start = match stop.window {
+                Some(window) => arrival.max(window.earliest),
+                None => arrival,
+            };
+
+            now = service_start + stop.service_time;
+            previous = s;
+
+            stops.push(PlannedStop {
+                order_id: stop.order_id,
+                distance_from_previous_km: distance,
+                travel_seconds_from_previous: travel.num_seconds(),
+                arrival_at: arrival,
+                service_start_at: service_start,
+                departure_at: now,
+            });
+        }
+
+        let return_distance = self.distances[previous][0];
+        let return_travel = self.travel_time(return_distance);
+
+        PlannedRoute {
+            vehicle_id: self.problem.vehicles[route.vehicle].vehicle_id,
+            load: self.load(&route.stops),
+            departure_at,
+            stops,
+            return_distance_km: return_distance,
+            return_travel_seconds: return_travel.num_seconds(),
+            distance_km: self.distance(&route.stops),
+            finish_at: now + return_travel,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(order_id: i32, latitude: f64, longitude: f64, load: i64) -> Stop {
+        Stop {
+            order_id,
+            location: Coordinates::new(latitude, longitude),
+            load: Decimal::from(load),
+            pickup_window: None,
+            window: None,
+            service_time: Duration::minutes(5),
+        }
+    }
+
+    fn departure_at() -> DateTime<Utc> {
+        "2024-07-01T08:00:00Z".parse().unwrap()
+    }
+
+    fn problem(stops: Vec<Stop>, capacities: &[i64]) -> PlanningProblem {
+        PlanningProblem {
+            depot: Coordinates::new(0.0, 0.0),
+            departure_at: departure_at(),
+            average_speed_kmh: 40.0,
+            stops,
+            vehicles: capacities
+                .iter()
+                .enumerate()
+                .map(|(i, &capacity)| PlanningVehicle {
+                    vehicle_id: i as i32 + 1,
+                    capacity: Decimal::from(capacity),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn splits_stops_that_overload_one_vehicle() {
+        let problem = problem(vec![stop(1, 0.0, 0.1, 6), stop(2, 0.0, 0.2, 6)], &[10, 10]);
+
+        let plan = plan_routes(&problem);
+
+        assert!(plan.unassigned_order_ids.is_empty());
+        assert_eq!(plan.routes.len(), 2);
+        for route in &plan.routes {
+            assert_eq!(route.stops.len(), 1);
+            assert_eq!(route.load, Decimal::from(6));
+        }
+    }
+
+    #[test]
+    fn leaves_out_a_stop_whose_window_cannot_be_met() {
+        let mut late = stop(2, 0.0, 0.2, 1);
+        late.window = Some(
+            TimeWindow::new(
+                departure_at() - Duration::hours(2),
+                departure_at() - Duration::hours(1),
+            )
+            .unwrap(),
+        );
+        let problem = problem(vec![stop(1, 0.0, 0.1, 1), late], &[10]);
+
+        let plan = plan_routes(&problem);
+
+        assert_eq!(plan.unassigned_order_ids, [2]);
+        assert_eq!(plan.routes.len(), 1);
+        assert_eq!(plan.routes[0].stops[0].order_id, 1);
+    }
+
+    #[test]
+    fn two_opt_uncrosses_a_route() {
+        // The corners of a square with the depot, visited so that two legs cross.
+        let problem = problem(
+            vec![
+                stop(1, 0.0, 0.1, 1),
+                stop(2, 0.1, 0.1, 1),
+                stop(3, 0.1, 0.0, 1),
+            ],
+            &[10],
+        );
+        let solver = Solver::new(&problem);
+        let mut route = Route {
+            vehicle: 0,
+            stops: vec![1, 3, 2],
+        };
+        let crossed = solver.distance(&route.stops);
+
+        assert!(solver.two_opt(&mut route));
+
+        assert_eq!(route.stops, [1, 2, 3]);
+        assert!(solver.distance(&route.stops) < crossed);
+    }
+}
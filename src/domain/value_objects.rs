@@ -0,0 +1,4 @@
+pub mod address;
+pub mod coordinates;
+pub mod tenant_id;
+pub mod time_window;
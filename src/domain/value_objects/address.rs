@@ -0,0 +1,302 @@
+use std::fmt;
+
+use crate::domain::value_objects::coordinates::Coordinates;
+
+pub const MAX_ADDRESS_LINES: usize = 2;
+
+// Character limits of the address columns.
+pub const MAX_LINE_LENGTH: usize = 255;
+pub const MAX_CITY_LENGTH: usize = 100;
+pub const MAX_REGION_LENGTH: usize = 100;
+pub const MAX_POSTCODE_LENGTH: usize = 20;
+
+// Country code stamped on addresses that could not be parsed from the legacy free text.
+pub const UNKNOWN_COUNTRY_CODE: &str = "ZZ";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddressError {
+    MissingLine,
+    TooManyLines,
+    LineTooLong,
+    MissingCity,
+    CityTooLong,
+    InvalidCountryCode,
+    InvalidPostcode,
+    MissingRegion,
+    InvalidRegion,
+    RegionTooLong,
+    InvalidCoordinates,
+}
+
+impl AddressError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AddressError::MissingLine => "missing_line",
+            AddressError::TooManyLines => "too_many_lines",
+            AddressError::LineTooLong => "line_too_long",
+            AddressError::MissingCity => "missing_city",
+            AddressError::CityTooLong => "city_too_long",
+            AddressError::InvalidCountryCode => "invalid_country_code",
+            AddressError::InvalidPostcode => "invalid_postcode",
+            AddressError::MissingRegion => "missing_region",
+            AddressError::InvalidRegion => "invalid_region",
+            AddressError::RegionTooLong => "region_too_long",
+            AddressError::InvalidCoordinates => "invalid_coordinates",
+        }
+    }
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AddressError::MissingLine => "At least one address line is required.",
+            AddressError::TooManyLines => "An address has at most two lines.",
+            AddressError::LineTooLong => "Address lines are limited to 255 characters.",
+            AddressError::MissingCity => "City is required.",
+            AddressError::CityTooLong => "City is limited to 100 characters.",
+            AddressError::InvalidCountryCode => "Country code must be an ISO 3166 alpha-2 code.",
+            AddressError::InvalidPostcode => "Postcode is not valid for the country.",
+            AddressError::MissingRegion => "Region is required for the country.",
+            AddressError::InvalidRegion => "Region is not valid for the country.",
+            AddressError::RegionTooLong => "Region is limited to 100 characters.",
+            AddressError::InvalidCoordinates => "Latitude or longitude is out of range.",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+// Postcode shapes per country, `N` is a digit and `A` a letter, anything else must match as is.
+struct CountryRules {
+    country_code: &'static str,
+    postcode_formats: &'static [&'static str],
+    regions: &'static [&'static str],
+}
+
+const COUNTRY_RULES: &[CountryRules] = &[
+    CountryRules {
+        country_code: "AU",
+        postcode_formats: &["NNNN"],
+        regions: &["ACT", "NSW", "NT", "QLD", "SA", "TAS", "VIC", "WA"],
+    },
+    CountryRules {
+        country_code: "NZ",
+        postcode_formats: &["NNNN"],
+        regions: &[],
+    },
+    CountryRules {
+        country_code: "US",
+        postcode_formats: &["NNNNN", "NNNNN-NNNN"],
+        regions: &[
+            "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "DC", "FL", "GA", "HI", "ID", "IL",
+            "IN", "IA", "KS", "KY", "LA", "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE",
+            "NV", "NH", "NJ", "NM", "NY", "NC", "ND", "OH", "OK", "OR", "PA", "RI", "SC", "SD",
+            "TN", "TX", "UT", "VT", "VA", "WA", "WV", "WI", "WY", "PR",
+        ],
+    },
+    CountryRules {
+        country_code: "CA",
+        postcode_formats: &["ANA NAN"],
+        regions: &[
+            "AB", "BC", "MB", "NB", "NL", "NS", "NT", "NU", "ON", "PE", "QC", "SK", "YT",
+        ],
+    },
+    CountryRules {
+        country_code: "GB",
+        postcode_formats: &[
+            "AN NAA", "ANN NAA", "AAN NAA", "AANN NAA", "ANA NAA", "AANA NAA",
+        ],
+        regions: &[],
+    },
+    CountryRules {
+        country_code: "DE",
+        postcode_formats: &["NNNNN"],
+        regions: &[],
+    },
+    CountryRules {
+        country_code: "FR",
+        postcode_formats: &["NNNNN"],
+        regions: &[],
+    },
+    CountryRules {
+        country_code: "IT",
+        postcode_formats: &["NNNNN"],
+        regions: &[],
+    },
+];
+
+#[derive(Clone, PartialEq, Debug)]
+#[readonly::make]
+pub struct Address {
+    pub lines: Vec<String>,
+    pub city: String,
+    pub region: Option<String>,
+    pub postcode: String,
+    pub country_code: String,
+    pub coordinates: Option<Coordinates>,
+}
+
+impl Address {
+    /// Normalises the parts (trimmed, upper-case codes) and validates them against the country.
+    pub fn new(
+        lines: &[&str],
+        city: &str,
+        region: Option<&str>,
+        postcode: &str,
+        country_code: &str,
+        coordinates: Option<Coordinates>,
+    ) -> Result<Self, AddressError> {
+        let lines: Vec<String> = lines
+            .iter()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+        let city = city.trim().to_string();
+        let region = region
+            .map(|r| r.trim().to_uppercase())
+            .filter(|r| !r.is_empty());
+        let postcode = postcode.trim().to_uppercase();
+        let country_code = country_code.trim().to_uppercase();
+
+        if lines.is_empty() {
+            return Err(AddressError::MissingLine);
+        }
+        if lines.len() > MAX_ADDRESS_LINES {
+            return Err(AddressError::TooManyLines);
+        }
+        if lines.iter().any(|l| l.chars().count() > MAX_LINE_LENGTH) {
+            return Err(AddressError::LineTooLong);
+        }
+        if city.is_empty() {
+            return Err(AddressError::MissingCity);
+        }
+        if city.chars().count() > MAX_CITY_LENGTH {
+            return Err(AddressError::CityTooLong);
+        }
+        if region
+            .as_ref()
+            .is_some_and(|r| r.chars().count() > MAX_REGION_LENGTH)
+        {
+            return Err(AddressError::RegionTooLong);
+        }
+        if postcode.chars().count() > MAX_POSTCODE_LENGTH {
+            return Err(AddressError::InvalidPostcode);
+        }
+        if country_code.len() != 2 || !country_code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(AddressError::InvalidCountryCode);
+        }
+        if let Some(c) = coordinates {
+            if !(-90.0..=90.0).contains(&c.latitude) || !(-180.0..=180.0).contains(&c.longitude) {
+                return Err(AddressError::InvalidCoordinates);
+            }
+        }
+
+        // Countries without specific rules only get the length checks above.
+        if let Some(rules) = COUNTRY_RULES
+            .iter()
+            .find(|r| r.country_code == country_code)
+        {
+            if !rules
+                .postcode_formats
+                .iter()
+                .any(|f| matches_format(&postcode, f))
+            {
+                return Err(AddressError::InvalidPostcode);
+            }
+
+            if !rules.regions.is_empty() {
+                match &region {
+                    None => return Err(AddressError::MissingRegion),
+                    Some(r) if !rules.regions.contains(&r.as_str()) => {
+                        return Err(AddressError::InvalidRegion)
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+
+        Ok(Self {
+            lines,
+            city,
+            region,
+            postcode,
+            country_code,
+            coordinates,
+        })
+    }
+
+    /// Rebuilds an address read back from storage, where rows migrated from the legacy free text
+    /// may not satisfy every rule yet.
+    pub fn restore(
+        lines: &[&str],
+        city: &str,
+        region: Option<&str>,
+        postcode: &str,
+        country_code: &str,
+        coordinates: Option<Coordinates>,
+    ) -> Self {
+        Self {
+            lines: lines
+                .iter()
+                .filter(|l| !l.is_empty())
+                .map(|l| l.to_string())
+                .collect(),
+            city: city.to_string(),
+            region: region.map(str::to_string),
+            postcode: postcode.to_string(),
+            country_code: country_code.to_string(),
+            coordinates,
+        }
+    }
+
+    pub fn line1(&self) -> &str {
+        self.lines.first().map(String::as_str).unwrap_or_default()
+    }
+
+    pub fn line2(&self) -> Option<&str> {
+        self.lines.get(1).map(String::as_str)
+    }
+
+    pub fn with_coordinates(&self, coordinates: Option<Coordinates>) -> Self {
+        Self {
+            coordinates,
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    // Single line form, e.g. "12 Main St, Perth WA 6000, AU".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let locality = [
+            Some(self.city.as_str()),
+            self.region.as_deref(),
+            Some(self.postcode.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        write!(
+            f,
+            "{}, {}, {}",
+            self.lines.join(", "),
+            locality,
+            self.country_code
+        )
+    }
+}
+
+fn matches_format(postcode: &str, format: &str) -> bool {
+    postcode.len() == format.len()
+        && postcode.chars().zip(format.chars()).all(|(c, f)| match f {
+            'N' => c.is_ascii_digit(),
+            'A' => c.is_ascii_uppercase(),
+            other => c == other,
+        })
+}
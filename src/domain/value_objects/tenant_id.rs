@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// The organisation a row belongs to, the Entra ID tenant of the users working with it. Nothing is
+/// read or written without one.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TenantId(String);
+
+impl TenantId {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
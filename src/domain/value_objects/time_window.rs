@@ -0,0 +1,54 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimeWindowError {
+    NotOrdered,
+    InThePast,
+    PickupAfterDelivery,
+}
+
+impl TimeWindowError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TimeWindowError::NotOrdered => "not_ordered",
+            TimeWindowError::InThePast => "in_the_past",
+            TimeWindowError::PickupAfterDelivery => "pickup_after_delivery",
+        }
+    }
+}
+
+impl fmt::Display for TimeWindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TimeWindowError::NotOrdered => "A window must end after it starts.",
+            TimeWindowError::InThePast => "A window cannot start in the past.",
+            TimeWindowError::PickupAfterDelivery => {
+                "The pickup window cannot open or close after the delivery window."
+            }
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for TimeWindowError {}
+
+/// The span of time something may happen in, e.g. a delivery.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub earliest: DateTime<Utc>,
+    pub latest: DateTime<Utc>,
+}
+
+impl TimeWindow {
+    pub fn new(earliest: DateTime<Utc>, latest: DateTime<Utc>) -> Result<Self, TimeWindowError> {
+        if earliest >= latest {
+            return Err(TimeWindowError::NotOrdered);
+        }
+
+        Ok(Self { earliest, latest })
+    }
+}
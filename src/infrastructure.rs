@@ -1,2 +1,63 @@
+use anyhow::Result;
+use sqlx::{migrate::Migrator, PgPool};
+
+pub mod geocoding;
+pub mod idempotency;
 pub mod queries;
+pub mod rate_limiting;
 pub mod repositories;
+pub mod tenancy;
+
+/// The migrations in `migrations/`, embedded at build time.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Applies the pending migrations across tenants. Rows they backfill without a tenant of their own
+/// go to `default_tenant_id`.
+pub async fn migrate(pg_pool: &PgPool, default_tenant_id: Option<&str>) -> Result<()> {
+    tenancy::across_tenants(async {
+        let mut conn = pg_pool.acquire().await?;
+
+        sqlx::query("SELECT set_config('app.default_tenant_id', $1, false)")
+            .bind(default_tenant_id.unwrap_or_default())
+            .execute(&mut *conn)
+            .await?;
+
+        MIGRATOR.run(&mut *conn).await?;
+
+        // The connection goes back to the pool.
+        sqlx::query("RESET app.default_tenant_id")
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    })
+    .await
+}
+
+/// Rebuilds the indexes of every table without locking out writes, e.g. after bloat or corruption.
+/// The lookups by name and email have no search index of their own, they run on these. Partitions
+/// are rebuilt through their parent table.
+pub async fn reindex(pg_pool: &PgPool) -> Result<Vec<String>> {
+    tenancy::across_tenants(async {
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+        SELECT c.relname::text FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public' AND c.relkind IN ('r', 'p') AND NOT c.relispartition
+        ORDER BY c.relname
+            "#,
+        )
+        .fetch_all(pg_pool)
+        .await?;
+
+        for table in &tables {
+            // REINDEX cannot take a bind parameter, the name comes from the catalogue.
+            sqlx::query(&format!("REINDEX TABLE CONCURRENTLY public.\"{}\"", table))
+                .execute(pg_pool)
+                .await?;
+        }
+
+        Ok(tables)
+    })
+    .await
+}
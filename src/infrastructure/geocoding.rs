@@ -0,0 +1,47 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use sqlx::PgPool;
+use url::Url;
+
+use crate::domain::services::geocoder::Geocoder;
+
+pub mod cached_geocoder;
+pub mod gazetteer_geocoder;
+pub mod nominatim_geocoder;
+
+use cached_geocoder::CachedGeocoder;
+use gazetteer_geocoder::GazetteerGeocoder;
+use nominatim_geocoder::NominatimGeocoder;
+
+/// Builds the cached provider chain: the HTTP provider at `url` first, then the postcode
+/// gazetteer at `gazetteer_path`. Either can be left unset, with neither set addresses are saved
+/// without coordinates.
+pub fn providers(
+    db_pool: PgPool,
+    url: Option<&Url>,
+    gazetteer_path: Option<&Path>,
+) -> Result<Arc<dyn Geocoder>> {
+    let mut providers: Vec<Arc<dyn Geocoder>> = Vec::new();
+
+    if let Some(url) = url {
+        providers.push(Arc::new(NominatimGeocoder::new(url.as_str())?));
+    }
+
+    if let Some(path) = gazetteer_path {
+        let gazetteer = GazetteerGeocoder::from_path(path)?;
+        tracing::debug!(
+            "loaded {} postcode centroids from {}",
+            gazetteer.len(),
+            path.display()
+        );
+        providers.push(Arc::new(gazetteer));
+    }
+
+    if providers.is_empty() {
+        tracing::warn!("No geocoder configured, addresses will be saved without coordinates.");
+    }
+
+    Ok(Arc::new(CachedGeocoder::new(db_pool, providers)))
+}
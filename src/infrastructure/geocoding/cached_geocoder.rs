@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+use crate::domain::{
+    services::geocoder::Geocoder,
+    value_objects::{address::Address, coordinates::Coordinates},
+};
+
+// Addresses nobody could resolve are retried after this long, found ones are kept for good.
+const MISS_TTL_DAYS: i64 = 7;
+
+/// Tries each provider in order until one resolves the address, remembering the outcome in
+/// `geocode_cache`.
+pub struct CachedGeocoder {
+    pg_pool: Arc<PgPool>,
+    providers: Vec<Arc<dyn Geocoder>>,
+}
+
+impl CachedGeocoder {
+    pub fn new(pg_pool: PgPool, providers: Vec<Arc<dyn Geocoder>>) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            providers,
+        }
+    }
+
+    async fn store(
+        &self,
+        address_key: &str,
+        provider: Option<&str>,
+        coordinates: Option<Coordinates>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+INSERT INTO geocode_cache (address_key, provider, latitude, longitude, geocoded_at)
+VALUES ($1, $2, $3, $4, now())
+ON CONFLICT (address_key) DO UPDATE
+SET provider = EXCLUDED.provider, latitude = EXCLUDED.latitude,
+    longitude = EXCLUDED.longitude, geocoded_at = EXCLUDED.geocoded_at
+        "#,
+            address_key,
+            provider,
+            coordinates.map(|c| c.latitude),
+            coordinates.map(|c| c.longitude)
+        )
+        .execute(&*self.pg_pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Geocoder for CachedGeocoder {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    async fn geocode(&self, address: &Address) -> Result<Option<Coordinates>> {
+        // Nothing to ask, and caching misses now would hide addresses once a provider is added.
+        if self.providers.is_empty() {
+            return Ok(None);
+        }
+
+        let address_key = address_key(address);
+
+        let cached = sqlx::query!(
+            r#"
+        SELECT latitude, longitude, geocoded_at
+        FROM geocode_cache
+        WHERE address_key = $1
+            "#,
+            address_key
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        if let Some(cached) = cached {
+            match cached.latitude.zip(cached.longitude) {
+                Some((latitude, longitude)) => {
+                    return Ok(Some(Coordinates::new(latitude, longitude)))
+                }
+                None if cached.geocoded_at > Utc::now() - Duration::days(MISS_TTL_DAYS) => {
+                    return Ok(None)
+                }
+                None => (),
+            }
+        }
+
+        let mut failed = false;
+
+        for provider in &self.providers {
+            match provider.geocode(address).await {
+                Ok(Some(coordinates)) => {
+                    self.store(&address_key, Some(provider.name()), Some(coordinates))
+                        .await?;
+                    return Ok(Some(coordinates));
+                }
+                Ok(None) => (),
+                Err(err) => {
+                    tracing::warn!("Geocoder {} failed: {:#}", provider.name(), err);
+                    failed = true;
+                }
+            }
+        }
+
+        // A provider being down is not a miss, the address gets another go next time.
+        if !failed {
+            self.store(&address_key, None, None).await?;
+        }
+
+        Ok(None)
+    }
+}
+
+// Case and whitespace insensitive, coordinates are not part of the key.
+fn address_key(address: &Address) -> String {
+    [
+        address.lines.join(" "),
+        address.city.clone(),
+        address.region.clone().unwrap_or_default(),
+        address.postcode.clone(),
+        address.country_code.clone(),
+    ]
+    .iter()
+    .map(|part| {
+        part.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    })
+    .collect::<Vec<_>>()
+    .join("|")
+}
@@ -0,0 +1,93 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result};
+use axum::async_trait;
+use serde::Deserialize;
+
+use crate::domain::{
+    services::geocoder::Geocoder,
+    value_objects::{address::Address, coordinates::Coordinates},
+};
+
+#[derive(Deserialize)]
+struct GazetteerRecord {
+    country_code: String,
+    postcode: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+/// Offline lookup of postcode centroids, only as precise as the postcode itself.
+///
+/// The CSV needs a `country_code,postcode,latitude,longitude` header. Postcodes are matched without
+/// spaces, falling back to the outward part (e.g. `SW1A` for `SW1A 1AA`) when the full code is not
+/// listed.
+pub struct GazetteerGeocoder {
+    centroids: HashMap<(String, String), Coordinates>,
+}
+
+impl GazetteerGeocoder {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut reader = csv::Reader::from_path(path)
+            .with_context(|| format!("failed to open gazetteer {}", path.display()))?;
+
+        let mut centroids = HashMap::new();
+
+        for (index, record) in reader.deserialize::<GazetteerRecord>().enumerate() {
+            // Line 1 is the header.
+            let record =
+                record.with_context(|| format!("invalid gazetteer row at line {}", index + 2))?;
+
+            centroids.insert(
+                key(&record.country_code, &record.postcode),
+                Coordinates::new(record.latitude, record.longitude),
+            );
+        }
+
+        Ok(Self { centroids })
+    }
+
+    pub fn len(&self) -> usize {
+        self.centroids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty()
+    }
+}
+
+#[async_trait]
+impl Geocoder for GazetteerGeocoder {
+    fn name(&self) -> &'static str {
+        "gazetteer"
+    }
+
+    async fn geocode(&self, address: &Address) -> Result<Option<Coordinates>> {
+        let full = key(&address.country_code, &address.postcode);
+
+        if let Some(coordinates) = self.centroids.get(&full) {
+            return Ok(Some(*coordinates));
+        }
+
+        let outward = address
+            .postcode
+            .split_whitespace()
+            .next()
+            .filter(|outward| outward.len() < address.postcode.trim().len())
+            .map(|outward| key(&address.country_code, outward));
+
+        Ok(outward.and_then(|k| self.centroids.get(&k).copied()))
+    }
+}
+
+fn key(country_code: &str, postcode: &str) -> (String, String) {
+    (
+        country_code.trim().to_uppercase(),
+        postcode
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase(),
+    )
+}
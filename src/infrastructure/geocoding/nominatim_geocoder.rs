@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use axum::async_trait;
+use serde::Deserialize;
+use tokio::{sync::Mutex, time::Instant};
+use url::Url;
+
+use crate::domain::{
+    services::geocoder::Geocoder,
+    value_objects::{
+        address::{Address, UNKNOWN_COUNTRY_CODE},
+        coordinates::Coordinates,
+    },
+};
+
+// The public Nominatim usage policy asks for an identifying agent and at most one request a second.
+const USER_AGENT: &str = concat!("tsm/", env!("CARGO_PKG_VERSION"));
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct SearchResult {
+    lat: String,
+    lon: String,
+}
+
+/// Structured `/search` lookups against Nominatim or anything that speaks its API.
+pub struct NominatimGeocoder {
+    client: reqwest::Client,
+    search_url: Url,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl NominatimGeocoder {
+    pub fn new(base_url: &str) -> Result<Self> {
+        // Without the trailing slash `join` would replace the last path segment.
+        let base_url = format!("{}/", base_url.trim_end_matches('/'));
+        let search_url = Url::parse(&base_url)
+            .and_then(|u| u.join("search"))
+            .with_context(|| format!("invalid geocoder url {}", base_url))?;
+
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            search_url,
+            last_request: Mutex::new(None),
+        })
+    }
+
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+
+        if let Some(last) = *last_request {
+            tokio::time::sleep_until(last + MIN_REQUEST_INTERVAL).await;
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+#[async_trait]
+impl Geocoder for NominatimGeocoder {
+    fn name(&self) -> &'static str {
+        "nominatim"
+    }
+
+    async fn geocode(&self, address: &Address) -> Result<Option<Coordinates>> {
+        let mut query = vec![
+            ("format", "jsonv2".to_string()),
+            ("limit", "1".to_string()),
+            ("street", address.lines.join(", ")),
+            ("city", address.city.clone()),
+            ("postalcode", address.postcode.clone()),
+        ];
+        if let Some(region) = &address.region {
+            query.push(("state", region.clone()));
+        }
+        if address.country_code != UNKNOWN_COUNTRY_CODE {
+            query.push(("countrycodes", address.country_code.to_lowercase()));
+        }
+
+        self.throttle().await;
+
+        let results: Vec<SearchResult> = self
+            .client
+            .get(self.search_url.clone())
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let Some(result) = results.first() else {
+            return Ok(None);
+        };
+
+        Ok(Some(Coordinates::new(
+            result
+                .lat
+                .parse()
+                .context("invalid latitude from geocoder")?,
+            result
+                .lon
+                .parse()
+                .context("invalid longitude from geocoder")?,
+        )))
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// A response kept in `idempotency_keys` to be sent again.
+#[derive(Debug, Clone)]
+pub struct SavedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// What a request with an idempotency key is to do.
+#[derive(Debug)]
+pub enum Claim {
+    /// The key is new or expired and now held by this request, which saves or releases it.
+    Claimed,
+    /// Another request with the key is still being answered.
+    InProgress,
+    /// The key was used for this same request, whose response is sent again.
+    Replay(SavedResponse),
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+/// Takes `key` for the request with `fingerprint`, unless a request of `principal` used it after
+/// `expired_before`. A claim left by an instance that stopped mid-request holds the key until it
+/// expires.
+pub async fn claim(
+    pg_pool: &PgPool,
+    tenant: &TenantId,
+    principal: &str,
+    key: &str,
+    fingerprint: &[u8],
+    expired_before: DateTime<Utc>,
+) -> Result<Claim> {
+    let claimed = sqlx::query(
+        r#"
+INSERT INTO idempotency_keys (tenant_id, principal, idempotency_key, fingerprint)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (tenant_id, principal, idempotency_key) DO UPDATE
+SET fingerprint = EXCLUDED.fingerprint, status = NULL, headers = NULL, body = NULL,
+    created_at = now()
+WHERE idempotency_keys.created_at < $5
+        "#,
+    )
+    .bind(tenant.as_str())
+    .bind(principal)
+    .bind(key)
+    .bind(fingerprint)
+    .bind(expired_before)
+    .execute(pg_pool)
+    .await?
+    .rows_affected()
+        == 1;
+
+    if claimed {
+        // Each new key clears the principal's expired ones, so the table holds about a window of
+        // keys per principal.
+        sqlx::query(
+            r#"
+DELETE FROM idempotency_keys
+WHERE tenant_id = $1 AND principal = $2 AND created_at < $3
+            "#,
+        )
+        .bind(tenant.as_str())
+        .bind(principal)
+        .bind(expired_before)
+        .execute(pg_pool)
+        .await?;
+
+        return Ok(Claim::Claimed);
+    }
+
+    let row = sqlx::query(
+        r#"
+SELECT fingerprint, status, headers, body
+FROM idempotency_keys
+WHERE tenant_id = $1 AND principal = $2 AND idempotency_key = $3
+        "#,
+    )
+    .bind(tenant.as_str())
+    .bind(principal)
+    .bind(key)
+    .fetch_optional(pg_pool)
+    .await?;
+
+    // Released by the request holding it since, the client may try again.
+    let Some(row) = row else {
+        return Ok(Claim::InProgress);
+    };
+
+    if row.get::<Vec<u8>, _>("fingerprint") != fingerprint {
+        return Ok(Claim::Mismatch);
+    }
+
+    let Some(status) = row.get::<Option<i16>, _>("status") else {
+        return Ok(Claim::InProgress);
+    };
+
+    let Json(headers) = row.get::<Json<Vec<(String, String)>>, _>("headers");
+
+    Ok(Claim::Replay(SavedResponse {
+        status: status as u16,
+        headers,
+        body: row.get("body"),
+    }))
+}
+
+/// Keeps the response to the request holding `key`.
+pub async fn save(
+    pg_pool: &PgPool,
+    tenant: &TenantId,
+    principal: &str,
+    key: &str,
+    response: &SavedResponse,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+UPDATE idempotency_keys
+SET status = $4, headers = $5, body = $6
+WHERE tenant_id = $1 AND principal = $2 AND idempotency_key = $3
+        "#,
+    )
+    .bind(tenant.as_str())
+    .bind(principal)
+    .bind(key)
+    .bind(response.status as i16)
+    .bind(Json(&response.headers))
+    .bind(&response.body)
+    .execute(pg_pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Frees `key` without a response, so a retry is answered afresh.
+pub async fn release(
+    pg_pool: &PgPool,
+    tenant: &TenantId,
+    principal: &str,
+    key: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+DELETE FROM idempotency_keys
+WHERE tenant_id = $1 AND principal = $2 AND idempotency_key = $3
+        "#,
+    )
+    .bind(tenant.as_str())
+    .bind(principal)
+    .bind(key)
+    .execute(pg_pool)
+    .await?;
+
+    Ok(())
+}
@@ -1,2 +1,13 @@
+pub mod contact_queries;
 pub mod customer_queries;
+pub mod health_queries;
+pub mod import_queries;
+pub mod invoice_queries;
+pub mod metrics_queries;
+pub mod order_queries;
+pub mod proof_of_delivery_queries;
+pub mod route_queries;
+pub mod settlement_queries;
+pub mod tenant_queries;
+pub mod vehicle_queries;
 pub mod vendor_queries;
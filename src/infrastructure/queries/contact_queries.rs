@@ -0,0 +1,102 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::aggregates::contact::{ContactOwner, ContactRole};
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::models::contact_dto::ContactDto;
+
+pub async fn list_contacts(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    owner: ContactOwner,
+    role: Option<ContactRole>,
+) -> Result<Vec<ContactDto>> {
+    let contacts = sqlx::query(
+        r#"
+SELECT * FROM contacts
+WHERE customer_id IS NOT DISTINCT FROM $1 AND vendor_id IS NOT DISTINCT FROM $2
+    AND ($3::varchar IS NULL OR role = $3) AND tenant_id = $4
+ORDER BY role, is_primary DESC, id
+        "#,
+    )
+    .bind(owner.customer_id())
+    .bind(owner.vendor_id())
+    .bind(role.map(|r| r.as_str()))
+    .bind(tenant.as_str())
+    .map(map_contact)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(contacts)
+}
+
+pub async fn get_contact_by_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    owner: ContactOwner,
+    id: i32,
+) -> Result<Option<ContactDto>> {
+    let contact = sqlx::query(
+        r#"
+SELECT * FROM contacts
+WHERE id = $1 AND customer_id IS NOT DISTINCT FROM $2 AND vendor_id IS NOT DISTINCT FROM $3
+    AND tenant_id = $4
+        "#,
+    )
+    .bind(id)
+    .bind(owner.customer_id())
+    .bind(owner.vendor_id())
+    .bind(tenant.as_str())
+    .map(map_contact)
+    .fetch_optional(&db_pool)
+    .await?;
+
+    Ok(contact)
+}
+
+/// Who to send something meant for `role`, primary first. Falls back to the general contacts
+/// when nobody holds the role, and skips contacts missing an address for their preferred channel.
+/// Notifications and documents go through this rather than the customer or vendor email.
+pub async fn list_contact_recipients(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    owner: ContactOwner,
+    role: ContactRole,
+) -> Result<Vec<ContactDto>> {
+    let recipients = sqlx::query(
+        r#"
+WITH reachable AS (
+    SELECT * FROM contacts
+    WHERE customer_id IS NOT DISTINCT FROM $1 AND vendor_id IS NOT DISTINCT FROM $2
+        AND tenant_id = $5
+        AND CASE preferred_channel WHEN 'email' THEN email IS NOT NULL ELSE phone IS NOT NULL END
+)
+SELECT * FROM reachable
+WHERE role = $3
+    OR (role = $4 AND NOT EXISTS (SELECT 1 FROM reachable WHERE role = $3))
+ORDER BY is_primary DESC, id
+        "#,
+    )
+    .bind(owner.customer_id())
+    .bind(owner.vendor_id())
+    .bind(role.as_str())
+    .bind(ContactRole::General.as_str())
+    .bind(tenant.as_str())
+    .map(map_contact)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(recipients)
+}
+
+fn map_contact(row: PgRow) -> ContactDto {
+    ContactDto {
+        id: row.get("id"),
+        name: row.get("name"),
+        role: row.get("role"),
+        email: row.get("email"),
+        phone: row.get("phone"),
+        preferred_channel: row.get("preferred_channel"),
+        is_primary: row.get("is_primary"),
+    }
+}
@@ -1,35 +1,105 @@
 use anyhow::Result;
+use async_stream::try_stream;
+use futures::{stream::BoxStream, TryStreamExt};
 use sqlx::{postgres::PgRow, PgPool, Row};
 
-use crate::models::customer_dto::CustomerDto;
-
-pub async fn list_customers(db_pool: PgPool) -> Result<Vec<CustomerDto>> {
-    let customers = sqlx::query("SELECT * FROM customers ORDER BY name")
-        .map(|row: PgRow| CustomerDto {
-            id: row.get("id"),
-            name: row.get("name"),
-            address: row.get("address"),
-            email: row.get("email"),
-            contact_number: row.get("contact_number"),
-        })
-        .fetch_all(&db_pool)
-        .await?;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::tenancy;
+use crate::models::{address_dto::AddressDto, customer_dto::CustomerDto};
 
-    Ok(customers)
+pub async fn list_customers(db_pool: PgPool, tenant: &TenantId) -> Result<Vec<CustomerDto>> {
+    stream_customers(db_pool, tenant.clone())
+        .try_collect()
+        .await
 }
 
-pub async fn get_customer_by_id(db_pool: PgPool, id: i32) -> Result<Option<CustomerDto>> {
-    let customer = sqlx::query("SELECT * FROM customers WHERE id = $1")
+/// The rows of `list_customers` one at a time, for exports too large to hold in memory.
+pub fn stream_customers(
+    db_pool: PgPool,
+    tenant: TenantId,
+) -> BoxStream<'static, Result<CustomerDto>> {
+    let scope = tenant.clone();
+    let customers = try_stream! {
+        let mut customers = sqlx::query("SELECT * FROM customers WHERE tenant_id = $1 ORDER BY name")
+            .bind(tenant.as_str())
+            .map(map_customer)
+            .fetch(&db_pool);
+
+        while let Some(customer) = customers.try_next().await? {
+            yield customer;
+        }
+    };
+
+    Box::pin(tenancy::stream_in_tenant(scope, customers))
+}
+
+pub async fn get_customer_by_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<CustomerDto>> {
+    let customer = sqlx::query("SELECT * FROM customers WHERE id = $1 AND tenant_id = $2")
         .bind(id)
-        .map(|row: PgRow| CustomerDto {
-            id: row.get("id"),
-            name: row.get("name"),
-            address: row.get("address"),
-            email: row.get("email"),
-            contact_number: row.get("contact_number"),
-        })
+        .bind(tenant.as_str())
+        .map(map_customer)
         .fetch_optional(&db_pool)
         .await?;
 
     Ok(customer)
 }
+
+/// Customers whose email matches one of `emails`, ignoring case.
+pub async fn list_customers_by_email(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    emails: &[String],
+) -> Result<Vec<CustomerDto>> {
+    let customers = sqlx::query(
+        "SELECT * FROM customers WHERE lower(email) = ANY(SELECT lower(e) FROM UNNEST($1::text[]) e) AND tenant_id = $2 ORDER BY id",
+    )
+    .bind(emails)
+    .bind(tenant.as_str())
+    .map(map_customer)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(customers)
+}
+
+pub async fn list_customers_by_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: &[i32],
+) -> Result<Vec<CustomerDto>> {
+    let customers =
+        sqlx::query("SELECT * FROM customers WHERE id = ANY($1) AND tenant_id = $2 ORDER BY id")
+            .bind(ids)
+            .bind(tenant.as_str())
+            .map(map_customer)
+            .fetch_all(&db_pool)
+            .await?;
+
+    Ok(customers)
+}
+
+fn map_customer(row: PgRow) -> CustomerDto {
+    let line2: Option<String> = row.get("address_line2");
+
+    CustomerDto {
+        id: row.get("id"),
+        name: row.get("name"),
+        address: AddressDto {
+            lines: std::iter::once(row.get("address_line1"))
+                .chain(line2)
+                .collect(),
+            city: row.get("city"),
+            region: row.get("region"),
+            postcode: row.get("postcode"),
+            country_code: row.get("country_code"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+        },
+        email: row.get("email"),
+        contact_number: row.get("contact_number"),
+    }
+}
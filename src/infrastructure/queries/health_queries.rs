@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::infrastructure::MIGRATOR;
+
+pub async fn ping(db_pool: PgPool) -> Result<()> {
+    sqlx::query("SELECT 1").execute(&db_pool).await?;
+
+    Ok(())
+}
+
+/// The migrations of this build that are not applied, failed or were edited after being applied.
+pub async fn unapplied_migrations(db_pool: PgPool) -> Result<Vec<String>> {
+    let applied: HashMap<i64, (bool, Vec<u8>)> = sqlx::query(
+        r#"
+SELECT version, success, checksum
+FROM _sqlx_migrations
+        "#,
+    )
+    .map(|row: PgRow| {
+        (
+            row.get("version"),
+            (row.get("success"), row.get("checksum")),
+        )
+    })
+    .fetch_all(&db_pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let unapplied = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .filter_map(|m| {
+            let problem = match applied.get(&m.version) {
+                None => "is not applied",
+                Some((false, _)) => "failed",
+                Some((true, checksum)) if *checksum != *m.checksum => {
+                    "was edited after being applied"
+                }
+                Some(_) => return None,
+            };
+
+            Some(format!("{} {} {}", m.version, m.description, problem))
+        })
+        .collect();
+
+    Ok(unapplied)
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::models::import_dto::ImportDto;
+
+pub async fn get_import_by_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<ImportDto>> {
+    let import = sqlx::query(
+        r#"
+SELECT id, entity, imported_by, total_rows, created_count, updated_count, rejected_count,
+    error_file IS NOT NULL AS has_error_file, created_at
+FROM imports
+WHERE id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| ImportDto {
+        id: row.get("id"),
+        entity: row.get("entity"),
+        imported_by: row.get("imported_by"),
+        total_rows: row.get("total_rows"),
+        created: row.get("created_count"),
+        updated: row.get("updated_count"),
+        rejected: row.get("rejected_count"),
+        has_error_file: row.get("has_error_file"),
+        created_at: row.get("created_at"),
+    })
+    .fetch_optional(&db_pool)
+    .await?;
+
+    Ok(import)
+}
+
+/// The rejected rows of an import as CSV, `None` when the import is unknown or had no rejects.
+pub async fn get_import_error_file(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<String>> {
+    let error_file = sqlx::query("SELECT error_file FROM imports WHERE id = $1 AND tenant_id = $2")
+        .bind(id)
+        .bind(tenant.as_str())
+        .map(|row: PgRow| row.get::<Option<String>, _>("error_file"))
+        .fetch_optional(&db_pool)
+        .await?;
+
+    Ok(error_file.flatten())
+}
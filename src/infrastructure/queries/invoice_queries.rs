@@ -0,0 +1,40 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::models::invoice_dto::InvoiceSummaryDto;
+
+/// Invoices and credit notes of a customer, newest first.
+pub async fn list_invoices_by_customer(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    customer_id: i32,
+) -> Result<Vec<InvoiceSummaryDto>> {
+    let invoices = sqlx::query(
+        r#"
+SELECT inv.id, inv.customer_id, inv.invoice_type, inv.invoice_status, inv.invoice_number,
+    inv.credited_invoice_id, inv.issued_at, COALESCE(SUM(il.line_total), 0) AS total
+FROM invoices inv
+LEFT JOIN invoice_lines il ON il.invoice_id = inv.id
+WHERE inv.customer_id = $1 AND inv.tenant_id = $2
+GROUP BY inv.id
+ORDER BY inv.id DESC
+        "#,
+    )
+    .bind(customer_id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| InvoiceSummaryDto {
+        id: row.get("id"),
+        customer_id: row.get("customer_id"),
+        invoice_type: row.get("invoice_type"),
+        status: row.get("invoice_status"),
+        invoice_number: row.get("invoice_number"),
+        credited_invoice_id: row.get("credited_invoice_id"),
+        issued_at: row.get("issued_at"),
+        total: row.get("total"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(invoices)
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::aggregates::order::OrderStatus;
+
+/// How many orders are in each status, zero for the statuses without orders so their series do
+/// not disappear once the last order moves on.
+pub async fn count_orders_by_status(db_pool: PgPool) -> Result<Vec<(String, i64)>> {
+    let statuses: Vec<&str> = OrderStatus::ALL.iter().map(|s| s.as_str()).collect();
+
+    let counts = sqlx::query(
+        r#"
+SELECT status AS order_status, count(orders.id) AS orders
+FROM unnest($1::text[]) AS status
+LEFT JOIN orders ON orders.order_status = status
+GROUP BY status
+ORDER BY status
+        "#,
+    )
+    .bind(&statuses)
+    .map(|row: PgRow| (row.get("order_status"), row.get("orders")))
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(counts)
+}
+
+/// How many vehicles are available and how many are not, in that order.
+pub async fn count_vehicles_by_availability(db_pool: PgPool) -> Result<(i64, i64)> {
+    let counts = sqlx::query(
+        r#"
+SELECT count(*) FILTER (WHERE availability_status) AS available,
+       count(*) FILTER (WHERE NOT availability_status) AS unavailable
+FROM vehicles
+        "#,
+    )
+    .map(|row: PgRow| (row.get("available"), row.get("unavailable")))
+    .fetch_one(&db_pool)
+    .await?;
+
+    Ok(counts)
+}
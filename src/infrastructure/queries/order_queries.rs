@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_stream::try_stream;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use futures::{stream::BoxStream, TryStreamExt};
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::tenancy;
+use crate::models::order_dto::{
+    ItemDto, OrderAssignmentDto, OrderDto, OrderDue, OrderLineDto, OrderWindowDto,
+};
+use crate::models::route_plan_dto::CoordinatesDto;
+
+/// Orders newest first, only those with `status` and `due` when given. The pickup window only
+/// counts until the goods are collected, days are those of the order's time zone.
+pub async fn list_orders(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    status: Option<&str>,
+    due: Option<OrderDue>,
+) -> Result<Vec<OrderDto>> {
+    stream_orders(db_pool, tenant.clone(), status.map(str::to_string), due)
+        .try_collect()
+        .await
+}
+
+/// The rows of `list_orders` one at a time, for exports too large to hold in memory.
+pub fn stream_orders(
+    db_pool: PgPool,
+    tenant: TenantId,
+    status: Option<String>,
+    due: Option<OrderDue>,
+) -> BoxStream<'static, Result<OrderDto>> {
+    let due = due.map(|d| match d {
+        OrderDue::Today => "today",
+        OrderDue::Late => "late",
+    });
+
+    let scope = tenant.clone();
+    let orders = try_stream! {
+        let mut orders = sqlx::query(
+            r#"
+SELECT * FROM orders
+WHERE ($1::text IS NULL OR order_status = $1)
+    AND ($2::text IS NULL OR CASE $2
+        WHEN 'today' THEN
+            (order_status IN ('pending', 'confirmed', 'assigned', 'in_transit')
+                AND (delivery_latest AT TIME ZONE time_zone)::date = (now() AT TIME ZONE time_zone)::date)
+            OR (order_status IN ('pending', 'confirmed', 'assigned')
+                AND (pickup_latest AT TIME ZONE time_zone)::date = (now() AT TIME ZONE time_zone)::date)
+        WHEN 'late' THEN
+            (order_status IN ('pending', 'confirmed', 'assigned', 'in_transit') AND delivery_latest < now())
+            OR (order_status IN ('pending', 'confirmed', 'assigned') AND pickup_latest < now())
+    END)
+    AND tenant_id = $3
+ORDER BY id DESC
+            "#,
+        )
+        .bind(status)
+        .bind(due)
+        .bind(tenant.as_str())
+        .map(map_order)
+        .fetch(&db_pool);
+
+        while let Some(order) = orders.try_next().await? {
+            yield order;
+        }
+    };
+
+    Box::pin(tenancy::stream_in_tenant(scope, orders))
+}
+
+pub async fn list_orders_by_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: &[i32],
+) -> Result<Vec<OrderDto>> {
+    let orders =
+        sqlx::query("SELECT * FROM orders WHERE id = ANY($1) AND tenant_id = $2 ORDER BY id")
+            .bind(ids)
+            .bind(tenant.as_str())
+            .map(map_order)
+            .fetch_all(&db_pool)
+            .await?;
+
+    Ok(orders)
+}
+
+/// The orders of several customers at once, newest first.
+pub async fn list_orders_by_customer_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    customer_ids: &[i32],
+) -> Result<Vec<OrderDto>> {
+    let orders = sqlx::query(
+        "SELECT * FROM orders WHERE customer_id = ANY($1) AND tenant_id = $2 ORDER BY id DESC",
+    )
+    .bind(customer_ids)
+    .bind(tenant.as_str())
+    .map(map_order)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(orders)
+}
+
+/// The items ordered in each of the orders, by item name.
+pub async fn list_order_lines(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_ids: &[i32],
+) -> Result<Vec<OrderLineDto>> {
+    let lines = sqlx::query(
+        r#"
+SELECT oi.order_id, oi.quantity, oi.unit_price, i.id AS item_id, i.name, i.description,
+    i.quantity_available, i.unit_price AS list_price
+FROM order_items oi
+JOIN items i ON i.id = oi.item_id
+WHERE oi.order_id = ANY($1) AND oi.tenant_id = $2
+ORDER BY oi.order_id, i.name
+        "#,
+    )
+    .bind(order_ids)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| OrderLineDto {
+        order_id: row.get("order_id"),
+        item: ItemDto {
+            id: row.get("item_id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            quantity_available: row.get("quantity_available"),
+            unit_price: row.get("list_price"),
+        },
+        quantity: row.get("quantity"),
+        unit_price: row.get("unit_price"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(lines)
+}
+
+/// The vehicle and main route each order was put on by the accepted route plans. Plans are stored
+/// as JSON, so their stops are matched to the routes created for the same vehicle on acceptance.
+pub async fn list_order_assignments(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_ids: &[i32],
+) -> Result<Vec<OrderAssignmentDto>> {
+    let assignments = sqlx::query(
+        r#"
+SELECT DISTINCT (s->>'order_id')::int AS order_id, vr.vehicle_id, vr.route_id
+FROM route_plans p
+CROSS JOIN jsonb_array_elements(p.plan->'routes') r
+CROSS JOIN jsonb_array_elements(r->'stops') s
+JOIN route_plan_routes prr ON prr.route_plan_id = p.id
+JOIN vehicle_routes vr ON vr.route_id = prr.route_id AND vr.vehicle_id = (r->>'vehicle_id')::int
+WHERE p.plan_status = 'accepted' AND (s->>'order_id')::int = ANY($1) AND p.tenant_id = $2
+ORDER BY order_id, vr.route_id
+        "#,
+    )
+    .bind(order_ids)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| OrderAssignmentDto {
+        order_id: row.get("order_id"),
+        vehicle_id: row.get("vehicle_id"),
+        route_id: row.get("route_id"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(assignments)
+}
+
+/// Geocoded customer address of each order, orders whose customer has no coordinates are left out.
+pub async fn list_delivery_coordinates(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_ids: Vec<i32>,
+) -> Result<HashMap<i32, CoordinatesDto>> {
+    let coordinates = sqlx::query(
+        r#"
+SELECT o.id, c.latitude, c.longitude
+FROM orders o
+JOIN customers c ON c.id = o.customer_id
+WHERE o.id = ANY($1) AND o.tenant_id = $2 AND c.latitude IS NOT NULL AND c.longitude IS NOT NULL
+        "#,
+    )
+    .bind(order_ids)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| {
+        (
+            row.get("id"),
+            CoordinatesDto {
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+            },
+        )
+    })
+    .fetch_all(&db_pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    Ok(coordinates)
+}
+
+fn map_order(row: PgRow) -> OrderDto {
+    let time_zone: Option<String> = row.get("time_zone");
+    let zone = time_zone.as_deref().and_then(|z| z.parse::<Tz>().ok());
+
+    let window = |earliest: &str, latest: &str| {
+        let earliest: Option<DateTime<Utc>> = row.get(earliest);
+        let latest: Option<DateTime<Utc>> = row.get(latest);
+
+        Some(OrderWindowDto::in_zone(earliest?, latest?, zone?))
+    };
+
+    OrderDto {
+        id: row.get("id"),
+        customer_id: row.get("customer_id"),
+        status: row.get("order_status"),
+        created_at: row.get("created_at"),
+        pickup_window: window("pickup_earliest", "pickup_latest"),
+        delivery_window: window("delivery_earliest", "delivery_latest"),
+        time_zone,
+    }
+}
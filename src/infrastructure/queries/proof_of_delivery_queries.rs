@@ -0,0 +1,101 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::models::proof_of_delivery_dto::{
+    DeliveryFileContent, DeliveryFileDto, ProofOfDeliveryDto,
+};
+
+pub async fn get_proof_of_delivery_by_order_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_id: i32,
+) -> Result<Option<ProofOfDeliveryDto>> {
+    let proof_of_delivery = sqlx::query(
+        r#"
+SELECT id, order_id, recipient_name, delivered_at, latitude, longitude, notes
+FROM proof_of_deliveries
+WHERE order_id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(order_id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| ProofOfDeliveryDto {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        recipient_name: row.get("recipient_name"),
+        delivered_at: row.get("delivered_at"),
+        latitude: row.get("latitude"),
+        longitude: row.get("longitude"),
+        notes: row.get("notes"),
+        signature: None,
+        photo: None,
+    })
+    .fetch_optional(&db_pool)
+    .await?;
+
+    let Some(mut proof_of_delivery) = proof_of_delivery else {
+        return Ok(None);
+    };
+
+    let files = sqlx::query(
+        r#"
+SELECT kind, file_name, content_type, octet_length(content) AS size
+FROM proof_of_delivery_files
+WHERE proof_of_delivery_id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(proof_of_delivery.id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| {
+        let kind: String = row.get("kind");
+        let file = DeliveryFileDto {
+            url: format!("/v1/api/orders/{}/proof-of-delivery/{}", order_id, kind),
+            file_name: row.get("file_name"),
+            content_type: row.get("content_type"),
+            size: row.get("size"),
+        };
+
+        (kind, file)
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    for (kind, file) in files {
+        match kind.as_str() {
+            "signature" => proof_of_delivery.signature = Some(file),
+            "photo" => proof_of_delivery.photo = Some(file),
+            _ => (),
+        }
+    }
+
+    Ok(Some(proof_of_delivery))
+}
+
+pub async fn get_proof_of_delivery_file(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    order_id: i32,
+    kind: &str,
+) -> Result<Option<DeliveryFileContent>> {
+    let file = sqlx::query(
+        r#"
+SELECT f.file_name, f.content_type, f.content
+FROM proof_of_delivery_files f
+JOIN proof_of_deliveries p ON p.id = f.proof_of_delivery_id
+WHERE p.order_id = $1 AND f.kind = $2 AND p.tenant_id = $3
+        "#,
+    )
+    .bind(order_id)
+    .bind(kind)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| DeliveryFileContent {
+        file_name: row.get("file_name"),
+        content_type: row.get("content_type"),
+        content: row.get("content"),
+    })
+    .fetch_optional(&db_pool)
+    .await?;
+
+    Ok(file)
+}
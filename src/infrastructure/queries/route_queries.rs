@@ -0,0 +1,129 @@
+use anyhow::Result;
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::models::route_dto::DispatchedRouteDto;
+use crate::models::settlement_dto::VehicleRouteDto;
+
+/// Length of a route in kilometres.
+pub async fn get_route_distance(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<Decimal>> {
+    let distance = sqlx::query("SELECT distance FROM routes WHERE id = $1 AND tenant_id = $2")
+        .bind(id)
+        .bind(tenant.as_str())
+        .fetch_optional(&db_pool)
+        .await?
+        .map(|row| row.get("distance"));
+
+    Ok(distance)
+}
+
+/// The trips of the accepted route plans, newest first, without their legs.
+pub async fn list_main_routes(
+    db_pool: PgPool,
+    tenant: &TenantId,
+) -> Result<Vec<DispatchedRouteDto>> {
+    let routes = sqlx::query(
+        "SELECT * FROM routes WHERE main_route_id IS NULL AND tenant_id = $1 ORDER BY id DESC",
+    )
+    .bind(tenant.as_str())
+    .map(map_route)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(routes)
+}
+
+pub async fn list_routes_by_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: &[i32],
+) -> Result<Vec<DispatchedRouteDto>> {
+    let routes =
+        sqlx::query("SELECT * FROM routes WHERE id = ANY($1) AND tenant_id = $2 ORDER BY id")
+            .bind(ids)
+            .bind(tenant.as_str())
+            .map(map_route)
+            .fetch_all(&db_pool)
+            .await?;
+
+    Ok(routes)
+}
+
+/// The legs of several main routes at once, in the order they are driven.
+pub async fn list_route_legs(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    main_route_ids: &[i32],
+) -> Result<Vec<DispatchedRouteDto>> {
+    let legs = sqlx::query(
+        "SELECT * FROM routes WHERE main_route_id = ANY($1) AND tenant_id = $2 ORDER BY main_route_id, id",
+    )
+    .bind(main_route_ids)
+    .bind(tenant.as_str())
+    .map(map_route)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(legs)
+}
+
+/// The routes driven by several vehicles at once, newest first.
+pub async fn list_vehicle_routes_by_vehicle_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vehicle_ids: &[i32],
+) -> Result<Vec<VehicleRouteDto>> {
+    let vehicle_routes = sqlx::query(
+        "SELECT * FROM vehicle_routes WHERE vehicle_id = ANY($1) AND tenant_id = $2 ORDER BY vehicle_id, route_id DESC",
+    )
+    .bind(vehicle_ids)
+    .bind(tenant.as_str())
+    .map(map_vehicle_route)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(vehicle_routes)
+}
+
+/// The vehicles driving several routes at once.
+pub async fn list_vehicle_routes_by_route_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    route_ids: &[i32],
+) -> Result<Vec<VehicleRouteDto>> {
+    let vehicle_routes = sqlx::query(
+        "SELECT * FROM vehicle_routes WHERE route_id = ANY($1) AND tenant_id = $2 ORDER BY route_id, vehicle_id",
+    )
+    .bind(route_ids)
+    .bind(tenant.as_str())
+    .map(map_vehicle_route)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(vehicle_routes)
+}
+
+fn map_route(row: PgRow) -> DispatchedRouteDto {
+    DispatchedRouteDto {
+        id: row.get("id"),
+        main_route_id: row.get("main_route_id"),
+        origin: row.get("origin"),
+        destination: row.get("destination"),
+        distance: row.get("distance"),
+        estimated_travel_time: row.get("estimated_travel_time"),
+    }
+}
+
+fn map_vehicle_route(row: PgRow) -> VehicleRouteDto {
+    VehicleRouteDto {
+        vehicle_id: row.get("vehicle_id"),
+        route_id: row.get("route_id"),
+        load: row.get("load"),
+        completed_at: row.get("completed_at"),
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::aggregates::settlement::SettlementStatus;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::queries::vendor_queries::get_vendor_by_id;
+use crate::models::settlement_dto::{
+    PayableDto, SettlementStatementDto, SettlementSummaryDto, StatementLineDto, StatementVendorDto,
+};
+
+/// Settlements of a vendor, latest period first.
+pub async fn list_settlements_by_vendor(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vendor_id: i32,
+) -> Result<Vec<SettlementSummaryDto>> {
+    let settlements = sqlx::query(
+        r#"
+SELECT s.id, s.vendor_id, s.period_start, s.period_end, s.settlement_status, s.approved_at,
+    COUNT(sl.route_id) AS route_count, COALESCE(SUM(sl.amount), 0) AS total
+FROM settlements s
+LEFT JOIN settlement_lines sl ON sl.settlement_id = s.id
+WHERE s.vendor_id = $1 AND s.tenant_id = $2
+GROUP BY s.id
+ORDER BY s.period_end DESC, s.id DESC
+        "#,
+    )
+    .bind(vendor_id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| SettlementSummaryDto {
+        id: row.get("id"),
+        vendor_id: row.get("vendor_id"),
+        period_start: row.get("period_start"),
+        period_end: row.get("period_end"),
+        status: row.get("settlement_status"),
+        approved_at: row.get("approved_at"),
+        route_count: row.get("route_count"),
+        total: row.get("total"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(settlements)
+}
+
+/// What the vendor is being paid for, route by route, as sent to them with the remittance.
+pub async fn get_settlement_statement(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<SettlementStatementDto>> {
+    let Some(header) = sqlx::query(
+        r#"
+SELECT id, vendor_id, period_start, period_end, settlement_status, approved_at, approved_by
+FROM settlements
+WHERE id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(id)
+    .bind(tenant.as_str())
+    .fetch_optional(&db_pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let Some(vendor) = get_vendor_by_id(db_pool.clone(), tenant, header.get("vendor_id")).await?
+    else {
+        return Ok(None);
+    };
+
+    let lines = sqlx::query(
+        r#"
+SELECT sl.completed_at, sl.vehicle_id, v.type, sl.route_id, r.origin, r.destination,
+    sl.distance, sl.load, rc.name AS rate_card_name, sl.amount, sl.minimum_charge_applied
+FROM settlement_lines sl
+JOIN vehicles v ON v.id = sl.vehicle_id
+JOIN routes r ON r.id = sl.route_id
+JOIN rate_cards rc ON rc.id = sl.rate_card_id
+WHERE sl.settlement_id = $1 AND sl.tenant_id = $2
+ORDER BY sl.completed_at, sl.vehicle_id, sl.route_id
+        "#,
+    )
+    .bind(id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| StatementLineDto {
+        completed_at: row.get("completed_at"),
+        vehicle_id: row.get("vehicle_id"),
+        vehicle_type: row.get("type"),
+        route_id: row.get("route_id"),
+        origin: row.get("origin"),
+        destination: row.get("destination"),
+        distance: row.get("distance"),
+        load: row.get("load"),
+        rate_card_name: row.get("rate_card_name"),
+        amount: row.get("amount"),
+        minimum_charge_applied: row.get("minimum_charge_applied"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(Some(SettlementStatementDto {
+        settlement_id: header.get("id"),
+        status: header.get("settlement_status"),
+        vendor: StatementVendorDto {
+            id: vendor.id,
+            name: vendor.name,
+            email: vendor.email,
+            address: vendor.address,
+        },
+        period_start: header.get("period_start"),
+        period_end: header.get("period_end"),
+        approved_at: header.get("approved_at"),
+        approved_by: header.get("approved_by"),
+        total: lines.iter().map(|l| l.amount).sum(),
+        lines,
+    }))
+}
+
+/// Approved settlements whose approval date (UTC) falls between the two dates, inclusive.
+pub async fn list_approved_payables(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<PayableDto>> {
+    let payables = sqlx::query(
+        r#"
+SELECT s.id, s.vendor_id, v.name AS vendor_name, v.email AS vendor_email,
+    s.period_start, s.period_end, s.approved_at, s.approved_by,
+    COUNT(sl.route_id) AS route_count, COALESCE(SUM(sl.amount), 0) AS amount
+FROM settlements s
+JOIN vendors v ON v.id = s.vendor_id
+LEFT JOIN settlement_lines sl ON sl.settlement_id = s.id
+WHERE s.settlement_status = $1 AND s.tenant_id = $4
+    AND (s.approved_at AT TIME ZONE 'UTC')::date BETWEEN $2 AND $3
+GROUP BY s.id, v.id
+ORDER BY s.approved_at, s.id
+        "#,
+    )
+    .bind(SettlementStatus::Approved.as_str())
+    .bind(from)
+    .bind(to)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| PayableDto {
+        settlement_id: row.get("id"),
+        vendor_id: row.get("vendor_id"),
+        vendor_name: row.get("vendor_name"),
+        vendor_email: row.get("vendor_email"),
+        period_start: row.get("period_start"),
+        period_end: row.get("period_end"),
+        approved_at: row.get("approved_at"),
+        approved_by: row.get("approved_by"),
+        route_count: row.get("route_count"),
+        amount: row.get("amount"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(payables)
+}
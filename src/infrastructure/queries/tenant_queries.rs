@@ -0,0 +1,22 @@
+use anyhow::Result;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// Tenants with customers or vendors saved without coordinates. Sees every tenant's rows, so it
+/// is meant for background jobs running across tenants.
+pub async fn list_tenants_without_coordinates(db_pool: PgPool) -> Result<Vec<TenantId>> {
+    let tenants = sqlx::query(
+        r#"
+SELECT tenant_id FROM customers WHERE latitude IS NULL
+UNION
+SELECT tenant_id FROM vendors WHERE latitude IS NULL
+ORDER BY tenant_id
+        "#,
+    )
+    .map(|row: PgRow| TenantId::new(row.get("tenant_id")))
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(tenants)
+}
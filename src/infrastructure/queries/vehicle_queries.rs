@@ -0,0 +1,170 @@
+use anyhow::Result;
+use async_stream::try_stream;
+use futures::{stream::BoxStream, TryStreamExt};
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::tenancy;
+use crate::models::vehicle_dto::{LatestVehiclePositionDto, VehicleDto};
+
+pub async fn list_vehicles_by_vendor(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vendor_id: i32,
+) -> Result<Vec<VehicleDto>> {
+    stream_vehicles_by_vendor(db_pool, tenant.clone(), vendor_id)
+        .try_collect()
+        .await
+}
+
+/// The rows of `list_vehicles_by_vendor` one at a time, for exports too large to hold in memory.
+pub fn stream_vehicles_by_vendor(
+    db_pool: PgPool,
+    tenant: TenantId,
+    vendor_id: i32,
+) -> BoxStream<'static, Result<VehicleDto>> {
+    let scope = tenant.clone();
+    let vehicles = try_stream! {
+        let mut vehicles = sqlx::query(
+            "SELECT * FROM vehicles WHERE vendor_id = $1 AND tenant_id = $2 ORDER BY id",
+        )
+        .bind(vendor_id)
+        .bind(tenant.as_str())
+        .map(map_vehicle)
+        .fetch(&db_pool);
+
+        while let Some(vehicle) = vehicles.try_next().await? {
+            yield vehicle;
+        }
+    };
+
+    Box::pin(tenancy::stream_in_tenant(scope, vehicles))
+}
+
+pub async fn list_vehicles(db_pool: PgPool, tenant: &TenantId) -> Result<Vec<VehicleDto>> {
+    let vehicles = sqlx::query("SELECT * FROM vehicles WHERE tenant_id = $1 ORDER BY id")
+        .bind(tenant.as_str())
+        .map(map_vehicle)
+        .fetch_all(&db_pool)
+        .await?;
+
+    Ok(vehicles)
+}
+
+pub async fn list_vehicles_by_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: &[i32],
+) -> Result<Vec<VehicleDto>> {
+    let vehicles =
+        sqlx::query("SELECT * FROM vehicles WHERE id = ANY($1) AND tenant_id = $2 ORDER BY id")
+            .bind(ids)
+            .bind(tenant.as_str())
+            .map(map_vehicle)
+            .fetch_all(&db_pool)
+            .await?;
+
+    Ok(vehicles)
+}
+
+/// The vehicles of several vendors at once, by vendor and id.
+pub async fn list_vehicles_by_vendor_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vendor_ids: &[i32],
+) -> Result<Vec<VehicleDto>> {
+    let vehicles = sqlx::query(
+        "SELECT * FROM vehicles WHERE vendor_id = ANY($1) AND tenant_id = $2 ORDER BY vendor_id, id",
+    )
+    .bind(vendor_ids)
+    .bind(tenant.as_str())
+    .map(map_vehicle)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(vehicles)
+}
+
+pub async fn get_vehicle_by_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vendor_id: i32,
+    id: i32,
+) -> Result<Option<VehicleDto>> {
+    let vehicle =
+        sqlx::query("SELECT * FROM vehicles WHERE vendor_id = $1 AND id = $2 AND tenant_id = $3")
+            .bind(vendor_id)
+            .bind(id)
+            .bind(tenant.as_str())
+            .map(map_vehicle)
+            .fetch_optional(&db_pool)
+            .await?;
+
+    Ok(vehicle)
+}
+
+/// Vehicles flagged as available, optionally narrowed down to the given ids.
+pub async fn list_available_vehicles(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: Option<Vec<i32>>,
+) -> Result<Vec<VehicleDto>> {
+    let vehicles = sqlx::query(
+        r#"
+SELECT * FROM vehicles
+WHERE availability_status AND ($1::int[] IS NULL OR id = ANY($1)) AND tenant_id = $2
+ORDER BY id
+        "#,
+    )
+    .bind(ids)
+    .bind(tenant.as_str())
+    .map(map_vehicle)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(vehicles)
+}
+
+pub async fn list_latest_vehicle_positions(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    vendor_id: Option<i32>,
+) -> Result<Vec<LatestVehiclePositionDto>> {
+    let positions = sqlx::query(
+        r#"
+SELECT p.vehicle_id, v.vendor_id, v.type, v.availability_status,
+       p.recorded_at, p.latitude, p.longitude, p.speed, p.heading
+FROM vehicle_last_positions p
+JOIN vehicles v ON v.id = p.vehicle_id
+WHERE ($1::int IS NULL OR v.vendor_id = $1) AND v.tenant_id = $2
+ORDER BY p.vehicle_id
+        "#,
+    )
+    .bind(vendor_id)
+    .bind(tenant.as_str())
+    .map(|row: PgRow| LatestVehiclePositionDto {
+        vehicle_id: row.get("vehicle_id"),
+        vendor_id: row.get("vendor_id"),
+        vehicle_type: row.get("type"),
+        availability_status: row.get("availability_status"),
+        recorded_at: row.get("recorded_at"),
+        latitude: row.get("latitude"),
+        longitude: row.get("longitude"),
+        speed: row.get("speed"),
+        heading: row.get("heading"),
+    })
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(positions)
+}
+
+fn map_vehicle(row: PgRow) -> VehicleDto {
+    VehicleDto {
+        id: row.get("id"),
+        vendor_id: row.get("vendor_id"),
+        vehicle_type: row.get("type"),
+        capacity: row.get("capacity"),
+        availability_status: row.get("availability_status"),
+    }
+}
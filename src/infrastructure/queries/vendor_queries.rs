@@ -1,35 +1,100 @@
 use anyhow::Result;
+use async_stream::try_stream;
+use futures::{stream::BoxStream, TryStreamExt};
 use sqlx::{postgres::PgRow, PgPool, Row};
 
-use crate::models::vendor_dto::VendorDto;
-
-pub async fn list_vendors(db_pool: PgPool) -> Result<Vec<VendorDto>> {
-    let vendors = sqlx::query("SELECT * FROM vendors ORDER BY name")
-        .map(|row: PgRow| VendorDto {
-            id: row.get("id"),
-            name: row.get("name"),
-            address: row.get("address"),
-            email: row.get("email"),
-            contact_number: row.get("contact_number"),
-        })
-        .fetch_all(&db_pool)
-        .await?;
+use crate::domain::value_objects::tenant_id::TenantId;
+use crate::infrastructure::tenancy;
+use crate::models::{address_dto::AddressDto, vendor_dto::VendorDto};
 
-    Ok(vendors)
+pub async fn list_vendors(db_pool: PgPool, tenant: &TenantId) -> Result<Vec<VendorDto>> {
+    stream_vendors(db_pool, tenant.clone()).try_collect().await
 }
 
-pub async fn get_vendor_by_id(db_pool: PgPool, id: i32) -> Result<Option<VendorDto>> {
-    let vendor = sqlx::query("SELECT * FROM vendors WHERE id = $1")
+/// The rows of `list_vendors` one at a time, for exports too large to hold in memory.
+pub fn stream_vendors(db_pool: PgPool, tenant: TenantId) -> BoxStream<'static, Result<VendorDto>> {
+    let scope = tenant.clone();
+    let vendors = try_stream! {
+        let mut vendors = sqlx::query("SELECT * FROM vendors WHERE tenant_id = $1 ORDER BY name")
+            .bind(tenant.as_str())
+            .map(map_vendor)
+            .fetch(&db_pool);
+
+        while let Some(vendor) = vendors.try_next().await? {
+            yield vendor;
+        }
+    };
+
+    Box::pin(tenancy::stream_in_tenant(scope, vendors))
+}
+
+pub async fn get_vendor_by_id(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    id: i32,
+) -> Result<Option<VendorDto>> {
+    let vendor = sqlx::query("SELECT * FROM vendors WHERE id = $1 AND tenant_id = $2")
         .bind(id)
-        .map(|row: PgRow| VendorDto {
-            id: row.get("id"),
-            name: row.get("name"),
-            address: row.get("address"),
-            email: row.get("email"),
-            contact_number: row.get("contact_number"),
-        })
+        .bind(tenant.as_str())
+        .map(map_vendor)
         .fetch_optional(&db_pool)
         .await?;
 
     Ok(vendor)
 }
+
+/// Vendors whose email matches one of `emails`, ignoring case.
+pub async fn list_vendors_by_email(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    emails: &[String],
+) -> Result<Vec<VendorDto>> {
+    let vendors = sqlx::query(
+        "SELECT * FROM vendors WHERE lower(email) = ANY(SELECT lower(e) FROM UNNEST($1::text[]) e) AND tenant_id = $2 ORDER BY id",
+    )
+    .bind(emails)
+    .bind(tenant.as_str())
+    .map(map_vendor)
+    .fetch_all(&db_pool)
+    .await?;
+
+    Ok(vendors)
+}
+
+pub async fn list_vendors_by_ids(
+    db_pool: PgPool,
+    tenant: &TenantId,
+    ids: &[i32],
+) -> Result<Vec<VendorDto>> {
+    let vendors =
+        sqlx::query("SELECT * FROM vendors WHERE id = ANY($1) AND tenant_id = $2 ORDER BY id")
+            .bind(ids)
+            .bind(tenant.as_str())
+            .map(map_vendor)
+            .fetch_all(&db_pool)
+            .await?;
+
+    Ok(vendors)
+}
+
+fn map_vendor(row: PgRow) -> VendorDto {
+    let line2: Option<String> = row.get("address_line2");
+
+    VendorDto {
+        id: row.get("id"),
+        name: row.get("name"),
+        address: AddressDto {
+            lines: std::iter::once(row.get("address_line1"))
+                .chain(line2)
+                .collect(),
+            city: row.get("city"),
+            region: row.get("region"),
+            postcode: row.get("postcode"),
+            country_code: row.get("country_code"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+        },
+        email: row.get("email"),
+        contact_number: row.get("contact_number"),
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub mod memory_store;
+pub mod postgres_store;
+
+use memory_store::MemoryStore;
+use postgres_store::PostgresStore;
+
+/// Where the requests of each rate limit window are counted.
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Counts a request of `principal` in the window starting at `window_start` and returns the
+    /// requests counted in that window so far, this one included.
+    async fn hit(&self, principal: &str, window_start: DateTime<Utc>) -> Result<u32>;
+}
+
+/// Counts in this process, or in Postgres when `shared` so the limits hold across instances.
+pub fn store(db_pool: PgPool, shared: bool) -> Arc<dyn RateLimitStore> {
+    match shared {
+        true => Arc::new(PostgresStore::new(db_pool)),
+        false => Arc::new(MemoryStore::default()),
+    }
+}
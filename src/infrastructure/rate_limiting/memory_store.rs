@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::RateLimitStore;
+
+/// The requests of every principal seen in the current window. Windows are aligned to the epoch,
+/// so they all start together and the counts of the last one are dropped when the next starts.
+#[derive(Default)]
+pub struct MemoryStore {
+    window: Mutex<Window>,
+}
+
+#[derive(Default)]
+struct Window {
+    start: Option<DateTime<Utc>>,
+    requests: HashMap<String, u32>,
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryStore {
+    async fn hit(&self, principal: &str, window_start: DateTime<Utc>) -> Result<u32> {
+        let mut window = self.window.lock().unwrap();
+
+        // A request that started just before the window changed is counted in the new one.
+        if window.start.is_none_or(|start| start < window_start) {
+            window.start = Some(window_start);
+            window.requests.clear();
+        }
+
+        let requests = window.requests.entry(principal.to_string()).or_insert(0);
+        *requests += 1;
+
+        Ok(*requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn forgets_the_principals_of_earlier_windows() {
+        let store = MemoryStore::default();
+        let first = DateTime::from_timestamp(3600, 0).unwrap();
+        let second = first + TimeDelta::hours(1);
+
+        assert_eq!(store.hit("alice", first).await.unwrap(), 1);
+        assert_eq!(store.hit("alice", first).await.unwrap(), 2);
+        assert_eq!(store.hit("bob", first).await.unwrap(), 1);
+
+        assert_eq!(store.hit("alice", second).await.unwrap(), 1);
+        assert_eq!(store.window.lock().unwrap().requests.len(), 1);
+
+        assert_eq!(store.hit("bob", first).await.unwrap(), 1);
+    }
+}
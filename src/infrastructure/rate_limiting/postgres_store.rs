@@ -0,0 +1,53 @@
+use anyhow::Result;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use super::RateLimitStore;
+
+/// Counts in `rate_limit_windows`, so every instance sees the requests of the others.
+pub struct PostgresStore {
+    pg_pool: PgPool,
+}
+
+impl PostgresStore {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for PostgresStore {
+    async fn hit(&self, principal: &str, window_start: DateTime<Utc>) -> Result<u32> {
+        let requests: i32 = sqlx::query_scalar(
+            r#"
+INSERT INTO rate_limit_windows (principal, window_start, requests)
+VALUES ($1, $2, 1)
+ON CONFLICT (principal, window_start) DO UPDATE
+SET requests = rate_limit_windows.requests + 1
+RETURNING requests
+            "#,
+        )
+        .bind(principal)
+        .bind(window_start)
+        .fetch_one(&self.pg_pool)
+        .await?;
+
+        // The first request of a window clears the principal's earlier ones, so the table holds
+        // about one row per principal.
+        if requests == 1 {
+            sqlx::query(
+                r#"
+DELETE FROM rate_limit_windows
+WHERE principal = $1 AND window_start < $2
+                "#,
+            )
+            .bind(principal)
+            .bind(window_start)
+            .execute(&self.pg_pool)
+            .await?;
+        }
+
+        Ok(requests as u32)
+    }
+}
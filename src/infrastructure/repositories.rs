@@ -1,2 +1,15 @@
+pub mod contact_repository;
 pub mod customer_repository;
+pub mod import_repository;
+pub mod invoice_repository;
+pub mod item_repository;
+pub mod order_repository;
+pub mod proof_of_delivery_repository;
+pub mod rate_card_repository;
+pub mod route_plan_repository;
+pub mod settlement_repository;
+pub mod user_repository;
+pub mod vehicle_position_repository;
+pub mod vehicle_repository;
+pub mod vehicle_route_repository;
 pub mod vendor_repository;
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use axum::async_trait;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
+
+use crate::domain::aggregates::contact::{Contact, ContactOwner};
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The contacts of one tenant.
+pub struct ContactRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl ContactRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, owner: ContactOwner, id: i32) -> Result<Option<Contact>>;
+    /// Saving a primary contact demotes the previous primary with the same role.
+    async fn create<'a, 'b>(&'a self, contact: &'b Contact) -> Result<i32>;
+    async fn update<'a, 'b>(&'a self, contact: &'b Contact) -> Result<bool>;
+    async fn delete(&self, owner: ContactOwner, id: i32) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for ContactRepository {
+    async fn by_id(&self, owner: ContactOwner, id: i32) -> Result<Option<Contact>> {
+        let contact_db = sqlx::query!(
+            r#"
+        SELECT id, customer_id, vendor_id, name, role, email, phone, preferred_channel, is_primary
+        FROM contacts
+        WHERE id = $1 AND tenant_id = $4
+            AND customer_id IS NOT DISTINCT FROM $2 AND vendor_id IS NOT DISTINCT FROM $3
+            "#,
+            id,
+            owner.customer_id(),
+            owner.vendor_id(),
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        let Some(contact_db) = contact_db else {
+            return Ok(None);
+        };
+
+        let owner = match (contact_db.customer_id, contact_db.vendor_id) {
+            (Some(customer_id), None) => ContactOwner::Customer(customer_id),
+            (None, Some(vendor_id)) => ContactOwner::Vendor(vendor_id),
+            _ => return Err(anyhow!("Contact {} has no single owner.", contact_db.id)),
+        };
+
+        let mut contact = Contact::new(
+            contact_db.id,
+            owner,
+            &contact_db.name,
+            contact_db.role.parse()?,
+            contact_db.email.as_deref(),
+            contact_db.phone.as_deref(),
+            contact_db.preferred_channel.parse()?,
+        );
+        contact.set_primary(contact_db.is_primary);
+
+        Ok(Some(contact))
+    }
+
+    async fn create<'a, 'b>(&'a self, contact: &'b Contact) -> Result<i32> {
+        if contact.id() != 0 {
+            panic!("Contact id must be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        if contact.is_primary {
+            demote_primary(&mut tx, &self.tenant, contact).await?;
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO contacts (customer_id, vendor_id, name, role, email, phone, preferred_channel, is_primary,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+RETURNING id
+        "#,
+            contact.owner.customer_id(),
+            contact.owner.vendor_id(),
+            contact.name,
+            contact.role.as_str(),
+            contact.email,
+            contact.phone,
+            contact.preferred_channel.as_str(),
+            contact.is_primary,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(record.id)
+    }
+
+    async fn update<'a, 'b>(&'a self, contact: &'b Contact) -> Result<bool> {
+        if contact.id() == 0 {
+            panic!("Contact id cannot be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        if contact.is_primary {
+            demote_primary(&mut tx, &self.tenant, contact).await?;
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE contacts SET name = $1, role = $2, email = $3, phone = $4, preferred_channel = $5,
+    is_primary = $6
+WHERE id = $7 AND tenant_id = $10
+    AND customer_id IS NOT DISTINCT FROM $8 AND vendor_id IS NOT DISTINCT FROM $9
+        "#,
+            contact.name,
+            contact.role.as_str(),
+            contact.email,
+            contact.phone,
+            contact.preferred_channel.as_str(),
+            contact.is_primary,
+            contact.id,
+            contact.owner.customer_id(),
+            contact.owner.vendor_id(),
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn delete(&self, owner: ContactOwner, id: i32) -> Result<bool> {
+        let rows_affected = sqlx::query!(
+            r#"
+DELETE FROM contacts
+WHERE id = $1 AND tenant_id = $4
+    AND customer_id IS NOT DISTINCT FROM $2 AND vendor_id IS NOT DISTINCT FROM $3
+        "#,
+            id,
+            owner.customer_id(),
+            owner.vendor_id(),
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+}
+
+async fn demote_primary(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant: &TenantId,
+    contact: &Contact,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+UPDATE contacts SET is_primary = FALSE
+WHERE is_primary AND role = $1 AND id <> $2 AND tenant_id = $5
+    AND customer_id IS NOT DISTINCT FROM $3 AND vendor_id IS NOT DISTINCT FROM $4
+        "#,
+        contact.role.as_str(),
+        contact.id,
+        contact.owner.customer_id(),
+        contact.owner.vendor_id(),
+        tenant.as_str()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
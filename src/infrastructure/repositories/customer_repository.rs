@@ -1,18 +1,25 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
+use crate::domain::aggregates::contact::{ContactChannel, ContactRole};
 use crate::domain::aggregates::customer::Customer;
-use anyhow::Result;
+use crate::domain::value_objects::{
+    address::Address, coordinates::Coordinates, tenant_id::TenantId,
+};
+use anyhow::{anyhow, Result};
 use axum::async_trait;
-use sqlx::postgres::PgPool;
+use sqlx::postgres::{PgConnection, PgExecutor, PgPool};
 
+/// The customers of one tenant.
 pub struct CustomerRepository {
     pg_pool: Arc<PgPool>,
+    tenant: TenantId,
 }
 
 impl CustomerRepository {
-    pub fn new(pg_pool: PgPool) -> Self {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
         Self {
             pg_pool: Arc::new(pg_pool),
+            tenant,
         }
     }
 }
@@ -22,6 +29,20 @@ pub trait Repository {
     async fn by_id(&self, id: i32) -> Result<Customer>;
     async fn create<'a, 'b>(&'a self, customer: &'b Customer) -> Result<i32>;
     async fn update<'a, 'b>(&'a self, customer: &'b Customer) -> Result<bool>;
+    /// Ids of customers still without coordinates, in id order after `after_id`.
+    async fn ids_without_coordinates(&self, after_id: i32, limit: i64) -> Result<Vec<i32>>;
+    /// Stores the coordinates only, provided the address has not been edited or located since it
+    /// was read.
+    async fn update_coordinates<'a, 'b>(&'a self, customer: &'b Customer) -> Result<bool>;
+    /// Creates the customers with id 0 and updates the others, all or none. Returns the ids in order.
+    async fn import<'a, 'b>(&'a self, customers: &'b [Customer]) -> Result<Vec<i32>>;
+    /// Imports customers matched to the existing ones by email beforehand. Returns `None` when the
+    /// match no longer holds: a row to create now shares its email with a customer, or a row to
+    /// update is no longer the only customer with its email.
+    async fn import_by_email<'a, 'b>(
+        &'a self,
+        customers: &'b [Customer],
+    ) -> Result<Option<Vec<i32>>>;
 }
 
 #[async_trait]
@@ -29,11 +50,13 @@ impl Repository for CustomerRepository {
     async fn by_id(&self, id: i32) -> Result<Customer> {
         let customer_db = sqlx::query!(
             r#"
-        SELECT id, name, email, address, contact_number
+        SELECT id, name, email, contact_number,
+            address_line1, address_line2, city, region, postcode, country_code, latitude, longitude
         FROM customers
-        WHERE id = $1
+        WHERE id = $1 AND tenant_id = $2
             "#,
-            id
+            id,
+            self.tenant.as_str()
         )
         .fetch_one(&*self.pg_pool)
         .await?;
@@ -42,7 +65,20 @@ impl Repository for CustomerRepository {
             customer_db.id,
             customer_db.name.as_str(),
             customer_db.email.as_str(),
-            customer_db.address.as_str(),
+            Address::restore(
+                &[
+                    customer_db.address_line1.as_str(),
+                    customer_db.address_line2.as_deref().unwrap_or_default(),
+                ],
+                &customer_db.city,
+                customer_db.region.as_deref(),
+                &customer_db.postcode,
+                &customer_db.country_code,
+                customer_db
+                    .latitude
+                    .zip(customer_db.longitude)
+                    .map(|(latitude, longitude)| Coordinates::new(latitude, longitude)),
+            ),
             customer_db.contact_number.as_deref(),
         ))
     }
@@ -53,39 +89,77 @@ impl Repository for CustomerRepository {
             _ => (),
         }
 
-        let record = sqlx::query!(
-            r#"
-INSERT INTO customers (name, email, address, contact_number)
-VALUES ($1, $2, $3, $4)
-RETURNING id
-        "#,
-            customer.name,
-            customer.email,
-            customer.address,
-            customer.contact_number
-        )
-        .fetch_one(&*self.pg_pool)
-        .await?;
+        let mut tx = self.pg_pool.begin().await?;
+
+        lock_emails(&mut tx, &self.tenant).await?;
+
+        let id = insert(&mut tx, &self.tenant, customer).await?;
+
+        tx.commit().await?;
 
-        Ok(record.id)
+        Ok(id)
     }
 
     async fn update<'a, 'b>(&'a self, customer: &'b Customer) -> Result<bool> {
-        match customer.id() {
-            value if value == 0 => panic!("Customer id cannot be 0."),
-            _ => (),
+        if customer.id() == 0 {
+            panic!("Customer id cannot be 0.");
         }
 
+        let mut tx = self.pg_pool.begin().await?;
+
+        lock_emails(&mut tx, &self.tenant).await?;
+
+        let rows_affected = update(&mut *tx, &self.tenant, customer).await?;
+
+        tx.commit().await?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn ids_without_coordinates(&self, after_id: i32, limit: i64) -> Result<Vec<i32>> {
+        let ids = sqlx::query!(
+            r#"
+        SELECT id
+        FROM customers
+        WHERE latitude IS NULL AND id > $1 AND tenant_id = $3
+        ORDER BY id
+        LIMIT $2
+            "#,
+            after_id,
+            limit,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        Ok(ids)
+    }
+
+    async fn update_coordinates<'a, 'b>(&'a self, customer: &'b Customer) -> Result<bool> {
+        let Some(coordinates) = customer.address.coordinates else {
+            panic!("Customer coordinates must be set.");
+        };
+
         let rows_affected = sqlx::query!(
             r#"
-UPDATE customers SET name = $1, email = $2, address = $3, contact_number = $4
-WHERE id = $5
+UPDATE customers SET latitude = $1, longitude = $2
+WHERE id = $3 AND tenant_id = $10 AND latitude IS NULL
+    AND address_line1 = $4 AND address_line2 IS NOT DISTINCT FROM $5 AND city = $6
+    AND region IS NOT DISTINCT FROM $7 AND postcode = $8 AND country_code = $9
         "#,
-            customer.name,
-            customer.email,
-            customer.address,
-            customer.contact_number,
-            customer.id
+            coordinates.latitude,
+            coordinates.longitude,
+            customer.id,
+            customer.address.line1(),
+            customer.address.line2(),
+            customer.address.city,
+            customer.address.region,
+            customer.address.postcode,
+            customer.address.country_code,
+            self.tenant.as_str()
         )
         .execute(&*self.pg_pool)
         .await?
@@ -93,4 +167,176 @@ WHERE id = $5
 
         Ok(rows_affected > 0)
     }
+
+    async fn import<'a, 'b>(&'a self, customers: &'b [Customer]) -> Result<Vec<i32>> {
+        let mut tx = self.pg_pool.begin().await?;
+
+        lock_emails(&mut tx, &self.tenant).await?;
+
+        let ids = save_all(&mut tx, &self.tenant, customers).await?;
+
+        tx.commit().await?;
+
+        Ok(ids)
+    }
+
+    async fn import_by_email<'a, 'b>(
+        &'a self,
+        customers: &'b [Customer],
+    ) -> Result<Option<Vec<i32>>> {
+        let mut tx = self.pg_pool.begin().await?;
+
+        lock_emails(&mut tx, &self.tenant).await?;
+
+        // The rows were matched before the lock was taken.
+        let emails: Vec<String> = customers.iter().map(|c| c.email.to_lowercase()).collect();
+        let mut matches: HashMap<String, Vec<i32>> = HashMap::new();
+        for r in sqlx::query!(
+            r#"
+        SELECT id, lower(email) AS "email!"
+        FROM customers
+        WHERE lower(email) = ANY($1) AND tenant_id = $2
+            "#,
+            &emails,
+            self.tenant.as_str()
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        {
+            matches.entry(r.email).or_default().push(r.id);
+        }
+
+        let changed = customers.iter().zip(&emails).any(|(c, email)| {
+            match (c.id(), matches.get(email).map(Vec::as_slice)) {
+                (0, None) => false,
+                (id, Some([matched])) => id != *matched,
+                _ => true,
+            }
+        });
+
+        if changed {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let ids = save_all(&mut tx, &self.tenant, customers).await?;
+
+        tx.commit().await?;
+
+        Ok(Some(ids))
+    }
+}
+
+async fn insert(conn: &mut PgConnection, tenant: &TenantId, customer: &Customer) -> Result<i32> {
+    let record = sqlx::query!(
+        r#"
+INSERT INTO customers (name, email, contact_number,
+    address_line1, address_line2, city, region, postcode, country_code, latitude, longitude,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+RETURNING id
+        "#,
+        customer.name,
+        customer.email,
+        customer.contact_number,
+        customer.address.line1(),
+        customer.address.line2(),
+        customer.address.city,
+        customer.address.region,
+        customer.address.postcode,
+        customer.address.country_code,
+        customer.address.coordinates.map(|c| c.latitude),
+        customer.address.coordinates.map(|c| c.longitude),
+        tenant.as_str()
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    // Mirrors the contacts migration, the customer's own details are its primary general contact.
+    sqlx::query!(
+        r#"
+INSERT INTO contacts (customer_id, name, role, email, phone, preferred_channel, is_primary,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, TRUE, $7)
+        "#,
+        record.id,
+        customer.name,
+        ContactRole::General.as_str(),
+        customer.email,
+        customer.contact_number,
+        ContactChannel::Email.as_str(),
+        tenant.as_str()
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(record.id)
+}
+
+async fn save_all(
+    conn: &mut PgConnection,
+    tenant: &TenantId,
+    customers: &[Customer],
+) -> Result<Vec<i32>> {
+    let mut ids = Vec::with_capacity(customers.len());
+
+    for customer in customers {
+        let id = match customer.id() {
+            0 => insert(&mut *conn, tenant, customer).await?,
+            id => {
+                if update(&mut *conn, tenant, customer).await? == 0 {
+                    return Err(anyhow!("Customer {} vanished during the import.", id));
+                }
+                id
+            }
+        };
+
+        ids.push(id);
+    }
+
+    Ok(ids)
+}
+
+// Serialises the writes that can give a customer an email, so an import sees every customer saved
+// before it while it matches rows by email and saves them.
+async fn lock_emails(conn: &mut PgConnection, tenant: &TenantId) -> Result<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(format!("customers:{}", tenant.as_str()))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn update(
+    executor: impl PgExecutor<'_>,
+    tenant: &TenantId,
+    customer: &Customer,
+) -> Result<u64> {
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE customers SET name = $1, email = $2, contact_number = $3,
+    address_line1 = $4, address_line2 = $5, city = $6, region = $7, postcode = $8,
+    country_code = $9, latitude = $10, longitude = $11
+WHERE id = $12 AND tenant_id = $13
+        "#,
+        customer.name,
+        customer.email,
+        customer.contact_number,
+        customer.address.line1(),
+        customer.address.line2(),
+        customer.address.city,
+        customer.address.region,
+        customer.address.postcode,
+        customer.address.country_code,
+        customer.address.coordinates.map(|c| c.latitude),
+        customer.address.coordinates.map(|c| c.longitude),
+        customer.id,
+        tenant.as_str()
+    )
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(rows_affected)
 }
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::import::Import;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The import runs of one tenant.
+pub struct ImportRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl ImportRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn create<'a, 'b>(&'a self, import: &'b Import) -> Result<i32>;
+}
+
+#[async_trait]
+impl Repository for ImportRepository {
+    async fn create<'a, 'b>(&'a self, import: &'b Import) -> Result<i32> {
+        match import.id() {
+            value if value != 0 => panic!("Import id must be 0."),
+            _ => (),
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO imports (entity, imported_by, total_rows, created_count, updated_count,
+    rejected_count, error_file, tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING id
+        "#,
+            import.entity.as_str(),
+            import.imported_by,
+            import.total_rows,
+            import.created,
+            import.updated,
+            import.rejected,
+            import.error_file,
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(record.id)
+    }
+}
@@ -0,0 +1,481 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgPool, PgExecutor, Postgres, Transaction};
+
+use crate::domain::aggregates::{
+    invoice::{Invoice, InvoiceLine, InvoiceStatus, InvoiceType},
+    order::OrderStatus,
+};
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The invoices and credit notes of one tenant.
+pub struct InvoiceRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl InvoiceRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<Invoice>>;
+    /// Lines of the customer's delivered orders that are not on an invoice yet, optionally
+    /// narrowed down to the given orders.
+    async fn uninvoiced_lines(
+        &self,
+        customer_id: i32,
+        order_ids: Option<Vec<i32>>,
+    ) -> Result<Vec<InvoiceLine>>;
+    /// Saves a draft invoice. Returns `None` when one of its orders has been invoiced meanwhile.
+    async fn create<'a, 'b>(&'a self, invoice: &'b Invoice) -> Result<Option<i32>>;
+    /// Numbers and issues a draft, returns `None` when it was no longer a draft.
+    async fn issue<'a, 'b>(
+        &'a self,
+        invoice: &'b Invoice,
+        issued_at: DateTime<Utc>,
+    ) -> Result<Option<String>>;
+    async fn void<'a, 'b>(&'a self, invoice: &'b Invoice) -> Result<bool>;
+    async fn has_credit_notes(&self, invoice_id: i32) -> Result<bool>;
+    /// Quantities already given back per invoice line by credit notes that are not void.
+    async fn credited_quantities(&self, invoice_id: i32) -> Result<HashMap<i32, i32>>;
+    /// Saves and issues a credit note. Returns `None` when the credited invoice is no longer
+    /// issued or a line would be credited more than was invoiced.
+    async fn create_credit_note<'a, 'b>(
+        &'a self,
+        credit_note: &'b Invoice,
+        issued_at: DateTime<Utc>,
+    ) -> Result<Option<i32>>;
+}
+
+#[async_trait]
+impl Repository for InvoiceRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<Invoice>> {
+        let invoice_db = sqlx::query!(
+            r#"
+        SELECT id, customer_id, invoice_type, invoice_status, invoice_number, credited_invoice_id,
+            reason, issued_at, voided_at, void_reason
+        FROM invoices
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        let Some(invoice_db) = invoice_db else {
+            return Ok(None);
+        };
+
+        let lines = sqlx::query!(
+            r#"
+        SELECT id, order_id, item_id, credited_line_id, description, quantity, unit_price
+        FROM invoice_lines
+        WHERE invoice_id = $1 AND tenant_id = $2
+        ORDER BY id
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|l| InvoiceLine {
+            id: l.id,
+            order_id: l.order_id,
+            item_id: l.item_id,
+            credited_line_id: l.credited_line_id,
+            description: l.description,
+            quantity: l.quantity,
+            unit_price: l.unit_price,
+        })
+        .collect();
+
+        Ok(Some(Invoice::new(
+            invoice_db.id,
+            invoice_db.customer_id,
+            invoice_db.invoice_type.parse()?,
+            invoice_db.invoice_status.parse()?,
+            invoice_db.invoice_number.as_deref(),
+            invoice_db.credited_invoice_id,
+            invoice_db.reason.as_deref(),
+            invoice_db.issued_at,
+            invoice_db.voided_at,
+            invoice_db.void_reason.as_deref(),
+            lines,
+        )))
+    }
+
+    async fn uninvoiced_lines(
+        &self,
+        customer_id: i32,
+        order_ids: Option<Vec<i32>>,
+    ) -> Result<Vec<InvoiceLine>> {
+        let lines = sqlx::query!(
+            r#"
+        SELECT oi.order_id, oi.item_id, i.name, oi.quantity, oi.unit_price
+        FROM order_items oi
+        JOIN orders o ON o.id = oi.order_id
+        JOIN items i ON i.id = oi.item_id
+        WHERE o.customer_id = $1 AND o.order_status = $2 AND o.tenant_id = $5
+            AND ($3::int[] IS NULL OR o.id = ANY($3))
+            AND NOT EXISTS (
+                SELECT 1 FROM invoice_lines il
+                JOIN invoices inv ON inv.id = il.invoice_id
+                WHERE il.order_id = o.id AND inv.invoice_status <> $4
+            )
+        ORDER BY oi.order_id, oi.item_id
+            "#,
+            customer_id,
+            OrderStatus::Delivered.as_str(),
+            order_ids as _,
+            InvoiceStatus::Void.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|l| InvoiceLine {
+            id: 0,
+            order_id: Some(l.order_id),
+            item_id: Some(l.item_id),
+            credited_line_id: None,
+            description: format!("{} (order {})", l.name, l.order_id),
+            quantity: l.quantity,
+            unit_price: l.unit_price,
+        })
+        .collect();
+
+        Ok(lines)
+    }
+
+    async fn create<'a, 'b>(&'a self, invoice: &'b Invoice) -> Result<Option<i32>> {
+        if invoice.id() != 0 {
+            panic!("Invoice id must be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        // Serialises invoice generation per customer, so an order cannot land on two drafts.
+        sqlx::query!(
+            "SELECT id FROM customers WHERE id = $1 AND tenant_id = $2 FOR UPDATE",
+            invoice.customer_id,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let already_invoiced = sqlx::query!(
+            r#"
+        SELECT EXISTS (
+            SELECT 1 FROM invoice_lines il
+            JOIN invoices inv ON inv.id = il.invoice_id
+            WHERE il.order_id = ANY($1) AND inv.invoice_status <> $2 AND inv.tenant_id = $3
+        ) AS "already_invoiced!"
+            "#,
+            &invoice.order_ids(),
+            InvoiceStatus::Void.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .already_invoiced;
+
+        if already_invoiced {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let id = insert_invoice(&mut tx, &self.tenant, invoice, None, None).await?;
+
+        tx.commit().await?;
+
+        Ok(Some(id))
+    }
+
+    async fn issue<'a, 'b>(
+        &'a self,
+        invoice: &'b Invoice,
+        issued_at: DateTime<Utc>,
+    ) -> Result<Option<String>> {
+        if invoice.id() == 0 {
+            panic!("Invoice id cannot be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let invoice_number = next_number(&mut tx, &self.tenant, invoice.invoice_type).await?;
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE invoices SET invoice_status = $1, invoice_number = $2, issued_at = $3
+WHERE id = $4 AND invoice_status = $5 AND tenant_id = $6
+        "#,
+            InvoiceStatus::Issued.as_str(),
+            invoice_number,
+            issued_at,
+            invoice.id,
+            InvoiceStatus::Draft.as_str(),
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // Rolling back hands the number back to the sequence.
+        if rows_affected == 0 {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(invoice_number))
+    }
+
+    async fn void<'a, 'b>(&'a self, invoice: &'b Invoice) -> Result<bool> {
+        if invoice.id() == 0 {
+            panic!("Invoice id cannot be 0.");
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE invoices SET invoice_status = $1, voided_at = $2, void_reason = $3
+WHERE id = $4 AND invoice_status <> $1 AND tenant_id = $5
+    AND NOT EXISTS (
+        SELECT 1 FROM invoices cn WHERE cn.credited_invoice_id = $4 AND cn.invoice_status <> $1
+    )
+        "#,
+            invoice.invoice_status.as_str(),
+            invoice.voided_at,
+            invoice.void_reason,
+            invoice.id,
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn has_credit_notes(&self, invoice_id: i32) -> Result<bool> {
+        let record = sqlx::query!(
+            r#"
+        SELECT EXISTS (
+            SELECT 1 FROM invoices
+            WHERE credited_invoice_id = $1 AND invoice_status <> $2 AND tenant_id = $3
+        ) AS "has_credit_notes!"
+            "#,
+            invoice_id,
+            InvoiceStatus::Void.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(record.has_credit_notes)
+    }
+
+    async fn credited_quantities(&self, invoice_id: i32) -> Result<HashMap<i32, i32>> {
+        credited_quantities(&*self.pg_pool, &self.tenant, invoice_id).await
+    }
+
+    async fn create_credit_note<'a, 'b>(
+        &'a self,
+        credit_note: &'b Invoice,
+        issued_at: DateTime<Utc>,
+    ) -> Result<Option<i32>> {
+        if credit_note.id() != 0 {
+            panic!("Credit note id must be 0.");
+        }
+        let Some(credited_invoice_id) = credit_note.credited_invoice_id else {
+            panic!("Credit note must point at an invoice.");
+        };
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        // Locks the invoice so concurrent credit notes see each other's quantities.
+        let credited_invoice = sqlx::query!(
+            "SELECT invoice_status FROM invoices WHERE id = $1 AND tenant_id = $2 FOR UPDATE",
+            credited_invoice_id,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if credited_invoice.invoice_status != InvoiceStatus::Issued.as_str() {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let invoiced: HashMap<i32, i32> = sqlx::query!(
+            "SELECT id, quantity FROM invoice_lines WHERE invoice_id = $1 AND tenant_id = $2",
+            credited_invoice_id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|l| (l.id, l.quantity))
+        .collect();
+
+        let credited = credited_quantities(&mut *tx, &self.tenant, credited_invoice_id).await?;
+
+        // Summed per line, in case a line is credited in parts on the one note.
+        let mut crediting: HashMap<i32, i32> = HashMap::new();
+        for l in &credit_note.lines {
+            *crediting
+                .entry(l.credited_line_id.unwrap_or_default())
+                .or_default() += l.quantity;
+        }
+
+        let over_credited = crediting.iter().any(|(line_id, quantity)| {
+            let remaining = invoiced.get(line_id).copied().unwrap_or_default()
+                - credited.get(line_id).copied().unwrap_or_default();
+
+            *quantity > remaining
+        });
+
+        if over_credited {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let invoice_number = next_number(&mut tx, &self.tenant, credit_note.invoice_type).await?;
+
+        let id = insert_invoice(
+            &mut tx,
+            &self.tenant,
+            credit_note,
+            Some(&invoice_number),
+            Some(issued_at),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(id))
+    }
+}
+
+async fn next_number(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant: &TenantId,
+    invoice_type: InvoiceType,
+) -> Result<String> {
+    // The row lock taken here is held until the transaction ends, which keeps numbers in order.
+    let counter = sqlx::query!(
+        r#"
+INSERT INTO invoice_counters (tenant_id, invoice_type, last_number)
+VALUES ($1, $2, 1)
+ON CONFLICT (tenant_id, invoice_type)
+DO UPDATE SET last_number = invoice_counters.last_number + 1
+RETURNING last_number
+        "#,
+        tenant.as_str(),
+        invoice_type.as_str()
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let sequence = sqlx::query!(
+        "SELECT prefix FROM invoice_sequences WHERE invoice_type = $1",
+        invoice_type.as_str()
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(format!("{}-{:06}", sequence.prefix, counter.last_number))
+}
+
+async fn credited_quantities(
+    executor: impl PgExecutor<'_>,
+    tenant: &TenantId,
+    invoice_id: i32,
+) -> Result<HashMap<i32, i32>> {
+    let quantities = sqlx::query!(
+        r#"
+    SELECT cl.credited_line_id AS "credited_line_id!", SUM(cl.quantity)::int AS "quantity!"
+    FROM invoice_lines cl
+    JOIN invoices cn ON cn.id = cl.invoice_id
+    WHERE cn.credited_invoice_id = $1 AND cn.invoice_status <> $2 AND cn.tenant_id = $3
+        AND cl.credited_line_id IS NOT NULL
+    GROUP BY cl.credited_line_id
+        "#,
+        invoice_id,
+        InvoiceStatus::Void.as_str(),
+        tenant.as_str()
+    )
+    .fetch_all(executor)
+    .await?
+    .into_iter()
+    .map(|r| (r.credited_line_id, r.quantity))
+    .collect();
+
+    Ok(quantities)
+}
+
+async fn insert_invoice(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant: &TenantId,
+    invoice: &Invoice,
+    invoice_number: Option<&str>,
+    issued_at: Option<DateTime<Utc>>,
+) -> Result<i32> {
+    let invoice_status = match invoice_number {
+        Some(_) => InvoiceStatus::Issued,
+        None => InvoiceStatus::Draft,
+    };
+
+    let record = sqlx::query!(
+        r#"
+INSERT INTO invoices (customer_id, invoice_type, invoice_status, invoice_number,
+    credited_invoice_id, reason, issued_at, tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING id
+        "#,
+        invoice.customer_id,
+        invoice.invoice_type.as_str(),
+        invoice_status.as_str(),
+        invoice_number,
+        invoice.credited_invoice_id,
+        invoice.reason,
+        issued_at,
+        tenant.as_str()
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    for line in &invoice.lines {
+        sqlx::query!(
+            r#"
+INSERT INTO invoice_lines (invoice_id, order_id, item_id, credited_line_id, description,
+    quantity, unit_price, tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            record.id,
+            line.order_id,
+            line.item_id,
+            line.credited_line_id,
+            line.description,
+            line.quantity,
+            line.unit_price,
+            tenant.as_str()
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(record.id)
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use axum::async_trait;
+use sqlx::postgres::{PgConnection, PgPool};
+
+use crate::domain::aggregates::item::Item;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The item catalogue of one tenant.
+pub struct ItemRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl ItemRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Item>;
+    /// Items whose name matches one of `names`, ignoring case.
+    async fn by_names<'a, 'b>(&'a self, names: &'b [String]) -> Result<Vec<Item>>;
+    async fn create<'a, 'b>(&'a self, item: &'b Item) -> Result<i32>;
+    async fn update<'a, 'b>(&'a self, item: &'b Item) -> Result<bool>;
+    /// Creates the items with id 0 and updates the others, all or none. Returns the ids in order.
+    async fn import<'a, 'b>(&'a self, items: &'b [Item]) -> Result<Vec<i32>>;
+}
+
+#[async_trait]
+impl Repository for ItemRepository {
+    async fn by_id(&self, id: i32) -> Result<Item> {
+        let item = sqlx::query!(
+            r#"
+        SELECT id, name, description, quantity_available, unit_price
+        FROM items
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(Item::new(
+            item.id,
+            &item.name,
+            item.description.as_deref(),
+            item.quantity_available,
+            item.unit_price,
+        ))
+    }
+
+    async fn by_names<'a, 'b>(&'a self, names: &'b [String]) -> Result<Vec<Item>> {
+        let items = sqlx::query!(
+            r#"
+        SELECT id, name, description, quantity_available, unit_price
+        FROM items
+        WHERE lower(name) = ANY(SELECT lower(n) FROM UNNEST($1::text[]) n) AND tenant_id = $2
+        ORDER BY id
+            "#,
+            names,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|i| {
+            Item::new(
+                i.id,
+                &i.name,
+                i.description.as_deref(),
+                i.quantity_available,
+                i.unit_price,
+            )
+        })
+        .collect();
+
+        Ok(items)
+    }
+
+    async fn create<'a, 'b>(&'a self, item: &'b Item) -> Result<i32> {
+        if item.id() != 0 {
+            panic!("Item id must be 0.");
+        }
+
+        let mut conn = self.pg_pool.acquire().await?;
+
+        insert(&mut conn, &self.tenant, item).await
+    }
+
+    async fn update<'a, 'b>(&'a self, item: &'b Item) -> Result<bool> {
+        let mut conn = self.pg_pool.acquire().await?;
+
+        update(&mut conn, &self.tenant, item).await
+    }
+
+    async fn import<'a, 'b>(&'a self, items: &'b [Item]) -> Result<Vec<i32>> {
+        let mut tx = self.pg_pool.begin().await?;
+
+        let mut ids = Vec::with_capacity(items.len());
+
+        for item in items {
+            let id = match item.id() {
+                0 => insert(&mut tx, &self.tenant, item).await?,
+                id => {
+                    if !update(&mut tx, &self.tenant, item).await? {
+                        return Err(anyhow!("Item {} vanished during the import.", id));
+                    }
+                    id
+                }
+            };
+
+            ids.push(id);
+        }
+
+        tx.commit().await?;
+
+        Ok(ids)
+    }
+}
+
+async fn insert(conn: &mut PgConnection, tenant: &TenantId, item: &Item) -> Result<i32> {
+    let record = sqlx::query!(
+        r#"
+INSERT INTO items (name, description, quantity_available, unit_price, tenant_id)
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id
+        "#,
+        item.name,
+        item.description,
+        item.quantity_available,
+        item.unit_price,
+        tenant.as_str()
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(record.id)
+}
+
+async fn update(conn: &mut PgConnection, tenant: &TenantId, item: &Item) -> Result<bool> {
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE items SET name = $1, description = $2, quantity_available = $3, unit_price = $4
+WHERE id = $5 AND tenant_id = $6
+        "#,
+        item.name,
+        item.description,
+        item.quantity_available,
+        item.unit_price,
+        item.id,
+        tenant.as_str()
+    )
+    .execute(conn)
+    .await?
+    .rows_affected();
+
+    Ok(rows_affected > 0)
+}
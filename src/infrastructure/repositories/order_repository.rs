@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::order::{Order, OrderLine, OrderStatus, OrderWindows};
+use crate::domain::value_objects::{tenant_id::TenantId, time_window::TimeWindow};
+
+/// The orders of one tenant.
+pub struct OrderRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl OrderRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<Order>>;
+    async fn by_ids<'a, 'b>(&'a self, ids: &'b [i32]) -> Result<Vec<Order>>;
+    /// Saves a placed order with its lines.
+    async fn create<'a, 'b>(&'a self, order: &'b Order, lines: &'b [OrderLine]) -> Result<i32>;
+    /// Saves the status of the order, returns `false` when it does not exist.
+    async fn update_status<'a, 'b>(&'a self, order: &'b Order) -> Result<bool>;
+    /// Saves the pickup and delivery windows of the order, returns `false` when it no longer
+    /// exists or has left meanwhile.
+    async fn schedule<'a, 'b>(&'a self, order: &'b Order) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for OrderRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<Order>> {
+        let order_db = sqlx::query!(
+            r#"
+        SELECT id, customer_id, order_status, time_zone, pickup_earliest, pickup_latest,
+            delivery_earliest, delivery_latest
+        FROM orders
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        match order_db {
+            Some(o) => Ok(Some(Order::new(
+                o.id,
+                o.customer_id,
+                o.order_status.parse()?,
+                order_windows(
+                    o.time_zone,
+                    window(o.pickup_earliest, o.pickup_latest),
+                    window(o.delivery_earliest, o.delivery_latest),
+                )?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    async fn by_ids<'a, 'b>(&'a self, ids: &'b [i32]) -> Result<Vec<Order>> {
+        let orders_db = sqlx::query!(
+            r#"
+        SELECT id, customer_id, order_status, time_zone, pickup_earliest, pickup_latest,
+            delivery_earliest, delivery_latest
+        FROM orders
+        WHERE id = ANY($1) AND tenant_id = $2
+        ORDER BY id
+            "#,
+            ids,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?;
+
+        orders_db
+            .into_iter()
+            .map(|o| {
+                Ok(Order::new(
+                    o.id,
+                    o.customer_id,
+                    o.order_status.parse()?,
+                    order_windows(
+                        o.time_zone,
+                        window(o.pickup_earliest, o.pickup_latest),
+                        window(o.delivery_earliest, o.delivery_latest),
+                    )?,
+                ))
+            })
+            .collect()
+    }
+
+    async fn create<'a, 'b>(&'a self, order: &'b Order, lines: &'b [OrderLine]) -> Result<i32> {
+        if order.id() != 0 {
+            panic!("Order id must be 0.");
+        }
+        if lines.is_empty() {
+            panic!("An order needs at least one line.");
+        }
+
+        let windows = order.windows.as_ref();
+        let (pickup, delivery) = (
+            windows.and_then(|w| w.pickup),
+            windows.and_then(|w| w.delivery),
+        );
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO orders (customer_id, order_status, time_zone, pickup_earliest, pickup_latest,
+    delivery_earliest, delivery_latest, tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING id
+        "#,
+            order.customer_id,
+            order.order_status.as_str(),
+            windows.map(|w| w.time_zone.name()),
+            pickup.map(|w| w.earliest),
+            pickup.map(|w| w.latest),
+            delivery.map(|w| w.earliest),
+            delivery.map(|w| w.latest),
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for line in lines {
+            sqlx::query!(
+                r#"
+INSERT INTO order_items (order_id, item_id, quantity, unit_price, tenant_id)
+VALUES ($1, $2, $3, $4, $5)
+            "#,
+                record.id,
+                line.item_id,
+                line.quantity,
+                line.unit_price,
+                self.tenant.as_str()
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(record.id)
+    }
+
+    async fn update_status<'a, 'b>(&'a self, order: &'b Order) -> Result<bool> {
+        if order.id() == 0 {
+            panic!("Order id cannot be 0.");
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE orders SET order_status = $1
+WHERE id = $2 AND tenant_id = $3
+        "#,
+            order.order_status.as_str(),
+            order.id,
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn schedule<'a, 'b>(&'a self, order: &'b Order) -> Result<bool> {
+        if order.id() == 0 {
+            panic!("Order id cannot be 0.");
+        }
+
+        let windows = order.windows.as_ref();
+        let (pickup, delivery) = (
+            windows.and_then(|w| w.pickup),
+            windows.and_then(|w| w.delivery),
+        );
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE orders SET time_zone = $1, pickup_earliest = $2, pickup_latest = $3,
+    delivery_earliest = $4, delivery_latest = $5
+WHERE id = $6 AND order_status = ANY($7) AND tenant_id = $8
+        "#,
+            windows.map(|w| w.time_zone.name()),
+            pickup.map(|w| w.earliest),
+            pickup.map(|w| w.latest),
+            delivery.map(|w| w.earliest),
+            delivery.map(|w| w.latest),
+            order.id,
+            &[
+                OrderStatus::Pending.as_str().to_string(),
+                OrderStatus::Confirmed.as_str().to_string()
+            ],
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+}
+
+fn window(earliest: Option<DateTime<Utc>>, latest: Option<DateTime<Utc>>) -> Option<TimeWindow> {
+    Some(TimeWindow {
+        earliest: earliest?,
+        latest: latest?,
+    })
+}
+
+fn order_windows(
+    time_zone: Option<String>,
+    pickup: Option<TimeWindow>,
+    delivery: Option<TimeWindow>,
+) -> Result<Option<OrderWindows>> {
+    let Some(time_zone) = time_zone else {
+        return Ok(None);
+    };
+
+    Ok(Some(OrderWindows {
+        time_zone: time_zone
+            .parse::<Tz>()
+            .map_err(|e| anyhow!("Order time zone '{}': {}", time_zone, e))?,
+        pickup,
+        delivery,
+    }))
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::{
+    order::{Order, OrderStatus},
+    proof_of_delivery::ProofOfDelivery,
+};
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The proofs of delivery of one tenant.
+pub struct ProofOfDeliveryRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl ProofOfDeliveryRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    /// Stores the proof of delivery and moves the order to delivered in one transaction.
+    /// Returns `None` when the order was no longer in transit.
+    async fn create<'a, 'b>(
+        &'a self,
+        order: &'b Order,
+        proof_of_delivery: &'b ProofOfDelivery,
+    ) -> Result<Option<i32>>;
+}
+
+#[async_trait]
+impl Repository for ProofOfDeliveryRepository {
+    async fn create<'a, 'b>(
+        &'a self,
+        order: &'b Order,
+        proof_of_delivery: &'b ProofOfDelivery,
+    ) -> Result<Option<i32>> {
+        if proof_of_delivery.id() != 0 {
+            panic!("Proof of delivery id must be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        // Guard on the previous status so two drivers cannot deliver the same order.
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE orders SET order_status = $1
+WHERE id = $2 AND order_status = $3 AND tenant_id = $4
+        "#,
+            order.order_status.as_str(),
+            order.id,
+            OrderStatus::InTransit.as_str(),
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO proof_of_deliveries (order_id, recipient_name, delivered_at, latitude, longitude, notes,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+RETURNING id
+        "#,
+            proof_of_delivery.order_id,
+            proof_of_delivery.recipient_name,
+            proof_of_delivery.delivered_at,
+            proof_of_delivery.latitude,
+            proof_of_delivery.longitude,
+            proof_of_delivery.notes,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for file in &proof_of_delivery.files {
+            sqlx::query!(
+                r#"
+INSERT INTO proof_of_delivery_files (proof_of_delivery_id, kind, file_name, content_type, content,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+                record.id,
+                file.kind.as_str(),
+                file.file_name,
+                file.content_type,
+                file.content,
+                self.tenant.as_str()
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(record.id))
+    }
+}
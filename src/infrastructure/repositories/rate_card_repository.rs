@@ -0,0 +1,283 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
+
+use crate::domain::aggregates::rate_card::{Rate, RateCard};
+use crate::domain::value_objects::tenant_id::TenantId;
+
+struct RateCardDb {
+    id: i32,
+    vendor_id: i32,
+    name: String,
+    vehicle_type: String,
+    minimum_charge: Decimal,
+    effective_from: NaiveDate,
+    effective_to: Option<NaiveDate>,
+}
+
+impl From<RateCardDb> for RateCard {
+    fn from(r: RateCardDb) -> Self {
+        RateCard::new(
+            r.id,
+            r.vendor_id,
+            &r.name,
+            &r.vehicle_type,
+            r.minimum_charge,
+            r.effective_from,
+            r.effective_to,
+        )
+    }
+}
+
+/// The rate cards of one tenant's vendors.
+pub struct RateCardRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl RateCardRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+
+    async fn with_rates(&self, mut rate_cards: Vec<RateCard>) -> Result<Vec<RateCard>> {
+        let ids: Vec<i32> = rate_cards.iter().map(|c| c.id()).collect();
+
+        let mut rates: HashMap<i32, Vec<Rate>> = HashMap::new();
+
+        for r in sqlx::query!(
+            r#"
+        SELECT rate_card_id, min_distance, min_weight, base_charge, rate_per_km, rate_per_kg
+        FROM rate_card_rates
+        WHERE rate_card_id = ANY($1) AND tenant_id = $2
+            "#,
+            &ids,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        {
+            rates.entry(r.rate_card_id).or_default().push(Rate {
+                min_distance: r.min_distance,
+                min_weight: r.min_weight,
+                base_charge: r.base_charge,
+                rate_per_km: r.rate_per_km,
+                rate_per_kg: r.rate_per_kg,
+            });
+        }
+
+        for rate_card in rate_cards.iter_mut() {
+            rate_card.set_rates(rates.remove(&rate_card.id()).unwrap_or_default());
+        }
+
+        Ok(rate_cards)
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<RateCard>>;
+    async fn by_vendor(&self, vendor_id: i32) -> Result<Vec<RateCard>>;
+    /// Rate cards of every vendor in effect on `date`.
+    async fn effective_on(&self, date: NaiveDate) -> Result<Vec<RateCard>>;
+    /// Returns `None` when another card for the vehicle type is in effect over the dates.
+    async fn create<'a, 'b>(&'a self, rate_card: &'b RateCard) -> Result<Option<i32>>;
+    /// Saves the card and replaces its whole rate grid. Returns false when the card is gone or
+    /// another card for the vehicle type is in effect over the dates.
+    async fn update<'a, 'b>(&'a self, rate_card: &'b RateCard) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for RateCardRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<RateCard>> {
+        let rate_cards = sqlx::query_as!(
+            RateCardDb,
+            r#"
+        SELECT id, vendor_id, name, vehicle_type, minimum_charge, effective_from, effective_to
+        FROM rate_cards
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(RateCard::from)
+        .collect();
+
+        Ok(self.with_rates(rate_cards).await?.pop())
+    }
+
+    async fn by_vendor(&self, vendor_id: i32) -> Result<Vec<RateCard>> {
+        let rate_cards = sqlx::query_as!(
+            RateCardDb,
+            r#"
+        SELECT id, vendor_id, name, vehicle_type, minimum_charge, effective_from, effective_to
+        FROM rate_cards
+        WHERE vendor_id = $1 AND tenant_id = $2
+        ORDER BY id
+            "#,
+            vendor_id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(RateCard::from)
+        .collect();
+
+        self.with_rates(rate_cards).await
+    }
+
+    async fn effective_on(&self, date: NaiveDate) -> Result<Vec<RateCard>> {
+        let rate_cards = sqlx::query_as!(
+            RateCardDb,
+            r#"
+        SELECT id, vendor_id, name, vehicle_type, minimum_charge, effective_from, effective_to
+        FROM rate_cards
+        WHERE effective_from <= $1 AND (effective_to IS NULL OR effective_to >= $1)
+            AND tenant_id = $2
+        ORDER BY id
+            "#,
+            date,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(RateCard::from)
+        .collect();
+
+        self.with_rates(rate_cards).await
+    }
+
+    async fn create<'a, 'b>(&'a self, rate_card: &'b RateCard) -> Result<Option<i32>> {
+        if rate_card.id() != 0 {
+            panic!("Rate card id must be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let record = match sqlx::query!(
+            r#"
+INSERT INTO rate_cards (vendor_id, name, vehicle_type, minimum_charge, effective_from, effective_to,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+RETURNING id
+        "#,
+            rate_card.vendor_id,
+            rate_card.name,
+            rate_card.vehicle_type,
+            rate_card.minimum_charge,
+            rate_card.effective_from,
+            rate_card.effective_to,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Err(err) if is_overlap(&err) => return Ok(None),
+            record => record?,
+        };
+
+        insert_rates(&mut tx, &self.tenant, record.id, &rate_card.rates).await?;
+
+        tx.commit().await?;
+
+        Ok(Some(record.id))
+    }
+
+    async fn update<'a, 'b>(&'a self, rate_card: &'b RateCard) -> Result<bool> {
+        if rate_card.id() == 0 {
+            panic!("Rate card id cannot be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let rows_affected = match sqlx::query!(
+            r#"
+UPDATE rate_cards SET name = $1, vehicle_type = $2, minimum_charge = $3,
+    effective_from = $4, effective_to = $5
+WHERE id = $6 AND tenant_id = $7
+        "#,
+            rate_card.name,
+            rate_card.vehicle_type,
+            rate_card.minimum_charge,
+            rate_card.effective_from,
+            rate_card.effective_to,
+            rate_card.id,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Err(err) if is_overlap(&err) => return Ok(false),
+            result => result?.rows_affected(),
+        };
+
+        if rows_affected == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "DELETE FROM rate_card_rates WHERE rate_card_id = $1 AND tenant_id = $2",
+            rate_card.id,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        insert_rates(&mut tx, &self.tenant, rate_card.id, &rate_card.rates).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
+
+fn is_overlap(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|e| e.constraint())
+        .is_some_and(|c| c == "rate_cards_no_overlap")
+}
+
+async fn insert_rates(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant: &TenantId,
+    rate_card_id: i32,
+    rates: &[Rate],
+) -> Result<()> {
+    let min_distances: Vec<_> = rates.iter().map(|r| r.min_distance).collect();
+    let min_weights: Vec<_> = rates.iter().map(|r| r.min_weight).collect();
+    let base_charges: Vec<_> = rates.iter().map(|r| r.base_charge).collect();
+    let rates_per_km: Vec<_> = rates.iter().map(|r| r.rate_per_km).collect();
+    let rates_per_kg: Vec<_> = rates.iter().map(|r| r.rate_per_kg).collect();
+
+    sqlx::query!(
+        r#"
+INSERT INTO rate_card_rates (rate_card_id, min_distance, min_weight, base_charge, rate_per_km, rate_per_kg,
+    tenant_id)
+SELECT $1, *, $7 FROM UNNEST($2::numeric[], $3::numeric[], $4::numeric[], $5::numeric[], $6::numeric[])
+        "#,
+        rate_card_id,
+        &min_distances,
+        &min_weights,
+        &base_charges,
+        &rates_per_km,
+        &rates_per_kg,
+        tenant.as_str()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
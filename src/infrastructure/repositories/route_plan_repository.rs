@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::{postgres::PgPool, types::Json};
+
+use crate::domain::{
+    aggregates::{
+        order::OrderStatus,
+        route_plan::{RoutePlan, RoutePlanStatus},
+    },
+    services::route_planner::{to_distance, to_travel_time, Plan},
+    value_objects::{coordinates::Coordinates, tenant_id::TenantId},
+};
+
+/// The route plans of one tenant.
+pub struct RoutePlanRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl RoutePlanRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<RoutePlan>>;
+    async fn create<'a, 'b>(&'a self, route_plan: &'b RoutePlan) -> Result<i32>;
+    /// Writes the planned `routes` and `vehicle_routes` rows, assigns the orders, takes the
+    /// vehicles out of service and marks the plan accepted. Returns `false` and writes nothing when
+    /// the plan had already been accepted, or another plan took one of its orders or vehicles.
+    async fn accept<'a, 'b>(&'a self, route_plan: &'b RoutePlan) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for RoutePlanRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<RoutePlan>> {
+        let route_plan_db = sqlx::query!(
+            r#"
+        SELECT id, plan_status, depot_latitude, depot_longitude, departure_at, plan as "plan: Json<Plan>"
+        FROM route_plans
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        let Some(route_plan_db) = route_plan_db else {
+            return Ok(None);
+        };
+
+        let accepted_routes = sqlx::query!(
+            r#"
+        SELECT vr.vehicle_id, vr.route_id
+        FROM route_plan_routes rpr
+        JOIN vehicle_routes vr ON vr.route_id = rpr.route_id
+        WHERE rpr.route_plan_id = $1 AND rpr.tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|r| (r.vehicle_id, r.route_id))
+        .collect();
+
+        Ok(Some(RoutePlan::new(
+            route_plan_db.id,
+            route_plan_db.plan_status.parse()?,
+            Coordinates::new(route_plan_db.depot_latitude, route_plan_db.depot_longitude),
+            route_plan_db.departure_at,
+            route_plan_db.plan.0,
+            accepted_routes,
+        )))
+    }
+
+    async fn create<'a, 'b>(&'a self, route_plan: &'b RoutePlan) -> Result<i32> {
+        if route_plan.id() != 0 {
+            panic!("Route plan id must be 0.");
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO route_plans (plan_status, depot_latitude, depot_longitude, departure_at, plan,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING id
+        "#,
+            route_plan.plan_status.as_str(),
+            route_plan.depot.latitude,
+            route_plan.depot.longitude,
+            route_plan.departure_at,
+            Json(&route_plan.plan) as _,
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(record.id)
+    }
+
+    async fn accept<'a, 'b>(&'a self, route_plan: &'b RoutePlan) -> Result<bool> {
+        if route_plan.id() == 0 {
+            panic!("Route plan id cannot be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE route_plans SET plan_status = $1
+WHERE id = $2 AND plan_status = $3 AND tenant_id = $4
+        "#,
+            route_plan.plan_status.as_str(),
+            route_plan.id,
+            RoutePlanStatus::Draft.as_str(),
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        // Only confirmed orders and available vehicles can be taken, so a second plan over the same
+        // ones cannot be accepted too.
+        let order_ids = route_plan.order_ids();
+        let orders_assigned = sqlx::query!(
+            r#"
+UPDATE orders SET order_status = $1
+WHERE id = ANY($2) AND order_status = $3 AND tenant_id = $4
+        "#,
+            OrderStatus::Assigned.as_str(),
+            &order_ids,
+            OrderStatus::Confirmed.as_str(),
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let mut vehicle_ids = route_plan.vehicle_ids();
+        vehicle_ids.sort_unstable();
+        vehicle_ids.dedup();
+        let vehicles_claimed = sqlx::query!(
+            r#"
+UPDATE vehicles SET availability_status = FALSE
+WHERE id = ANY($1) AND availability_status AND tenant_id = $2
+        "#,
+            &vehicle_ids,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if orders_assigned < order_ids.len() as u64 || vehicles_claimed < vehicle_ids.len() as u64 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        for planned in &route_plan.plan.routes {
+            let legs = planned.legs();
+
+            // The whole trip is the main route, each leg is a child route pointing at it.
+            let main_route = sqlx::query!(
+                r#"
+INSERT INTO routes (main_route_id, origin, destination, distance, estimated_travel_time,
+    tenant_id)
+VALUES (NULL, $1, $2, $3, $4, $5)
+RETURNING id
+            "#,
+                legs[0].origin,
+                legs[legs.len() - 1].destination,
+                to_distance(planned.distance_km),
+                to_travel_time(planned.duration_seconds()),
+                self.tenant.as_str()
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for leg in &legs {
+                sqlx::query!(
+                    r#"
+INSERT INTO routes (main_route_id, origin, destination, distance, estimated_travel_time,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                    main_route.id,
+                    leg.origin,
+                    leg.destination,
+                    leg.distance(),
+                    leg.estimated_travel_time(),
+                    self.tenant.as_str()
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            sqlx::query!(
+                r#"
+INSERT INTO vehicle_routes (vehicle_id, route_id, load, tenant_id)
+VALUES ($1, $2, $3, $4)
+            "#,
+                planned.vehicle_id,
+                main_route.id,
+                planned.load,
+                self.tenant.as_str()
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+INSERT INTO route_plan_routes (route_plan_id, route_id, tenant_id)
+VALUES ($1, $2, $3)
+            "#,
+                route_plan.id,
+                main_route.id,
+                self.tenant.as_str()
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
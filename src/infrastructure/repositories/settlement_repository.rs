@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::settlement::{
+    CompletedRoute, Settlement, SettlementLine, SettlementStatus,
+};
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The vendor settlements of one tenant.
+pub struct SettlementRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl SettlementRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<Settlement>>;
+    /// Routes the vendor's vehicles completed between the two dates (UTC, inclusive) that are not
+    /// on a draft or approved settlement yet.
+    async fn unsettled_routes(
+        &self,
+        vendor_id: i32,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<Vec<CompletedRoute>>;
+    /// Returns `None` when one of the routes has been settled meanwhile.
+    async fn create<'a, 'b>(&'a self, settlement: &'b Settlement) -> Result<Option<i32>>;
+    /// Saves an approval or rejection, returns `false` when the settlement was no longer a draft.
+    async fn review<'a, 'b>(&'a self, settlement: &'b Settlement) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for SettlementRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<Settlement>> {
+        let settlement_db = sqlx::query!(
+            r#"
+        SELECT id, vendor_id, period_start, period_end, settlement_status,
+            approved_at, approved_by, rejected_at, rejection_reason
+        FROM settlements
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        let Some(settlement_db) = settlement_db else {
+            return Ok(None);
+        };
+
+        let lines = sqlx::query!(
+            r#"
+        SELECT vehicle_id, route_id, rate_card_id, completed_at, distance, load, amount,
+            minimum_charge_applied
+        FROM settlement_lines
+        WHERE settlement_id = $1 AND tenant_id = $2
+        ORDER BY completed_at, vehicle_id, route_id
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|l| SettlementLine {
+            vehicle_id: l.vehicle_id,
+            route_id: l.route_id,
+            rate_card_id: l.rate_card_id,
+            completed_at: l.completed_at,
+            distance: l.distance,
+            load: l.load,
+            amount: l.amount,
+            minimum_charge_applied: l.minimum_charge_applied,
+        })
+        .collect();
+
+        let mut settlement = Settlement::new(
+            settlement_db.id,
+            settlement_db.vendor_id,
+            settlement_db.period_start,
+            settlement_db.period_end,
+            settlement_db.settlement_status.parse()?,
+            lines,
+        );
+        settlement.restore_review(
+            settlement_db.approved_at,
+            settlement_db.approved_by.as_deref(),
+            settlement_db.rejected_at,
+            settlement_db.rejection_reason.as_deref(),
+        );
+
+        Ok(Some(settlement))
+    }
+
+    async fn unsettled_routes(
+        &self,
+        vendor_id: i32,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<Vec<CompletedRoute>> {
+        let routes = sqlx::query!(
+            r#"
+        SELECT vr.vehicle_id, vr.route_id, v.type, vr.completed_at AS "completed_at!",
+            r.distance, vr.load
+        FROM vehicle_routes vr
+        JOIN vehicles v ON v.id = vr.vehicle_id
+        JOIN routes r ON r.id = vr.route_id
+        WHERE v.vendor_id = $1 AND vr.tenant_id = $5 AND vr.completed_at IS NOT NULL
+            AND (vr.completed_at AT TIME ZONE 'UTC')::date BETWEEN $2 AND $3
+            AND NOT EXISTS (
+                SELECT 1 FROM settlement_lines sl
+                JOIN settlements s ON s.id = sl.settlement_id
+                WHERE sl.vehicle_id = vr.vehicle_id AND sl.route_id = vr.route_id
+                    AND s.settlement_status <> $4
+            )
+        ORDER BY vr.completed_at, vr.vehicle_id, vr.route_id
+            "#,
+            vendor_id,
+            period_start,
+            period_end,
+            SettlementStatus::Rejected.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_all(&*self.pg_pool)
+        .await?
+        .into_iter()
+        .map(|r| CompletedRoute {
+            vehicle_id: r.vehicle_id,
+            route_id: r.route_id,
+            vehicle_type: r.r#type,
+            completed_at: r.completed_at,
+            distance: r.distance,
+            load: r.load,
+        })
+        .collect();
+
+        Ok(routes)
+    }
+
+    async fn create<'a, 'b>(&'a self, settlement: &'b Settlement) -> Result<Option<i32>> {
+        if settlement.id() != 0 {
+            panic!("Settlement id must be 0.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        // Serialises settlement runs per vendor, so a route cannot be paid twice.
+        sqlx::query!(
+            "SELECT id FROM vendors WHERE id = $1 AND tenant_id = $2 FOR UPDATE",
+            settlement.vendor_id,
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let vehicle_ids: Vec<i32> = settlement.lines.iter().map(|l| l.vehicle_id).collect();
+        let route_ids: Vec<i32> = settlement.lines.iter().map(|l| l.route_id).collect();
+
+        let already_settled = sqlx::query!(
+            r#"
+        SELECT EXISTS (
+            SELECT 1 FROM settlement_lines sl
+            JOIN settlements s ON s.id = sl.settlement_id
+            JOIN UNNEST($1::int[], $2::int[]) AS l (vehicle_id, route_id)
+                ON l.vehicle_id = sl.vehicle_id AND l.route_id = sl.route_id
+            WHERE s.settlement_status <> $3 AND s.tenant_id = $4
+        ) AS "already_settled!"
+            "#,
+            &vehicle_ids,
+            &route_ids,
+            SettlementStatus::Rejected.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .already_settled;
+
+        if already_settled {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO settlements (vendor_id, period_start, period_end, settlement_status, tenant_id)
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id
+        "#,
+            settlement.vendor_id,
+            settlement.period_start,
+            settlement.period_end,
+            settlement.settlement_status.as_str(),
+            self.tenant.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for line in &settlement.lines {
+            sqlx::query!(
+                r#"
+INSERT INTO settlement_lines (settlement_id, vehicle_id, route_id, rate_card_id, completed_at,
+    distance, load, amount, minimum_charge_applied, tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+                record.id,
+                line.vehicle_id,
+                line.route_id,
+                line.rate_card_id,
+                line.completed_at,
+                line.distance,
+                line.load,
+                line.amount,
+                line.minimum_charge_applied,
+                self.tenant.as_str()
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(record.id))
+    }
+
+    async fn review<'a, 'b>(&'a self, settlement: &'b Settlement) -> Result<bool> {
+        if settlement.id() == 0 {
+            panic!("Settlement id cannot be 0.");
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE settlements SET settlement_status = $1, approved_at = $2, approved_by = $3,
+    rejected_at = $4, rejection_reason = $5
+WHERE id = $6 AND settlement_status = $7 AND tenant_id = $8
+        "#,
+            settlement.settlement_status.as_str(),
+            settlement.approved_at,
+            settlement.approved_by,
+            settlement.rejected_at,
+            settlement.rejection_reason,
+            settlement.id,
+            SettlementStatus::Draft.as_str(),
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+}
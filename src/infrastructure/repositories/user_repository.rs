@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::user::User;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The local users of one tenant.
+pub struct UserRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl UserRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    /// The user with `email`, ignoring case.
+    async fn by_email(&self, email: &str) -> Result<Option<User>>;
+    async fn create<'a, 'b>(&'a self, user: &'b User) -> Result<i32>;
+    async fn update<'a, 'b>(&'a self, user: &'b User) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for UserRepository {
+    async fn by_email(&self, email: &str) -> Result<Option<User>> {
+        let user = sqlx::query!(
+            r#"
+        SELECT id, name, email, role, contact_number
+        FROM users
+        WHERE lower(email) = lower($1) AND tenant_id = $2
+        ORDER BY id
+        LIMIT 1
+            "#,
+            email,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?
+        .map(|u| {
+            User::new(
+                u.id,
+                &u.name,
+                &u.email,
+                &u.role,
+                u.contact_number.as_deref(),
+            )
+        });
+
+        Ok(user)
+    }
+
+    async fn create<'a, 'b>(&'a self, user: &'b User) -> Result<i32> {
+        if user.id() != 0 {
+            panic!("User id must be 0.");
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO users (name, email, role, contact_number, tenant_id)
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id
+            "#,
+            user.name,
+            user.email,
+            user.role,
+            user.contact_number,
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(record.id)
+    }
+
+    async fn update<'a, 'b>(&'a self, user: &'b User) -> Result<bool> {
+        if user.id() == 0 {
+            panic!("User id cannot be 0.");
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE users SET name = $1, email = $2, role = $3, contact_number = $4
+WHERE id = $5 AND tenant_id = $6
+            "#,
+            user.name,
+            user.email,
+            user.role,
+            user.contact_number,
+            user.id,
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+}
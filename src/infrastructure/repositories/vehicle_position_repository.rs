@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::vehicle::VehiclePosition;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The vehicle positions of one tenant.
+pub struct VehiclePositionRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl VehiclePositionRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    /// Appends positions for one vehicle, skipping fixes already stored, and returns how many were new.
+    async fn create_many<'a, 'b>(
+        &'a self,
+        vehicle_id: i32,
+        positions: &'b [VehiclePosition],
+    ) -> Result<u64>;
+}
+
+#[async_trait]
+impl Repository for VehiclePositionRepository {
+    async fn create_many<'a, 'b>(
+        &'a self,
+        vehicle_id: i32,
+        positions: &'b [VehiclePosition],
+    ) -> Result<u64> {
+        let Some(latest) = positions.iter().max_by_key(|p| p.recorded_at) else {
+            return Ok(0);
+        };
+
+        let recorded_at: Vec<_> = positions.iter().map(|p| p.recorded_at).collect();
+        let latitude: Vec<_> = positions.iter().map(|p| p.latitude).collect();
+        let longitude: Vec<_> = positions.iter().map(|p| p.longitude).collect();
+        let speed: Vec<_> = positions.iter().map(|p| p.speed).collect();
+        let heading: Vec<_> = positions.iter().map(|p| p.heading).collect();
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        // A single UNNEST insert keeps a batch to one round trip.
+        let rows_affected = sqlx::query!(
+            r#"
+INSERT INTO vehicle_positions (vehicle_id, recorded_at, latitude, longitude, speed, heading,
+    tenant_id)
+SELECT $1, *, $7
+FROM UNNEST($2::timestamptz[], $3::float8[], $4::float8[], $5::real[], $6::real[])
+ON CONFLICT (vehicle_id, recorded_at) DO NOTHING
+        "#,
+            vehicle_id,
+            &recorded_at,
+            &latitude,
+            &longitude,
+            &speed as &[Option<f32>],
+            &heading as &[Option<f32>],
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+INSERT INTO vehicle_last_positions (vehicle_id, recorded_at, latitude, longitude, speed, heading,
+    tenant_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (vehicle_id) DO UPDATE
+SET recorded_at = EXCLUDED.recorded_at, latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude,
+    speed = EXCLUDED.speed, heading = EXCLUDED.heading
+WHERE vehicle_last_positions.recorded_at < EXCLUDED.recorded_at
+    AND vehicle_last_positions.tenant_id = EXCLUDED.tenant_id
+        "#,
+            vehicle_id,
+            latest.recorded_at,
+            latest.latitude,
+            latest.longitude,
+            latest.speed,
+            latest.heading,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(rows_affected)
+    }
+}
+
+/// Creates upcoming daily partitions and drops the ones older than the retention window. The
+/// partitions hold the positions of every tenant.
+pub async fn apply_retention(
+    pg_pool: &PgPool,
+    retention_days: i32,
+    premake_days: i32,
+) -> Result<()> {
+    sqlx::query!(
+        "SELECT maintain_vehicle_position_partitions($1, $2)",
+        retention_days,
+        premake_days
+    )
+    .execute(pg_pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::vehicle::Vehicle;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The vehicles of one tenant's vendors.
+pub struct VehicleRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl VehicleRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, id: i32) -> Result<Option<Vehicle>>;
+    async fn create<'a, 'b>(&'a self, vehicle: &'b Vehicle) -> Result<i32>;
+    async fn update<'a, 'b>(&'a self, vehicle: &'b Vehicle) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for VehicleRepository {
+    async fn by_id(&self, id: i32) -> Result<Option<Vehicle>> {
+        let vehicle_db = sqlx::query!(
+            r#"
+        SELECT id, vendor_id, type, capacity, availability_status
+        FROM vehicles
+        WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?;
+
+        Ok(vehicle_db.map(|v| {
+            Vehicle::new(
+                v.id,
+                v.vendor_id,
+                v.r#type.as_str(),
+                v.capacity,
+                v.availability_status,
+            )
+        }))
+    }
+
+    async fn create<'a, 'b>(&'a self, vehicle: &'b Vehicle) -> Result<i32> {
+        if vehicle.id() != 0 {
+            panic!("Vehicle id must be 0.");
+        }
+
+        let record = sqlx::query!(
+            r#"
+INSERT INTO vehicles (vendor_id, type, capacity, availability_status, tenant_id)
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id
+        "#,
+            vehicle.vendor_id,
+            vehicle.vehicle_type,
+            vehicle.capacity,
+            vehicle.availability_status,
+            self.tenant.as_str()
+        )
+        .fetch_one(&*self.pg_pool)
+        .await?;
+
+        Ok(record.id)
+    }
+
+    async fn update<'a, 'b>(&'a self, vehicle: &'b Vehicle) -> Result<bool> {
+        if vehicle.id() == 0 {
+            panic!("Vehicle id cannot be 0.");
+        }
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE vehicles SET type = $1, capacity = $2, availability_status = $3
+WHERE id = $4 AND tenant_id = $5
+        "#,
+            vehicle.vehicle_type,
+            vehicle.capacity,
+            vehicle.availability_status,
+            vehicle.id,
+            self.tenant.as_str()
+        )
+        .execute(&*self.pg_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::async_trait;
+use sqlx::postgres::PgPool;
+
+use crate::domain::aggregates::vehicle_route::VehicleRoute;
+use crate::domain::value_objects::tenant_id::TenantId;
+
+/// The vehicle routes of one tenant.
+pub struct VehicleRouteRepository {
+    pg_pool: Arc<PgPool>,
+    tenant: TenantId,
+}
+
+impl VehicleRouteRepository {
+    pub fn new(pg_pool: PgPool, tenant: TenantId) -> Self {
+        Self {
+            pg_pool: Arc::new(pg_pool),
+            tenant,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Repository {
+    async fn by_id(&self, vehicle_id: i32, route_id: i32) -> Result<Option<VehicleRoute>>;
+    /// Puts the vehicle back in service once it has no other open route. Returns `false` when the
+    /// route had already been completed.
+    async fn complete<'a, 'b>(&'a self, vehicle_route: &'b VehicleRoute) -> Result<bool>;
+}
+
+#[async_trait]
+impl Repository for VehicleRouteRepository {
+    async fn by_id(&self, vehicle_id: i32, route_id: i32) -> Result<Option<VehicleRoute>> {
+        let vehicle_route = sqlx::query!(
+            r#"
+        SELECT vehicle_id, route_id, load, completed_at
+        FROM vehicle_routes
+        WHERE vehicle_id = $1 AND route_id = $2 AND tenant_id = $3
+            "#,
+            vehicle_id,
+            route_id,
+            self.tenant.as_str()
+        )
+        .fetch_optional(&*self.pg_pool)
+        .await?
+        .map(|r| VehicleRoute::new(r.vehicle_id, r.route_id, r.load, r.completed_at));
+
+        Ok(vehicle_route)
+    }
+
+    async fn complete<'a, 'b>(&'a self, vehicle_route: &'b VehicleRoute) -> Result<bool> {
+        if vehicle_route.completed_at.is_none() {
+            panic!("Vehicle route must be completed.");
+        }
+
+        let mut tx = self.pg_pool.begin().await?;
+
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE vehicle_routes SET load = $1, completed_at = $2
+WHERE vehicle_id = $3 AND route_id = $4 AND tenant_id = $5 AND completed_at IS NULL
+        "#,
+            vehicle_route.load,
+            vehicle_route.completed_at,
+            vehicle_route.vehicle_id,
+            vehicle_route.route_id,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+UPDATE vehicles SET availability_status = TRUE
+WHERE id = $1 AND tenant_id = $2 AND NOT EXISTS (
+    SELECT 1 FROM vehicle_routes
+    WHERE vehicle_id = $1 AND completed_at IS NULL AND tenant_id = $2
+)
+        "#,
+            vehicle_route.vehicle_id,
+            self.tenant.as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,72 @@
+use std::future::Future;
+
+use futures::{stream, Stream, StreamExt};
+use sqlx::postgres::{PgConnection, PgPoolOptions};
+
+use crate::domain::value_objects::tenant_id::TenantId;
+
+tokio::task_local! {
+    static SCOPE: Scope;
+}
+
+/// Whose rows the connections taken from the pool may see, see the `tenant_isolation` policies.
+#[derive(Clone, Debug)]
+enum Scope {
+    Tenant(TenantId),
+    /// Background jobs and migrations, which work across tenants.
+    AllTenants,
+}
+
+/// Pool options that set `app.tenant_id` and `app.all_tenants` on a connection each time it is
+/// taken from the pool, from the scope the caller runs in. Outside any scope a connection sees no
+/// tenant's rows. This costs a round trip per acquire.
+pub fn pool_options() -> PgPoolOptions {
+    PgPoolOptions::new()
+        .after_connect(|conn, _| Box::pin(apply_scope(conn)))
+        .before_acquire(|conn, _| {
+            Box::pin(async move {
+                apply_scope(conn).await?;
+                Ok(true)
+            })
+        })
+}
+
+async fn apply_scope(conn: &mut PgConnection) -> sqlx::Result<()> {
+    let (tenant_id, all_tenants) = match SCOPE.try_with(Scope::clone) {
+        Ok(Scope::Tenant(tenant)) => (tenant.as_str().to_string(), "off"),
+        Ok(Scope::AllTenants) => (String::new(), "on"),
+        Err(_) => (String::new(), "off"),
+    };
+
+    sqlx::query(
+        "SELECT set_config('app.tenant_id', $1, false), set_config('app.all_tenants', $2, false)",
+    )
+    .bind(tenant_id)
+    .bind(all_tenants)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Runs `future` with the connections it takes limited to the rows of `tenant`.
+pub async fn in_tenant<F: Future>(tenant: TenantId, future: F) -> F::Output {
+    SCOPE.scope(Scope::Tenant(tenant), future).await
+}
+
+/// Runs `future` with the connections it takes seeing the rows of every tenant.
+pub async fn across_tenants<F: Future>(future: F) -> F::Output {
+    SCOPE.scope(Scope::AllTenants, future).await
+}
+
+/// `in_tenant` for a stream, which is polled after the request handler has returned, e.g. the
+/// body of an export.
+pub fn stream_in_tenant<S>(tenant: TenantId, stream: S) -> impl Stream<Item = S::Item> + Send
+where
+    S: Stream + Send + 'static,
+{
+    let scope = Scope::Tenant(tenant);
+    let mut stream = Box::pin(stream);
+
+    stream::poll_fn(move |cx| SCOPE.sync_scope(scope.clone(), || stream.poll_next_unpin(cx)))
+}
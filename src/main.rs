@@ -1,23 +1,34 @@
 use anyhow::Context;
 use dotenvy::dotenv;
-use sqlx::postgres::PgPoolOptions;
 
+use tsm::application::config::Config;
 use tsm::application::routes::serve;
+use tsm::application::telemetry;
+use tsm::infrastructure::{migrate, tenancy};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
-    tracing_subscriber::fmt::init();
 
-    let database_url = dotenvy::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    // Warnings about the configuration are printed before the configured output is set up.
+    let config =
+        tracing::subscriber::with_default(tracing_subscriber::fmt().finish(), Config::load)?;
+    let telemetry = telemetry::init(&config.telemetry)?;
 
-    let db = PgPoolOptions::new()
-        .max_connections(20)
-        .connect(&database_url)
+    tracing::info!("starting with the {} profile", config.profile);
+
+    let db = tenancy::pool_options()
+        .max_connections(config.database.max_connections)
+        .connect(&config.database.url)
         .await
         .context("failed to connect to DATABASE_URL")?;
 
-    sqlx::migrate!().run(&db).await?;
+    migrate(&db, config.tenancy.default_tenant_id.as_deref()).await?;
+
+    let result = serve(db, config).await;
+
+    // Exporting blocks, keep it off the runtime's threads.
+    tokio::task::spawn_blocking(move || telemetry.shutdown()).await?;
 
-    serve(db).await
+    result
 }
@@ -1,3 +1,18 @@
+pub mod address_dto;
+pub mod batch_dto;
+pub mod contact_dto;
 pub mod customer_dto;
+pub mod export_dto;
+pub mod health_dto;
+pub mod import_dto;
+pub mod invoice_dto;
+pub mod item_dto;
+pub mod order_dto;
+pub mod proof_of_delivery_dto;
+pub mod rate_card_dto;
+pub mod route_dto;
+pub mod route_plan_dto;
+pub mod settlement_dto;
 pub mod user_dto;
+pub mod vehicle_dto;
 pub mod vendor_dto;
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::ValidationError;
+
+use crate::domain::value_objects::{
+    address::{Address, AddressError},
+    coordinates::Coordinates,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressDto {
+    pub lines: Vec<String>,
+    pub city: String,
+    pub region: Option<String>,
+    pub postcode: String,
+    pub country_code: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl AddressDto {
+    pub fn to_domain(&self) -> Result<Address, AddressError> {
+        let coordinates = match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => Some(Coordinates::new(latitude, longitude)),
+            (None, None) => None,
+            _ => return Err(AddressError::InvalidCoordinates),
+        };
+
+        let lines: Vec<&str> = self.lines.iter().map(String::as_str).collect();
+
+        Address::new(
+            &lines,
+            &self.city,
+            self.region.as_deref(),
+            &self.postcode,
+            &self.country_code,
+            coordinates,
+        )
+    }
+}
+
+impl From<&Address> for AddressDto {
+    fn from(address: &Address) -> Self {
+        Self {
+            lines: address.lines.clone(),
+            city: address.city.clone(),
+            region: address.region.clone(),
+            postcode: address.postcode.clone(),
+            country_code: address.country_code.clone(),
+            latitude: address.coordinates.map(|c| c.latitude),
+            longitude: address.coordinates.map(|c| c.longitude),
+        }
+    }
+}
+
+// Runs the domain rules so request validation and the aggregate can never disagree.
+pub fn validate_address(address: &AddressDto) -> Result<(), ValidationError> {
+    address.to_domain().map(|_| ()).map_err(|e| {
+        let mut error = ValidationError::new(e.code());
+        error.message = Some(e.to_string().into());
+        error
+    })
+}
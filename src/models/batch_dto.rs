@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Creates and updates of one kind of record, sent together.
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest<T> {
+    /// Saves every operation or none of them. Otherwise each operation is saved on its own and
+    /// the others go ahead when one fails.
+    #[serde(default)]
+    pub atomic: bool,
+    pub operations: Vec<BatchOperation<T>>,
+}
+
+/// The same body as the create and update endpoints, with the id of the record to update.
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperation<T> {
+    /// Updates this record, a new one is created when absent.
+    pub id: Option<i32>,
+    #[serde(flatten)]
+    pub fields: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchStatus {
+    Created,
+    Updated,
+    /// The operation failed validation, see its errors.
+    Invalid,
+    /// There is no record with the id to update.
+    NotFound,
+    /// Saving the operation failed.
+    Failed,
+    /// Valid, but not saved because another operation of the atomic batch was not.
+    RolledBack,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResultDto {
+    /// The position of the operation in the request, from 0.
+    pub index: usize,
+    pub status: BatchStatus,
+    /// The id of the record created or updated, or of the update that was not.
+    pub id: Option<i32>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReportDto {
+    pub atomic: bool,
+    pub created: i32,
+    pub updated: i32,
+    /// The operations not saved, whatever the reason.
+    pub rejected: i32,
+    /// One per operation, in the order of the request.
+    pub results: Vec<BatchResultDto>,
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::domain::aggregates::contact::{ContactChannel, ContactRole};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactDto {
+    pub id: i32,
+    pub name: String,
+    pub role: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub preferred_channel: String,
+    pub is_primary: bool,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_preferred_channel"))]
+pub struct CreateContactRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(custom(function = "validate_role"))]
+    pub role: String,
+    #[validate(email, length(max = 255))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub phone: Option<String>,
+    #[validate(custom(function = "validate_channel"))]
+    pub preferred_channel: String,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ContactsQuery {
+    #[validate(custom(function = "validate_role"))]
+    pub role: Option<String>,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ContactRecipientsQuery {
+    #[validate(custom(function = "validate_role"))]
+    pub role: String,
+}
+
+fn validate_role(role: &str) -> Result<(), ValidationError> {
+    role.parse::<ContactRole>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("unknown_role"))
+}
+
+fn validate_channel(channel: &str) -> Result<(), ValidationError> {
+    channel
+        .parse::<ContactChannel>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("unknown_channel"))
+}
+
+// The preferred channel needs somewhere to send to.
+fn validate_preferred_channel(req: &CreateContactRequest) -> Result<(), ValidationError> {
+    let missing = match req.preferred_channel.parse::<ContactChannel>() {
+        Ok(ContactChannel::Email) => req.email.is_none(),
+        Ok(ContactChannel::Phone | ContactChannel::Sms) => req.phone.is_none(),
+        Err(_) => false,
+    };
+
+    if missing {
+        return Err(ValidationError::new("preferred_channel_without_address"));
+    }
+
+    Ok(())
+}
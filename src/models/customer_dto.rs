@@ -1,23 +1,63 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::models::address_dto::{validate_address, AddressDto};
+use crate::models::export_dto::{ExportRow, ExportValue};
+
 // The user data we'll get back from Microsoft Graph.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomerDto {
     pub id: i32,
     pub name: String,
     pub email: String,
-    pub address: String,
+    pub address: AddressDto,
     pub contact_number: Option<String>,
 }
 
-#[derive(Deserialize, Validate)]
+// The columns of the import file, so an export can be edited and imported back.
+impl ExportRow for CustomerDto {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "email",
+        "contact_number",
+        "address_line1",
+        "address_line2",
+        "city",
+        "region",
+        "postcode",
+        "country_code",
+        "latitude",
+        "longitude",
+    ];
+
+    fn values(&self) -> Vec<ExportValue> {
+        vec![
+            self.id.into(),
+            self.name.as_str().into(),
+            self.email.as_str().into(),
+            self.contact_number.clone().into(),
+            self.address.lines.first().cloned().into(),
+            self.address.lines.get(1).cloned().into(),
+            self.address.city.as_str().into(),
+            self.address.region.clone().into(),
+            self.address.postcode.as_str().into(),
+            self.address.country_code.as_str().into(),
+            self.address.latitude.into(),
+            self.address.longitude.into(),
+        ]
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateCustomerRequest {
     pub name: String,
     #[validate(length(min = 1, max = 1000))]
     pub email: String,
-    pub address: String,
+    #[validate(custom(function = "validate_address"))]
+    pub address: AddressDto,
     pub contact_number: Option<String>,
 }
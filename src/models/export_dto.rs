@@ -0,0 +1,76 @@
+use rust_decimal::Decimal;
+
+/// A typed cell, so spreadsheets get numbers where CSV only has text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    Empty,
+    Text(String),
+    Integer(i64),
+    Float(f64),
+    Decimal(Decimal),
+    Bool(bool),
+}
+
+impl From<String> for ExportValue {
+    fn from(value: String) -> Self {
+        ExportValue::Text(value)
+    }
+}
+
+impl From<&str> for ExportValue {
+    fn from(value: &str) -> Self {
+        ExportValue::Text(value.to_string())
+    }
+}
+
+impl From<i32> for ExportValue {
+    fn from(value: i32) -> Self {
+        ExportValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for ExportValue {
+    fn from(value: f64) -> Self {
+        ExportValue::Float(value)
+    }
+}
+
+impl From<Decimal> for ExportValue {
+    fn from(value: Decimal) -> Self {
+        ExportValue::Decimal(value)
+    }
+}
+
+impl From<bool> for ExportValue {
+    fn from(value: bool) -> Self {
+        ExportValue::Bool(value)
+    }
+}
+
+impl<T: Into<ExportValue>> From<Option<T>> for ExportValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(ExportValue::Empty, Into::into)
+    }
+}
+
+impl ExportValue {
+    pub fn to_text(&self) -> String {
+        match self {
+            ExportValue::Empty => String::new(),
+            ExportValue::Text(value) => value.clone(),
+            ExportValue::Integer(value) => value.to_string(),
+            ExportValue::Float(value) => value.to_string(),
+            ExportValue::Decimal(value) => value.to_string(),
+            ExportValue::Bool(value) => value.to_string(),
+        }
+    }
+}
+
+/// A DTO that list endpoints can also return as a CSV or XLSX file, one flat row per item.
+pub trait ExportRow: Send + 'static {
+    /// The snake_case header row.
+    const COLUMNS: &'static [&'static str];
+
+    /// One value per column.
+    fn values(&self) -> Vec<ExportValue>;
+}
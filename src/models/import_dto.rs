@@ -0,0 +1,109 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::address_dto::AddressDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportMode {
+    DryRun,
+    Commit,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    pub mode: ImportMode,
+}
+
+/// One line of a customer or vendor CSV file, the headers are the snake_case field names.
+#[derive(Debug, Deserialize)]
+pub struct PartyCsvRow {
+    pub name: String,
+    pub email: String,
+    pub contact_number: Option<String>,
+    pub address_line1: String,
+    pub address_line2: Option<String>,
+    pub city: String,
+    pub region: Option<String>,
+    pub postcode: String,
+    pub country_code: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl PartyCsvRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 6] = [
+        "name",
+        "email",
+        "address_line1",
+        "city",
+        "postcode",
+        "country_code",
+    ];
+
+    pub fn address(&self) -> AddressDto {
+        AddressDto {
+            lines: std::iter::once(self.address_line1.clone())
+                .chain(self.address_line2.clone())
+                .collect(),
+            city: self.city.clone(),
+            region: self.region.clone(),
+            postcode: self.postcode.clone(),
+            country_code: self.country_code.clone(),
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Reject,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowDto {
+    /// The line of the row in the uploaded file, the header being line 1.
+    pub line: u64,
+    pub email: Option<String>,
+    pub action: ImportAction,
+    /// Set once a committed row has been saved.
+    pub id: Option<i32>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReportDto {
+    pub mode: ImportMode,
+    /// Set when the import was committed.
+    pub import_id: Option<i32>,
+    pub total_rows: i32,
+    pub created: i32,
+    pub updated: i32,
+    pub rejected: i32,
+    /// Where to download the rejected rows of a committed import.
+    pub error_file: Option<String>,
+    pub rows: Vec<ImportRowDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportDto {
+    pub id: i32,
+    pub entity: String,
+    pub imported_by: String,
+    pub total_rows: i32,
+    pub created: i32,
+    pub updated: i32,
+    pub rejected: i32,
+    pub has_error_file: bool,
+    pub created_at: Option<NaiveDateTime>,
+}
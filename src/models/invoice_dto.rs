@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceSummaryDto {
+    pub id: i32,
+    pub customer_id: i32,
+    pub invoice_type: String,
+    pub status: String,
+    pub invoice_number: Option<String>,
+    pub credited_invoice_id: Option<i32>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub total: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDto {
+    pub id: i32,
+    pub customer_id: i32,
+    pub invoice_type: String,
+    pub status: String,
+    pub invoice_number: Option<String>,
+    pub credited_invoice_id: Option<i32>,
+    pub reason: Option<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+    pub total: Decimal,
+    pub lines: Vec<InvoiceLineDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceLineDto {
+    pub id: i32,
+    pub order_id: Option<i32>,
+    pub item_id: Option<i32>,
+    pub credited_line_id: Option<i32>,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    pub line_total: Decimal,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateInvoiceRequest {
+    // Defaults to every delivered order not invoiced yet.
+    #[validate(length(min = 1))]
+    pub order_ids: Option<Vec<i32>>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VoidInvoiceRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCreditNoteRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+    // Defaults to whatever has not been credited yet on every line.
+    #[validate(length(min = 1), custom(function = "validate_distinct_lines"), nested)]
+    pub lines: Option<Vec<CreditNoteLineRequest>>,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditNoteLineRequest {
+    pub invoice_line_id: i32,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
+
+fn validate_distinct_lines(lines: &[CreditNoteLineRequest]) -> Result<(), ValidationError> {
+    let line_ids: HashSet<i32> = lines.iter().map(|l| l.invoice_line_id).collect();
+
+    if line_ids.len() != lines.len() {
+        return Err(ValidationError::new("duplicate_line"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ItemRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub description: Option<String>,
+    #[validate(range(min = 0))]
+    pub quantity_available: i32,
+    #[validate(custom(function = "validate_not_negative"))]
+    pub unit_price: Decimal,
+}
+
+/// The catalogue a tenant starts from, as loaded by `tsm-admin seed`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ReferenceData {
+    #[serde(default)]
+    pub items: Vec<ItemRequest>,
+}
+
+fn validate_not_negative(value: &Decimal) -> Result<(), ValidationError> {
+    if value.is_sign_negative() {
+        return Err(ValidationError::new("negative"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::domain::aggregates::order::OrderWindows;
+use crate::domain::value_objects::time_window::{TimeWindow, TimeWindowError};
+use crate::models::export_dto::{ExportRow, ExportValue};
+use crate::models::route_plan_dto::TimeWindowDto;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDto {
+    pub id: i32,
+    pub customer_id: i32,
+    pub status: String,
+    pub created_at: Option<NaiveDateTime>,
+    /// The IANA time zone of the order's sites, the windows are given in it.
+    pub time_zone: Option<String>,
+    pub pickup_window: Option<OrderWindowDto>,
+    pub delivery_window: Option<OrderWindowDto>,
+}
+
+// The windows flattened to a column per end, as RFC 3339 with the offset of the order's sites.
+impl ExportRow for OrderDto {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "customer_id",
+        "status",
+        "created_at",
+        "time_zone",
+        "pickup_earliest",
+        "pickup_latest",
+        "delivery_earliest",
+        "delivery_latest",
+    ];
+
+    fn values(&self) -> Vec<ExportValue> {
+        let at = |at: Option<DateTime<FixedOffset>>| at.map(|at| at.to_rfc3339()).into();
+
+        vec![
+            self.id.into(),
+            self.customer_id.into(),
+            self.status.as_str().into(),
+            self.created_at
+                .map(|at| at.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .into(),
+            self.time_zone.clone().into(),
+            at(self.pickup_window.map(|w| w.earliest)),
+            at(self.pickup_window.map(|w| w.latest)),
+            at(self.delivery_window.map(|w| w.earliest)),
+            at(self.delivery_window.map(|w| w.latest)),
+        ]
+    }
+}
+
+/// A pickup or delivery window at the local time of the order's sites.
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderWindowDto {
+    pub earliest: DateTime<FixedOffset>,
+    pub latest: DateTime<FixedOffset>,
+}
+
+impl OrderWindowDto {
+    pub fn in_zone(earliest: DateTime<Utc>, latest: DateTime<Utc>, time_zone: Tz) -> Self {
+        Self {
+            earliest: earliest.with_timezone(&time_zone).fixed_offset(),
+            latest: latest.with_timezone(&time_zone).fixed_offset(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderDue {
+    /// Still to be collected or delivered, with a window closing today in the order's time zone.
+    Today,
+    /// Still to be collected or delivered, with a window that closed already.
+    Late,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct OrdersQuery {
+    /// `pending`, `confirmed`, `assigned`, `in_transit`, `delivered` or `cancelled`.
+    pub status: Option<String>,
+    #[param(inline)]
+    pub due: Option<OrderDue>,
+}
+
+/// Replaces the windows of an order, those left out are cleared.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_order_windows"))]
+pub struct ScheduleOrderRequest {
+    /// The IANA time zone of the order's sites, e.g. `Australia/Sydney`.
+    #[schema(value_type = String, example = "Australia/Sydney")]
+    pub time_zone: Tz,
+    pub pickup_window: Option<TimeWindowDto>,
+    pub delivery_window: Option<TimeWindowDto>,
+}
+
+impl ScheduleOrderRequest {
+    pub fn to_domain(&self) -> Result<OrderWindows, TimeWindowError> {
+        let window =
+            |w: Option<TimeWindowDto>| w.map(|w| TimeWindow::new(w.earliest, w.latest)).transpose();
+
+        Ok(OrderWindows {
+            time_zone: self.time_zone,
+            pickup: window(self.pickup_window)?,
+            delivery: window(self.delivery_window)?,
+        })
+    }
+}
+
+// The rules that do not depend on the order, the others are checked when it is scheduled.
+fn validate_order_windows(request: &ScheduleOrderRequest) -> Result<(), ValidationError> {
+    request
+        .to_domain()
+        .map(|_| ())
+        .map_err(|e| window_error(&e))
+}
+
+pub fn window_error(e: &TimeWindowError) -> ValidationError {
+    let mut error = ValidationError::new(e.code());
+    error.message = Some(e.to_string().into());
+    error
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDto {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub quantity_available: i32,
+    /// The current list price, orders keep the price they were placed at.
+    pub unit_price: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderLineDto {
+    pub order_id: i32,
+    pub item: ItemDto,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+/// The vehicle and main route an accepted route plan put an order on.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAssignmentDto {
+    pub order_id: i32,
+    pub vehicle_id: i32,
+    pub route_id: i32,
+}
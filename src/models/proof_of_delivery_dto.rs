@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOfDeliveryDto {
+    pub id: i32,
+    pub order_id: i32,
+    pub recipient_name: String,
+    pub delivered_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub notes: Option<String>,
+    pub signature: Option<DeliveryFileDto>,
+    pub photo: Option<DeliveryFileDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryFileDto {
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i32,
+    pub url: String,
+}
+
+// Raw file returned by the signature and photo download endpoints.
+pub struct DeliveryFileContent {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+// Describes the multipart form for the API documentation, the handler reads the parts one by one.
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOfDeliveryForm {
+    pub recipient_name: String,
+    /// RFC 3339, not in the future.
+    pub delivered_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub notes: Option<String>,
+    /// An image.
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub signature: Option<Vec<u8>>,
+    /// An image.
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub photo: Option<Vec<u8>>,
+}
+
+// Text fields of the multipart form, the signature and photo are read as separate parts.
+#[derive(Debug, Validate)]
+pub struct RecordProofOfDeliveryRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub recipient_name: String,
+    #[validate(custom(function = "validate_not_in_future"))]
+    pub delivered_at: DateTime<Utc>,
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+    pub notes: Option<String>,
+}
+
+fn validate_not_in_future(delivered_at: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if *delivered_at > Utc::now() {
+        return Err(ValidationError::new("delivered_at_in_future"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+pub const MAX_RATES_PER_CARD: u64 = 500;
+// Twice around the world, and a heavy freight train.
+pub const MAX_QUOTE_DISTANCE: Decimal = Decimal::from_parts(80_000, 0, 0, false, 0);
+pub const MAX_QUOTE_WEIGHT: Decimal = Decimal::from_parts(10_000_000, 0, 0, false, 0);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateDto {
+    // Kilometres.
+    #[validate(custom(function = "validate_not_negative"))]
+    pub min_distance: Decimal,
+    // Kilograms, same unit as the vehicle capacity.
+    #[validate(custom(function = "validate_not_negative"))]
+    pub min_weight: Decimal,
+    #[validate(custom(function = "validate_not_negative"))]
+    pub base_charge: Decimal,
+    #[validate(custom(function = "validate_not_negative"))]
+    pub rate_per_km: Decimal,
+    #[validate(custom(function = "validate_not_negative"))]
+    pub rate_per_kg: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateCardDto {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub name: String,
+    pub vehicle_type: String,
+    pub minimum_charge: Decimal,
+    pub effective_from: NaiveDate,
+    pub effective_to: Option<NaiveDate>,
+    pub rates: Vec<RateDto>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_rate_card"))]
+pub struct CreateRateCardRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(min = 1, max = 50))]
+    pub vehicle_type: String,
+    #[validate(custom(function = "validate_not_negative"))]
+    pub minimum_charge: Decimal,
+    pub effective_from: NaiveDate,
+    // Open ended when empty.
+    pub effective_to: Option<NaiveDate>,
+    #[validate(length(min = 1, max = "MAX_RATES_PER_CARD"), nested)]
+    pub rates: Vec<RateDto>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_quote_distance"))]
+pub struct QuoteRequest {
+    // Priced on the distance of an existing route, or on `distance` in kilometres.
+    pub route_id: Option<i32>,
+    #[validate(custom(function = "validate_quote_distance_range"))]
+    pub distance: Option<Decimal>,
+    #[validate(custom(function = "validate_quote_weight_range"))]
+    pub weight: Decimal,
+    // Defaults to every vehicle type.
+    #[validate(length(min = 1, max = 50))]
+    pub vehicle_type: Option<String>,
+    // Defaults to today.
+    pub ship_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteDto {
+    pub rank: usize,
+    pub vendor_id: i32,
+    pub rate_card_id: i32,
+    pub rate_card_name: String,
+    pub vehicle_type: String,
+    pub distance: Decimal,
+    pub weight: Decimal,
+    pub amount: Decimal,
+    pub minimum_charge_applied: bool,
+}
+
+fn validate_rate_card(req: &CreateRateCardRequest) -> Result<(), ValidationError> {
+    if req.effective_to.is_some_and(|to| to < req.effective_from) {
+        return Err(ValidationError::new("effective_to_before_effective_from"));
+    }
+
+    let cells: HashSet<(Decimal, Decimal)> = req
+        .rates
+        .iter()
+        .map(|r| (r.min_distance.normalize(), r.min_weight.normalize()))
+        .collect();
+
+    if cells.len() != req.rates.len() {
+        return Err(ValidationError::new("duplicate_rate"));
+    }
+
+    Ok(())
+}
+
+fn validate_quote_distance(req: &QuoteRequest) -> Result<(), ValidationError> {
+    if req.route_id.is_some() == req.distance.is_some() {
+        return Err(ValidationError::new("route_id_or_distance_required"));
+    }
+
+    Ok(())
+}
+
+fn validate_quote_distance_range(value: &Decimal) -> Result<(), ValidationError> {
+    validate_not_negative(value)?;
+
+    if *value > MAX_QUOTE_DISTANCE {
+        return Err(ValidationError::new("too_large"));
+    }
+
+    Ok(())
+}
+
+fn validate_quote_weight_range(value: &Decimal) -> Result<(), ValidationError> {
+    validate_not_negative(value)?;
+
+    if *value > MAX_QUOTE_WEIGHT {
+        return Err(ValidationError::new("too_large"));
+    }
+
+    Ok(())
+}
+
+fn validate_not_negative(value: &Decimal) -> Result<(), ValidationError> {
+    if value.is_sign_negative() {
+        return Err(ValidationError::new("negative"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use chrono::NaiveTime;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// A route dispatched by accepting a route plan, either the whole trip or one of its legs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatchedRouteDto {
+    pub id: i32,
+    /// The trip this is a leg of, `None` for the trip itself.
+    pub main_route_id: Option<i32>,
+    pub origin: String,
+    pub destination: String,
+    pub distance: Decimal,
+    pub estimated_travel_time: NaiveTime,
+}
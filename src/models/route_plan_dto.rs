@@ -0,0 +1,117 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+pub const MAX_ORDERS_PER_PLAN: u64 = 500;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CoordinatesDto {
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_time_window"))]
+pub struct TimeWindowDto {
+    pub earliest: DateTime<Utc>,
+    pub latest: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRoutesRequest {
+    #[validate(nested)]
+    pub depot: CoordinatesDto,
+    pub departure_at: DateTime<Utc>,
+    #[validate(range(min = 5.0, max = 150.0))]
+    pub average_speed_kmh: Option<f64>,
+    #[validate(length(min = 1, max = "MAX_ORDERS_PER_PLAN"), nested)]
+    pub orders: Vec<PlanningOrderRequest>,
+    // Defaults to every available vehicle.
+    pub vehicle_ids: Option<Vec<i32>>,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningOrderRequest {
+    pub order_id: i32,
+    // Defaults to the geocoded address of the order's customer.
+    #[validate(nested)]
+    pub location: Option<CoordinatesDto>,
+    // Same unit as the vehicle capacity.
+    #[validate(custom(function = "validate_not_negative"))]
+    pub load: Decimal,
+    #[validate(nested)]
+    pub delivery_window: Option<TimeWindowDto>,
+    #[validate(range(min = 0, max = 480))]
+    pub service_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlanDto {
+    pub id: i32,
+    pub status: String,
+    pub depot: CoordinatesDto,
+    pub departure_at: DateTime<Utc>,
+    pub routes: Vec<PlannedVehicleRouteDto>,
+    pub unassigned_order_ids: Vec<i32>,
+}
+
+// A proposed `vehicle_routes` row with the main `routes` row and its legs.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedVehicleRouteDto {
+    pub vehicle_id: i32,
+    pub load: Decimal,
+    pub route: RouteDto,
+    pub legs: Vec<RouteLegDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteDto {
+    // Only set once the plan has been accepted.
+    pub id: Option<i32>,
+    pub origin: String,
+    pub destination: String,
+    pub distance: Decimal,
+    pub estimated_travel_time: NaiveTime,
+    pub departure_at: DateTime<Utc>,
+    pub finish_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteLegDto {
+    // Empty for the return leg to the depot.
+    pub order_id: Option<i32>,
+    pub origin: String,
+    pub destination: String,
+    pub distance: Decimal,
+    pub estimated_travel_time: NaiveTime,
+    pub arrival_at: Option<DateTime<Utc>>,
+    pub departure_at: Option<DateTime<Utc>>,
+}
+
+fn validate_time_window(window: &TimeWindowDto) -> Result<(), ValidationError> {
+    if window.earliest > window.latest {
+        return Err(ValidationError::new("earliest_after_latest"));
+    }
+
+    Ok(())
+}
+
+fn validate_not_negative(value: &Decimal) -> Result<(), ValidationError> {
+    if value.is_sign_negative() {
+        return Err(ValidationError::new("negative"));
+    }
+
+    Ok(())
+}
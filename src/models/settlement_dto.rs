@@ -0,0 +1,189 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::models::address_dto::AddressDto;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementSummaryDto {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub status: String,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub route_count: i64,
+    pub total: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementDto {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub status: String,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub approved_by: Option<String>,
+    pub rejected_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub total: Decimal,
+    pub lines: Vec<SettlementLineDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLineDto {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    pub rate_card_id: i32,
+    pub completed_at: DateTime<Utc>,
+    pub distance: Decimal,
+    pub load: Decimal,
+    pub amount: Decimal,
+    pub minimum_charge_applied: bool,
+}
+
+// A new settlement, with the completed routes that could not be priced and were left out.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementDraftDto {
+    #[serde(flatten)]
+    pub settlement: SettlementDto,
+    pub unpriced_routes: Vec<UnpricedRouteDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnpricedRouteDto {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    pub vehicle_type: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_settlement_period"))]
+pub struct CreateSettlementRequest {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectSettlementRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementStatementDto {
+    pub settlement_id: i32,
+    pub status: String,
+    pub vendor: StatementVendorDto,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub approved_by: Option<String>,
+    pub lines: Vec<StatementLineDto>,
+    pub total: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementVendorDto {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub address: AddressDto,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLineDto {
+    pub completed_at: DateTime<Utc>,
+    pub vehicle_id: i32,
+    pub vehicle_type: String,
+    pub route_id: i32,
+    pub origin: String,
+    pub destination: String,
+    pub distance: Decimal,
+    pub load: Decimal,
+    pub rate_card_name: String,
+    pub amount: Decimal,
+    pub minimum_charge_applied: bool,
+}
+
+// One approved settlement in the payables export.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PayableDto {
+    pub settlement_id: i32,
+    pub vendor_id: i32,
+    pub vendor_name: String,
+    pub vendor_email: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub approved_at: DateTime<Utc>,
+    pub approved_by: String,
+    pub route_count: i64,
+    pub amount: Decimal,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "validate_payables_range"))]
+pub struct PayablesExportQuery {
+    // Approval dates (UTC, inclusive).
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleRouteDto {
+    pub vehicle_id: i32,
+    pub route_id: i32,
+    pub load: Option<Decimal>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteVehicleRouteRequest {
+    // Defaults to now.
+    pub completed_at: Option<DateTime<Utc>>,
+    // Actual load, defaults to the planned one.
+    #[validate(custom(function = "validate_not_negative"))]
+    pub load: Option<Decimal>,
+}
+
+fn validate_settlement_period(req: &CreateSettlementRequest) -> Result<(), ValidationError> {
+    if req.period_end < req.period_start {
+        return Err(ValidationError::new("period_end_before_period_start"));
+    }
+
+    Ok(())
+}
+
+fn validate_payables_range(query: &PayablesExportQuery) -> Result<(), ValidationError> {
+    if query.to < query.from {
+        return Err(ValidationError::new("to_before_from"));
+    }
+
+    Ok(())
+}
+
+fn validate_not_negative(value: &Decimal) -> Result<(), ValidationError> {
+    if value.is_sign_negative() {
+        return Err(ValidationError::new("negative"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::models::export_dto::{ExportRow, ExportValue};
+
+pub const MAX_POSITIONS_PER_BATCH: u64 = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleDto {
+    pub id: i32,
+    pub vendor_id: i32,
+    pub vehicle_type: String,
+    pub capacity: Decimal,
+    pub availability_status: bool,
+}
+
+impl ExportRow for VehicleDto {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "vendor_id",
+        "vehicle_type",
+        "capacity",
+        "availability_status",
+    ];
+
+    fn values(&self) -> Vec<ExportValue> {
+        vec![
+            self.id.into(),
+            self.vendor_id.into(),
+            self.vehicle_type.as_str().into(),
+            self.capacity.into(),
+            self.availability_status.into(),
+        ]
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVehicleRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub vehicle_type: String,
+    #[validate(custom(function = "validate_positive"))]
+    pub capacity: Decimal,
+    pub availability_status: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VehiclePositionDto {
+    pub recorded_at: DateTime<Utc>,
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+    // Kilometres per hour.
+    #[validate(range(min = 0.0))]
+    pub speed: Option<f32>,
+    // Degrees clockwise from true north.
+    #[validate(range(min = 0.0, max = 360.0))]
+    pub heading: Option<f32>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordVehiclePositionsRequest {
+    #[validate(length(min = 1, max = "MAX_POSITIONS_PER_BATCH"), nested)]
+    pub positions: Vec<VehiclePositionDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordVehiclePositionsResponse {
+    pub received: usize,
+    pub stored: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestVehiclePositionDto {
+    pub vehicle_id: i32,
+    pub vendor_id: i32,
+    pub vehicle_type: String,
+    pub availability_status: bool,
+    pub recorded_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed: Option<f32>,
+    pub heading: Option<f32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct LatestVehiclePositionsQuery {
+    pub vendor_id: Option<i32>,
+}
+
+fn validate_positive(value: &Decimal) -> Result<(), ValidationError> {
+    if value.is_sign_negative() || value.is_zero() {
+        return Err(ValidationError::new("not_positive"));
+    }
+
+    Ok(())
+}
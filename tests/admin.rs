@@ -0,0 +1,242 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use tsm::application::imports::{import_csv, ImportOutcome};
+use tsm::application::jobs::invoicing;
+use tsm::application::reference_data::{seed, SeedSummary};
+use tsm::domain::aggregates::import::ImportEntity;
+use tsm::domain::aggregates::invoice::InvoiceStatus;
+use tsm::domain::aggregates::user::User;
+use tsm::domain::value_objects::tenant_id::TenantId;
+use tsm::infrastructure::reindex;
+use tsm::infrastructure::repositories::user_repository::{Repository as _, UserRepository};
+use tsm::models::import_dto::ImportMode;
+use tsm::models::item_dto::ReferenceData;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+fn tenant() -> TenantId {
+    TenantId::new(TENANT_ID)
+}
+
+fn reference_data(json: &str) -> ReferenceData {
+    serde_json::from_str(json).unwrap()
+}
+
+#[sqlx::test]
+async fn seeding_again_updates_the_items_by_name(db_pool: PgPool) {
+    let data = reference_data(
+        r#"{ "items": [
+            { "name": "Pallet", "quantityAvailable": 40, "unitPrice": "12.50" },
+            { "name": "Crate", "description": "Wooden", "quantityAvailable": 10, "unitPrice": "4.00" }
+        ] }"#,
+    );
+
+    let summary = seed(db_pool.clone(), tenant(), &data).await.unwrap();
+    assert_eq!(
+        summary,
+        SeedSummary {
+            created: 2,
+            updated: 0
+        }
+    );
+
+    let data = reference_data(
+        r#"{ "items": [{ "name": "PALLET", "quantityAvailable": 60, "unitPrice": "13.00" }] }"#,
+    );
+    let summary = seed(db_pool.clone(), tenant(), &data).await.unwrap();
+    assert_eq!(
+        summary,
+        SeedSummary {
+            created: 0,
+            updated: 1
+        }
+    );
+
+    let items: Vec<(String, i32, Decimal)> = sqlx::query_as(
+        "SELECT name, quantity_available, unit_price FROM items WHERE tenant_id = $1 ORDER BY name",
+    )
+    .bind(TENANT_ID)
+    .fetch_all(&db_pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        items,
+        [
+            ("Crate".to_string(), 10, Decimal::new(400, 2)),
+            ("PALLET".to_string(), 60, Decimal::new(1300, 2)),
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn invalid_reference_data_seeds_nothing(db_pool: PgPool) {
+    let data = reference_data(
+        r#"{ "items": [
+            { "name": "Pallet", "quantityAvailable": 40, "unitPrice": "12.50" },
+            { "name": "Crate", "quantityAvailable": -1, "unitPrice": "4.00" }
+        ] }"#,
+    );
+
+    let error = seed(db_pool.clone(), tenant(), &data).await.unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("item 'Crate': quantity_available: range"),
+        "{}",
+        error
+    );
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM items")
+        .fetch_one(&db_pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[sqlx::test]
+async fn users_are_found_by_email_to_be_granted_a_role(db_pool: PgPool) {
+    let repository = UserRepository::new(db_pool.clone(), tenant());
+
+    let user = User::new(
+        0,
+        "Dana",
+        "dana@haulage.test",
+        "driver",
+        Some("0400 000 000"),
+    );
+    let id = repository.create(&user).await.unwrap();
+
+    let mut user = repository
+        .by_email("DANA@haulage.test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.id(), id);
+
+    user.grant("dispatcher");
+    assert!(repository.update(&user).await.unwrap());
+
+    let user = repository
+        .by_email("dana@haulage.test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.role, "dispatcher");
+
+    let elsewhere = UserRepository::new(
+        db_pool,
+        TenantId::new("20000000-0000-0000-0000-00000000000b"),
+    );
+    assert!(elsewhere
+        .by_email("dana@haulage.test")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[sqlx::test]
+async fn imports_a_csv_file_like_the_endpoint(db_pool: PgPool) {
+    let csv = "name,email,address_line1,city,region,postcode,country_code\n\
+               Acme,orders@acme.test,1 Main St,Sydney,NSW,2000,AU\n\
+               Globex,,2 Side St,Sydney,NSW,2000,AU\n";
+
+    for (mode, import_id) in [(ImportMode::DryRun, false), (ImportMode::Commit, true)] {
+        let outcome = import_csv(
+            ImportEntity::Customers,
+            db_pool.clone(),
+            tenant(),
+            csv.as_bytes(),
+            mode,
+            "tsm-admin",
+        )
+        .await
+        .unwrap();
+
+        let ImportOutcome::Report(report) = outcome else {
+            panic!("the file was rejected");
+        };
+        assert_eq!((report.created, report.rejected), (1, 1));
+        assert_eq!(report.import_id.is_some(), import_id);
+    }
+
+    let outcome = import_csv(
+        ImportEntity::Vendors,
+        db_pool,
+        tenant(),
+        b"name,email\nHaulage Co,ops@haulage.test\n",
+        ImportMode::Commit,
+        "tsm-admin",
+    )
+    .await
+    .unwrap();
+    assert!(matches!(outcome, ImportOutcome::Rejected(_)));
+}
+
+#[sqlx::test]
+async fn invoices_every_customer_with_delivered_orders_once(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    app.create_customer("Globex", "orders@globex.test").await;
+
+    let order_id: i32 = sqlx::query_scalar(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, 'delivered', $2) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    let item_id: i32 = sqlx::query_scalar(
+        "INSERT INTO items (name, quantity_available, unit_price, tenant_id) VALUES ('Pallet', 100, 12.50, $1) RETURNING id",
+    )
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO order_items (order_id, item_id, quantity, unit_price, tenant_id) VALUES ($1, $2, 3, 10.00, $3)",
+    )
+    .bind(order_id)
+    .bind(item_id)
+    .bind(TENANT_ID)
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let run = invoicing::run(app.db_pool.clone(), tenant(), true)
+        .await
+        .unwrap();
+    assert!(run.skipped.is_empty());
+    assert_eq!(run.invoices.len(), 1);
+
+    let invoice = &run.invoices[0];
+    assert_eq!(invoice.customer_id, customer_id);
+    assert_eq!(invoice.invoice_status, InvoiceStatus::Issued);
+    assert!(invoice.invoice_number.is_some());
+    assert_eq!(invoice.total(), Decimal::new(3000, 2));
+
+    let run = invoicing::run(app.db_pool.clone(), tenant(), true)
+        .await
+        .unwrap();
+    assert!(run.invoices.is_empty());
+}
+
+#[sqlx::test]
+async fn reindexes_every_table(db_pool: PgPool) {
+    let tables = reindex(&db_pool).await.unwrap();
+
+    assert!(tables.iter().any(|t| t == "customers"));
+    // Partitions go with their parent.
+    assert_eq!(
+        tables
+            .iter()
+            .filter(|t| t.starts_with("vehicle_positions"))
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,159 @@
+use reqwest::Method;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::TestApp;
+
+#[sqlx::test]
+async fn requests_without_a_token_are_unauthorized(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app.anonymous(Method::GET, "/me").send().await.unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn tokens_for_another_audience_are_unauthorized(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let mut claims = app.issuer.claims(&[]);
+    claims["aud"] = json!("api://another-app");
+    let token = app.issuer.mint(&claims);
+
+    let response = app
+        .anonymous(Method::GET, "/me")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn expired_tokens_are_unauthorized(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let mut claims = app.issuer.claims(&[]);
+    claims["exp"] = json!(chrono::Utc::now().timestamp() - 3600);
+    let token = app.issuer.mint(&claims);
+
+    let response = app
+        .anonymous(Method::GET, "/me")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn tampered_tokens_are_unauthorized(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    // The claims of one token under the signature of another.
+    let signed = app.issuer.token(&[]);
+    let elevated = app.issuer.token(&["Admin"]);
+    let mut parts: Vec<&str> = signed.split('.').collect();
+    parts[1] = elevated.split('.').nth(1).unwrap();
+    let token = parts.join(".");
+
+    let response = app
+        .anonymous(Method::GET, "/me")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn malformed_tokens_are_unauthorized(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .anonymous(Method::GET, "/me")
+        .bearer_auth("not-a-token")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn tokens_signed_with_a_rotated_key_are_accepted_straight_away(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let me = |token: String| app.anonymous(Method::GET, "/me").bearer_auth(token).send();
+
+    let old = app.issuer.token(&[]);
+    assert_eq!(me(old.clone()).await.unwrap().status(), 200);
+
+    app.issuer.rotate("tsm-rotated-key");
+    assert_eq!(me(app.issuer.token(&[])).await.unwrap().status(), 200);
+    assert_eq!(me(old).await.unwrap().status(), 401);
+
+    // The keys were only just fetched again, so this one has to wait for the next refresh.
+    app.issuer.rotate("tsm-rotated-again");
+    assert_eq!(me(app.issuer.token(&[])).await.unwrap().status(), 401);
+}
+
+#[sqlx::test]
+async fn me_describes_the_signed_in_user(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool)
+        .await
+        .signed_in_with(&["Dispatcher", "Billing"]);
+
+    let response = app.get("/me").send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let me: Value = response.json().await.unwrap();
+    assert_eq!(me["username"], "test.user@example.com");
+    assert_eq!(me["displayName"], "Test User");
+    assert_eq!(me["roles"], json!(["Dispatcher", "Billing"]));
+}
+
+#[sqlx::test]
+async fn me_describes_an_application_signed_in_as_itself(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let token = app
+        .issuer
+        .mint(&app.issuer.app_claims("tsm-integration", &[]));
+
+    let response = app
+        .anonymous(Method::GET, "/me")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let me: Value = response.json().await.unwrap();
+    assert_eq!(me["id"], "tsm-integration");
+    assert_eq!(me["username"], "tsm-integration");
+    assert_eq!(me["displayName"], Value::Null);
+    assert_eq!(me["roles"], json!([]));
+}
+
+#[sqlx::test]
+async fn api_docs_are_public(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let spec = app
+        .anonymous(Method::GET, "/openapi.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(spec.status(), 200);
+    let spec: Value = spec.json().await.unwrap();
+    assert_eq!(spec["openapi"], "3.1.0");
+
+    let docs = app.anonymous(Method::GET, "/docs").send().await.unwrap();
+    assert_eq!(docs.status(), 200);
+    assert!(docs.text().await.unwrap().contains("<html"));
+}
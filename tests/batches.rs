@@ -0,0 +1,248 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{party, TestApp, TENANT_ID};
+
+async fn list(app: &TestApp, path: &str) -> Vec<Value> {
+    app.get(path).send().await.unwrap().json().await.unwrap()
+}
+
+fn update(id: i32, name: &str, email: &str) -> Value {
+    let mut operation = party(name, email);
+    operation["id"] = json!(id);
+    operation
+}
+
+#[sqlx::test]
+async fn saves_each_operation_on_its_own(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    let mut invalid = party("Initech", "");
+    invalid["address"]["countryCode"] = json!("Australia");
+
+    let response = app
+        .post("/customers:batch")
+        .json(&json!({
+            "operations": [
+                party("Globex", "orders@globex.test"),
+                update(id, "Acme Corporation", "orders@acme.test"),
+                update(id + 1000, "Hooli", "orders@hooli.test"),
+                invalid,
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["atomic"], false);
+    assert_eq!(report["created"], 1);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["rejected"], 2);
+
+    let results = report["results"].as_array().unwrap();
+    let statuses: Vec<&str> = results
+        .iter()
+        .map(|r| r["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["created", "updated", "notFound", "invalid"]);
+    assert!(results[0]["id"].as_i64().is_some());
+    assert_eq!(results[1]["id"], id);
+    assert_eq!(results[2]["id"], id + 1000);
+    assert!(!results[3]["errors"].as_array().unwrap().is_empty());
+
+    let names: Vec<Value> = list(&app, "/customers")
+        .await
+        .into_iter()
+        .map(|c| c["name"].clone())
+        .collect();
+    assert_eq!(names, ["Acme Corporation", "Globex"]);
+}
+
+#[sqlx::test]
+async fn an_atomic_batch_saves_all_or_nothing(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+
+    let response = app
+        .post("/vendors:batch")
+        .json(&json!({
+            "atomic": true,
+            "operations": [
+                party("Freight Co", "ops@freight.test"),
+                update(id + 1000, "Movers Co", "ops@movers.test"),
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["results"][0]["status"], "rolledBack");
+    assert_eq!(report["results"][1]["status"], "notFound");
+    assert_eq!(list(&app, "/vendors").await.len(), 1);
+
+    let response = app
+        .post("/vendors:batch")
+        .json(&json!({
+            "atomic": true,
+            "operations": [
+                party("Freight Co", "ops@freight.test"),
+                update(id, "Haulage Company", "ops@haulage.test"),
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["created"], 1);
+    assert_eq!(report["updated"], 1);
+
+    let new_id = report["results"][0]["id"].as_i64().unwrap();
+    let vendor: Value = app
+        .get(&format!("/vendors/{}", new_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(vendor["name"], "Freight Co");
+
+    let vendor: Value = app
+        .get(&format!("/vendors/{}", id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(vendor["name"], "Haulage Company");
+}
+
+#[sqlx::test]
+async fn saves_items_of_the_catalogue(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id: i32 = sqlx::query_scalar(
+        "INSERT INTO items (name, quantity_available, unit_price, tenant_id) VALUES ('Pallet', 100, 10, $1) RETURNING id",
+    )
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    let item = |name: &str, price: &str| json!({ "name": name, "description": null, "quantityAvailable": 5, "unitPrice": price });
+    let batch = |atomic: bool, operations: Vec<Value>| {
+        app.post("/items:batch")
+            .json(&json!({ "atomic": atomic, "operations": operations }))
+            .send()
+    };
+
+    let mut repriced = item("Pallet", "12.50");
+    repriced["id"] = json!(id);
+    let mut missing = item("Crate", "4");
+    missing["id"] = json!(id + 1000);
+
+    let response = batch(
+        false,
+        vec![item("Crate", "4"), repriced, missing, item("Drum", "-1")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let report: Value = response.json().await.unwrap();
+    let statuses: Vec<&str> = report["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["created", "updated", "notFound", "invalid"]);
+
+    let response = batch(true, vec![item("Drum", "30"), item("", "1")])
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["results"][0]["status"], "rolledBack");
+
+    let items: Vec<(String, String)> =
+        sqlx::query_as("SELECT name, unit_price::text FROM items WHERE tenant_id = $1 ORDER BY id")
+            .bind(TENANT_ID)
+            .fetch_all(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        items,
+        [
+            ("Pallet".to_string(), "12.50".to_string()),
+            ("Crate".to_string(), "4.00".to_string()),
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn updates_stay_within_the_tenant(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    let response = app
+        .in_tenant("20000000-0000-0000-0000-00000000000b")
+        .post("/customers:batch")
+        .json(&json!({ "operations": [update(id, "Hijacked", "x@example.test")] }))
+        .send()
+        .await
+        .unwrap();
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["results"][0]["status"], "notFound");
+
+    assert_eq!(list(&app, "/customers").await[0]["name"], "Acme");
+}
+
+#[sqlx::test]
+async fn rejects_an_oversized_batch(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let operations: Vec<Value> = (0..1001)
+        .map(|i| party("Acme", &format!("orders{}@acme.test", i)))
+        .collect();
+
+    let response = app
+        .post("/customers:batch")
+        .json(&json!({ "operations": operations }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    assert!(list(&app, "/customers").await.is_empty());
+}
+
+#[sqlx::test]
+async fn only_the_batch_suffix_is_routed(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    for path in [
+        "/customersfoo",
+        "/customers:batches",
+        "/vendors:import",
+        "/items:batched",
+    ] {
+        let response = app
+            .post(path)
+            .json(&json!({ "operations": [] }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use http::Method;
+use sqlx::PgPool;
+
+use tsm::application::config::{Config, CounterStore, LogFormat, Profile};
+
+mod support;
+
+use support::TestApp;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    move |name| vars.get(name).cloned()
+}
+
+const REQUIRED: &[(&str, &str)] = &[
+    ("DATABASE_URL", "postgres://localhost/tsm"),
+    ("TENANT_ID", "contoso"),
+    ("AUDIENCE", "api://tsm"),
+];
+
+const PRODUCTION: &[(&str, &str)] = &[
+    ("TSM_PROFILE", "production"),
+    ("METRICS_TOKEN", "scrape-secret"),
+];
+
+#[test]
+fn development_defaults() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+
+    assert_eq!(config.profile, Profile::Development);
+    assert_eq!(config.server.bind_address.to_string(), "127.0.0.1:3000");
+    assert_eq!(config.database.max_connections, 20);
+    assert_eq!(config.cors.allowed_origins, ["http://localhost:5173"]);
+    assert!(config.cors.allowed_methods.contains(&Method::PUT));
+    assert!(config.cors.allowed_methods.contains(&Method::DELETE));
+    assert_eq!(
+        config.auth.jwks_url.as_str(),
+        "https://login.microsoftonline.com/contoso/discovery/v2.0/keys"
+    );
+    assert_eq!(
+        config.auth.issuer,
+        "https://login.microsoftonline.com/contoso/v2.0"
+    );
+    assert_eq!(config.vehicle_positions.retention_days, 30);
+}
+
+#[test]
+fn production_listens_on_every_interface_without_cross_origin_access() {
+    let mut vars = REQUIRED.to_vec();
+    vars.extend_from_slice(PRODUCTION);
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+
+    assert_eq!(config.profile, Profile::Production);
+    assert_eq!(config.server.bind_address.to_string(), "0.0.0.0:3000");
+    assert!(config.cors.allowed_origins.is_empty());
+}
+
+#[test]
+fn production_asks_for_a_metrics_token() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+    assert!(config.metrics.token.is_none());
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("TSM_PROFILE", "production"));
+
+    let error = Config::from_sources(env(&vars), None)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("METRICS_TOKEN"), "{}", error);
+}
+
+#[test]
+fn the_profile_table_overrides_the_file_and_the_environment_overrides_both() {
+    let file = r#"
+        [cors]
+        allowed_origins = ["https://tms.example.com", "https://admin.example.com:8443"]
+        allowed_methods = ["get", "post"]
+
+        [database]
+        max_connections = 50
+
+        [profiles.production.database]
+        max_connections = 80
+
+        [profiles.production.server]
+        bind_address = "0.0.0.0:8080"
+    "#;
+
+    let mut vars = REQUIRED.to_vec();
+    vars.extend_from_slice(PRODUCTION);
+    vars.push(("BIND_ADDRESS", "0.0.0.0:9090"));
+
+    let config = Config::from_sources(env(&vars), Some((Path::new("tsm.toml"), file))).unwrap();
+
+    assert_eq!(config.server.bind_address.to_string(), "0.0.0.0:9090");
+    assert_eq!(config.database.max_connections, 80);
+    assert_eq!(
+        config.cors.allowed_origins,
+        ["https://tms.example.com", "https://admin.example.com:8443"]
+    );
+    assert_eq!(config.cors.allowed_methods, [Method::GET, Method::POST]);
+
+    // Other profiles ignore the production table.
+    let config = Config::from_sources(env(REQUIRED), Some((Path::new("tsm.toml"), file))).unwrap();
+    assert_eq!(config.server.bind_address.to_string(), "127.0.0.1:3000");
+    assert_eq!(config.database.max_connections, 50);
+}
+
+#[test]
+fn the_example_file_is_valid() {
+    let file = include_str!("../tsm.example.toml");
+
+    let config =
+        Config::from_sources(env(PRODUCTION), Some((Path::new("tsm.example.toml"), file))).unwrap();
+
+    assert_eq!(config.server.bind_address.to_string(), "0.0.0.0:3000");
+    assert_eq!(config.cors.allowed_origins, ["https://tms.example.com"]);
+}
+
+#[test]
+fn origins_are_a_comma_separated_list_in_the_environment() {
+    let mut vars = REQUIRED.to_vec();
+    vars.push((
+        "CORS_ALLOWED_ORIGINS",
+        "https://tms.example.com, http://localhost:5173",
+    ));
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+
+    assert_eq!(
+        config.cors.allowed_origins,
+        ["https://tms.example.com", "http://localhost:5173"]
+    );
+}
+
+#[test]
+fn reports_every_invalid_setting_at_once() {
+    let vars = [
+        ("BIND_ADDRESS", "localhost"),
+        ("DATABASE_MAX_CONNECTIONS", "many"),
+        ("CORS_ALLOWED_ORIGINS", "https://tms.example.com/app"),
+        ("AUDIENCE", "api://tsm"),
+    ];
+
+    let error = Config::from_sources(env(&vars), None)
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        error.contains("server.bind_address (BIND_ADDRESS)"),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains("DATABASE_MAX_CONNECTIONS: 'many'"),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains("database.url (DATABASE_URL) is required"),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains("'https://tms.example.com/app' has a path"),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains("auth.tenant_id (TENANT_ID) is required"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn rejects_unknown_settings_and_profiles() {
+    let error = Config::from_sources(
+        env(REQUIRED),
+        Some((Path::new("tsm.toml"), "[server]\nport = 3000\n")),
+    )
+    .unwrap_err();
+    assert!(format!("{:#}", error).contains("unknown field `port`"));
+
+    let error = Config::from_sources(
+        env(REQUIRED),
+        Some((Path::new("tsm.toml"), "[profiles.staging.server]\n")),
+    )
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown profile [profiles.staging]"));
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("TSM_PROFILE", "staging"));
+    assert!(Config::from_sources(env(&vars), None).is_err());
+}
+
+#[test]
+fn accepts_the_audience_under_its_original_name() {
+    let vars = [
+        ("DATABASE_URL", "postgres://localhost/tsm"),
+        ("TENANT_ID", "contoso"),
+        ("AUDIENDE", "api://tsm"),
+    ];
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+
+    assert_eq!(config.auth.audience, "api://tsm");
+}
+
+#[test]
+fn logs_json_in_production_and_exports_spans_only_when_asked() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+    assert_eq!(config.telemetry.log_format, LogFormat::Text);
+    assert!(config.telemetry.otlp_endpoint.is_none());
+
+    let mut vars = REQUIRED.to_vec();
+    vars.extend_from_slice(PRODUCTION);
+    vars.push(("OTEL_EXPORTER_OTLP_ENDPOINT", "http://localhost:4318"));
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+    assert_eq!(config.telemetry.log_format, LogFormat::Json);
+    assert_eq!(
+        config.telemetry.otlp_endpoint.unwrap().as_str(),
+        "http://localhost:4318/"
+    );
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("LOG_FORMAT", "xml"));
+
+    let error = Config::from_sources(env(&vars), None)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("LOG_FORMAT): 'xml'"), "{}", error);
+}
+
+#[test]
+fn rate_limits_per_role_come_as_pairs_in_the_environment() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+    assert_eq!(config.rate_limit.store, CounterStore::Memory);
+    assert_eq!(config.rate_limit.requests, 300);
+    assert!(config.rate_limit.roles.is_empty());
+
+    let mut vars = REQUIRED.to_vec();
+    vars.extend_from_slice(PRODUCTION);
+    vars.push(("RATE_LIMIT_ROLES", "Integration=1200, Admin=600"));
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+    assert_eq!(config.rate_limit.store, CounterStore::Postgres);
+    assert_eq!(config.rate_limit.roles["Integration"], 1200);
+    assert_eq!(config.rate_limit.roles["Admin"], 600);
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("RATE_LIMIT_ROLES", "Integration"));
+
+    let error = Config::from_sources(env(&vars), None)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("'Integration' is not a role=requests pair"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn existing_rows_go_to_the_entra_id_tenant_unless_another_is_given() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+    assert_eq!(config.tenancy.default_tenant_id.as_deref(), Some("contoso"));
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("DEFAULT_TENANT_ID", "fabrikam"));
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+    assert_eq!(
+        config.tenancy.default_tenant_id.as_deref(),
+        Some("fabrikam")
+    );
+}
+
+#[test]
+fn idempotency_keys_expire_after_a_day_unless_set() {
+    let config = Config::from_sources(env(REQUIRED), None).unwrap();
+    assert_eq!(
+        config.idempotency.key_ttl,
+        Duration::from_secs(24 * 60 * 60)
+    );
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("IDEMPOTENCY_KEY_TTL_HOURS", "2"));
+
+    let config = Config::from_sources(env(&vars), None).unwrap();
+    assert_eq!(config.idempotency.key_ttl, Duration::from_secs(2 * 60 * 60));
+
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("IDEMPOTENCY_KEY_TTL_HOURS", "0"));
+
+    let error = Config::from_sources(env(&vars), None)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("IDEMPOTENCY_KEY_TTL_HOURS) must be at least 1"),
+        "{}",
+        error
+    );
+}
+
+#[sqlx::test]
+async fn browsers_may_put_from_an_allowed_origin(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .anonymous(Method::OPTIONS, "/customers/1")
+        .header("origin", "http://localhost:5173")
+        .header("access-control-request-method", "PUT")
+        .header(
+            "access-control-request-headers",
+            "authorization,content-type",
+        )
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "http://localhost:5173"
+    );
+    let methods = response.headers()["access-control-allow-methods"]
+        .to_str()
+        .unwrap();
+    assert!(methods.contains("PUT"), "{}", methods);
+
+    let response = app
+        .anonymous(Method::OPTIONS, "/customers/1")
+        .header("origin", "https://elsewhere.example.com")
+        .header("access-control-request-method", "PUT")
+        .send()
+        .await
+        .unwrap();
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+}
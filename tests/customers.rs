@@ -0,0 +1,206 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{address, party, TestApp};
+
+#[sqlx::test]
+async fn creates_reads_and_updates_a_customer(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .post("/customers")
+        .json(&party("Acme", "orders@acme.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let created: Value = response.json().await.unwrap();
+    let id = created["id"].as_i64().unwrap();
+    assert_eq!(location, format!("/v1/api/customers/{}", id));
+    // Saved first, located by the geocoder in the background.
+    assert!(created["address"]["latitude"].is_null());
+    app.located(&location.replace("/v1/api", "")).await;
+
+    let mut update = party("Acme Pty Ltd", "orders@acme.test");
+    update["address"] = address("2 Market Street", "2000");
+    let response = app
+        .put(&format!("/customers/{}", id))
+        .json(&update)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let customer: Value = app
+        .get(&format!("/customers/{}", id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(customer["name"], "Acme Pty Ltd");
+    assert_eq!(customer["address"]["lines"], json!(["2 Market Street"]));
+
+    let customer = app.located(&format!("/customers/{}", id)).await;
+    assert_eq!(customer["address"]["postcode"], "2000");
+}
+
+#[sqlx::test]
+async fn unknown_customers_are_not_found(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app.get("/customers/4040").send().await.unwrap();
+
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn addresses_are_validated(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let mut customer = party("Acme", "orders@acme.test");
+    customer["address"]["region"] = Value::Null;
+
+    let response = app.post("/customers").json(&customer).send().await.unwrap();
+    assert_eq!(response.status(), 422);
+
+    let errors: Value = response.json().await.unwrap();
+    assert_eq!(errors["address"][0]["code"], "missing_region");
+
+    // Longer than the columns they are stored in.
+    let cases = [
+        ("city", json!("C".repeat(101)), "city_too_long"),
+        ("region", json!("R".repeat(101)), "region_too_long"),
+        ("postcode", json!("P".repeat(21)), "invalid_postcode"),
+    ];
+
+    for (field, value, code) in cases {
+        let mut customer = party("Acme", "orders@acme.test");
+        customer["address"]["countryCode"] = json!("NZ");
+        customer["address"]["postcode"] = json!("6011");
+        customer["address"][field] = value;
+
+        let response = app.post("/customers").json(&customer).send().await.unwrap();
+        assert_eq!(response.status(), 422, "{}", field);
+
+        let errors: Value = response.json().await.unwrap();
+        assert_eq!(errors["address"][0]["code"], code);
+    }
+
+    let customers: Vec<Value> = app
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(customers.is_empty());
+
+    // Countries without postcode rules may fill the column.
+    let mut customer = party("Acme", "orders@acme.test");
+    customer["address"]["countryCode"] = json!("IE");
+    customer["address"]["postcode"] = json!("P".repeat(20));
+
+    let response = app.post("/customers").json(&customer).send().await.unwrap();
+    assert_eq!(response.status(), 201);
+}
+
+#[sqlx::test]
+async fn lists_customers_as_json_or_csv(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    app.create_customer("Zenith", "zenith@example.test").await;
+    app.create_customer("Acme", "acme@example.test").await;
+
+    let customers: Vec<Value> = app
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let names: Vec<_> = customers.iter().map(|c| c["name"].clone()).collect();
+    assert_eq!(names, [json!("Acme"), json!("Zenith")]);
+
+    let response = app
+        .get("/customers")
+        .header("accept", "text/csv")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,name,email,"));
+    assert!(lines[1].contains(",Acme,acme@example.test,"));
+
+    let response = app.get("/customers?format=xlsx").send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"customers.xlsx\""
+    );
+
+    // A zip archive, read back from the temporary file to its end.
+    let workbook = response.bytes().await.unwrap();
+    assert!(workbook.starts_with(b"PK\x03\x04"));
+    assert!(workbook[workbook.len() - 22..].starts_with(b"PK\x05\x06"));
+}
+
+#[sqlx::test]
+async fn manages_the_contacts_of_a_customer(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    let contact_id = app
+        .create(
+            &format!("/customers/{}/contacts", id),
+            &json!({
+                "name": "Pat Billing",
+                "role": "billing",
+                "email": "billing@acme.test",
+                "preferredChannel": "email",
+            }),
+        )
+        .await;
+
+    let recipients: Vec<Value> = app
+        .get(&format!(
+            "/customers/{}/contacts/recipients?role=billing",
+            id
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(recipients.len(), 1);
+    assert_eq!(recipients[0]["id"], contact_id);
+
+    let response = app
+        .delete(&format!("/customers/{}/contacts/{}", id, contact_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = app
+        .get(&format!("/customers/{}/contacts/{}", id, contact_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use tsm::application::demo_data::{generate, DemoDataOptions, DemoDataSummary};
+use tsm::domain::value_objects::tenant_id::TenantId;
+
+mod support;
+
+use support::TENANT_ID;
+
+const OTHER_TENANT_ID: &str = "20000000-0000-0000-0000-00000000000b";
+
+fn options(seed: u64) -> DemoDataOptions {
+    let departure_at: DateTime<Utc> = "2024-09-02T08:00:00Z".parse().unwrap();
+
+    DemoDataOptions {
+        customers: 8,
+        vendors: 2,
+        vehicles_per_vendor: 2,
+        orders: 20,
+        ..DemoDataOptions::new(seed, departure_at)
+    }
+}
+
+/// What a tenant's data looks like, without the ids.
+async fn snapshot(db_pool: &PgPool, tenant_id: &str) -> Vec<String> {
+    sqlx::query_scalar(
+        r#"
+        SELECT concat_ws(' ', c.name, c.email, c.address_line1, c.city, c.postcode, c.contact_number)
+        FROM customers c WHERE c.tenant_id = $1
+        UNION ALL
+        SELECT concat_ws(' ', v.name, (SELECT string_agg(concat_ws(' ', type, capacity), ', ' ORDER BY id) FROM vehicles WHERE vendor_id = v.id))
+        FROM vendors v WHERE v.tenant_id = $1
+        UNION ALL
+        SELECT concat_ws(' ', c.name, o.order_status, (SELECT string_agg(concat_ws(' ', i.name, oi.quantity), ', ' ORDER BY i.name) FROM order_items oi JOIN items i ON i.id = oi.item_id WHERE oi.order_id = o.id))
+        FROM orders o JOIN customers c ON c.id = o.customer_id WHERE o.tenant_id = $1
+        ORDER BY 1
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(db_pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn orders_in_every_status_with_consistent_relations(db_pool: PgPool) {
+    let summary = generate(db_pool.clone(), TenantId::new(TENANT_ID), &options(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        (
+            summary.customers,
+            summary.vendors,
+            summary.vehicles,
+            summary.items,
+            summary.orders
+        ),
+        (8, 2, 4, 10, 20)
+    );
+    assert!(summary.routes >= 2, "{:?}", summary);
+
+    let statuses: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT order_status, count(*) FROM orders WHERE tenant_id = $1 GROUP BY 1 ORDER BY 1",
+    )
+    .bind(TENANT_ID)
+    .fetch_all(&db_pool)
+    .await
+    .unwrap();
+    let statuses: Vec<&str> = statuses.iter().map(|(s, _)| s.as_str()).collect();
+    assert_eq!(
+        statuses,
+        [
+            "cancelled",
+            "confirmed",
+            "delivered",
+            "in_transit",
+            "pending"
+        ]
+    );
+
+    // Orders on the road or delivered each have a leg, on a route of one of the vehicles.
+    let without_leg: i64 = sqlx::query_scalar(
+        r#"
+        SELECT count(*) FROM orders o
+        WHERE o.order_status IN ('in_transit', 'delivered') AND NOT EXISTS (
+            SELECT 1 FROM routes leg
+            JOIN vehicle_routes vr ON vr.route_id = leg.main_route_id
+            WHERE leg.destination = 'Order ' || o.id
+        )
+        "#,
+    )
+    .fetch_one(&db_pool)
+    .await
+    .unwrap();
+    assert_eq!(without_leg, 0);
+
+    // Every order has lines, every customer a located address.
+    let empty: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM orders o WHERE NOT EXISTS (SELECT 1 FROM order_items oi WHERE oi.order_id = o.id)",
+    )
+    .fetch_one(&db_pool)
+    .await
+    .unwrap();
+    assert_eq!(empty, 0);
+
+    let not_located: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM customers WHERE latitude IS NULL")
+            .fetch_one(&db_pool)
+            .await
+            .unwrap();
+    assert_eq!(not_located, 0);
+
+    // The routes of the delivered orders are completed, the ones on the road are not.
+    let open_with_delivered: i64 = sqlx::query_scalar(
+        r#"
+        SELECT count(*) FROM vehicle_routes vr
+        JOIN routes leg ON leg.main_route_id = vr.route_id
+        JOIN orders o ON leg.destination = 'Order ' || o.id
+        WHERE (vr.completed_at IS NULL) = (o.order_status = 'delivered')
+        "#,
+    )
+    .fetch_one(&db_pool)
+    .await
+    .unwrap();
+    assert_eq!(open_with_delivered, 0);
+
+    // Delivered orders have their proof of delivery, as if recorded by the driver.
+    let without_proof: i64 = sqlx::query_scalar(
+        r#"
+        SELECT count(*) FROM orders o
+        WHERE o.order_status = 'delivered' AND NOT EXISTS (
+            SELECT 1 FROM proof_of_deliveries pod WHERE pod.order_id = o.id
+        )
+        "#,
+    )
+    .fetch_one(&db_pool)
+    .await
+    .unwrap();
+    assert_eq!(without_proof, 0);
+}
+
+#[sqlx::test]
+async fn the_same_seed_gives_the_same_data(db_pool: PgPool) {
+    for tenant_id in [TENANT_ID, OTHER_TENANT_ID] {
+        generate(db_pool.clone(), TenantId::new(tenant_id), &options(42))
+            .await
+            .unwrap();
+    }
+
+    let first = snapshot(&db_pool, TENANT_ID).await;
+    assert_eq!(first.len(), 8 + 2 + 20);
+    assert_eq!(first, snapshot(&db_pool, OTHER_TENANT_ID).await);
+}
+
+#[sqlx::test]
+async fn another_seed_gives_other_data(db_pool: PgPool) {
+    generate(db_pool.clone(), TenantId::new(TENANT_ID), &options(1))
+        .await
+        .unwrap();
+    generate(db_pool.clone(), TenantId::new(OTHER_TENANT_ID), &options(2))
+        .await
+        .unwrap();
+
+    assert_ne!(
+        snapshot(&db_pool, TENANT_ID).await,
+        snapshot(&db_pool, OTHER_TENANT_ID).await
+    );
+}
+
+#[sqlx::test]
+async fn generates_only_what_is_asked_for(db_pool: PgPool) {
+    let options = DemoDataOptions {
+        vendors: 0,
+        orders: 0,
+        ..options(1)
+    };
+
+    let summary = generate(db_pool, TenantId::new(TENANT_ID), &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        summary,
+        DemoDataSummary {
+            customers: 8,
+            items: 10,
+            ..DemoDataSummary::default()
+        }
+    );
+}
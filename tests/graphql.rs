@@ -0,0 +1,283 @@
+use reqwest::Method;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+impl TestApp {
+    async fn graphql(&self, query: &str) -> Value {
+        let response = self
+            .root(Method::POST, "/v1/graphql")
+            .bearer_auth(self.issuer.token(&[]))
+            .json(&json!({ "query": query }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        response.json().await.unwrap()
+    }
+}
+
+/// An order of two items for `customer_id`, put on `vehicle_id` by an accepted route plan.
+/// Returns the order and the main route.
+async fn deliver_order(app: &TestApp, customer_id: i32, vehicle_id: i32) -> (i32, i32) {
+    let insert = |sql: &'static str| sqlx::query_scalar::<_, i32>(sql);
+
+    let order_id = insert(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, 'pending', $2) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    for (name, quantity) in [("Pallet", 2), ("Crate", 5)] {
+        let item_id = insert(
+            "INSERT INTO items (name, quantity_available, unit_price, tenant_id) VALUES ($1, 100, 12.50, $2) RETURNING id",
+        )
+        .bind(name)
+        .bind(TENANT_ID)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO order_items (order_id, item_id, quantity, unit_price, tenant_id) VALUES ($1, $2, $3, 10.00, $4)",
+        )
+        .bind(order_id)
+        .bind(item_id)
+        .bind(quantity)
+        .bind(TENANT_ID)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let route_id = insert(
+        "INSERT INTO routes (origin, destination, distance, estimated_travel_time, tenant_id) VALUES ('Depot', 'Depot', 12.40, '00:40', $1) RETURNING id",
+    )
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    for destination in [format!("Order {}", order_id), "Depot".to_string()] {
+        sqlx::query(
+            "INSERT INTO routes (main_route_id, origin, destination, distance, estimated_travel_time, tenant_id) VALUES ($1, 'Depot', $2, 6.20, '00:20', $3)",
+        )
+        .bind(route_id)
+        .bind(destination)
+        .bind(TENANT_ID)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    sqlx::query(
+        "INSERT INTO vehicle_routes (vehicle_id, route_id, load, tenant_id) VALUES ($1, $2, 7, $3)",
+    )
+    .bind(vehicle_id)
+    .bind(route_id)
+    .bind(TENANT_ID)
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let plan = json!({
+        "routes": [{ "vehicle_id": vehicle_id, "stops": [{ "order_id": order_id }] }],
+        "unassigned_order_ids": [],
+    });
+    let plan_id = insert(
+        "INSERT INTO route_plans (plan_status, depot_latitude, depot_longitude, departure_at, plan, tenant_id) VALUES ('accepted', -33.87, 151.21, now(), $1, $2) RETURNING id",
+    )
+    .bind(plan)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO route_plan_routes (route_plan_id, route_id, tenant_id) VALUES ($1, $2, $3)",
+    )
+    .bind(plan_id)
+    .bind(route_id)
+    .bind(TENANT_ID)
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    (order_id, route_id)
+}
+
+async fn create_vehicle(app: &TestApp, vendor_id: i32) -> i32 {
+    app.create(
+        &format!("/vendors/{}/vehicles", vendor_id),
+        &json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true }),
+    )
+    .await
+}
+
+#[sqlx::test]
+async fn reads_customers_with_their_orders_items_vehicles_and_routes(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let acme = app.create_customer("Acme", "orders@acme.test").await;
+    let globex = app.create_customer("Globex", "orders@globex.test").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let vehicle_id = create_vehicle(&app, vendor_id).await;
+
+    let (acme_order, route_id) = deliver_order(&app, acme, vehicle_id).await;
+    let (globex_order, _) = deliver_order(&app, globex, vehicle_id).await;
+
+    let response = app
+        .graphql(
+            r#"{
+                customers {
+                    name
+                    address { city }
+                    orders {
+                        id
+                        lines { quantity unitPrice item { name unitPrice } }
+                        assignments {
+                            vehicle { vehicleType vendor { name } }
+                            route { id legs { destination } }
+                        }
+                    }
+                }
+            }"#,
+        )
+        .await;
+    assert!(response.get("errors").is_none(), "{}", response);
+
+    let customers = response["data"]["customers"].as_array().unwrap();
+    assert_eq!(customers.len(), 2);
+    assert_eq!(customers[0]["name"], "Acme");
+    assert_eq!(customers[0]["address"]["city"], "Sydney");
+
+    let order = &customers[0]["orders"][0];
+    assert_eq!(order["id"], acme_order);
+    assert_eq!(
+        order["lines"],
+        json!([
+            { "quantity": 5, "unitPrice": "10.00", "item": { "name": "Crate", "unitPrice": "12.50" } },
+            { "quantity": 2, "unitPrice": "10.00", "item": { "name": "Pallet", "unitPrice": "12.50" } },
+        ])
+    );
+    assert_eq!(
+        order["assignments"][0]["vehicle"],
+        json!({ "vehicleType": "Van", "vendor": { "name": "Haulage Co" } })
+    );
+    assert_eq!(order["assignments"][0]["route"]["id"], route_id);
+    assert_eq!(
+        order["assignments"][0]["route"]["legs"],
+        json!([
+            { "destination": format!("Order {}", acme_order) },
+            { "destination": "Depot" },
+        ])
+    );
+
+    assert_eq!(customers[1]["orders"][0]["id"], globex_order);
+    assert_eq!(
+        customers[1]["orders"][0]["lines"].as_array().unwrap().len(),
+        2
+    );
+}
+
+#[sqlx::test]
+async fn follows_relations_from_vehicles_and_routes(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let vehicle_id = create_vehicle(&app, vendor_id).await;
+    let (_, route_id) = deliver_order(&app, customer_id, vehicle_id).await;
+
+    let response = app
+        .graphql(&format!(
+            r#"{{
+                vendor(id: {vendor_id}) {{ vehicles {{ id routes {{ load completedAt route {{ id }} }} }} }}
+                route(id: {route_id}) {{ distance estimatedTravelTime vehicles {{ vehicle {{ id }} }} }}
+                routes {{ id mainRoute {{ id }} }}
+                order(id: 999999) {{ id }}
+            }}"#,
+        ))
+        .await;
+    assert!(response.get("errors").is_none(), "{}", response);
+
+    let data = &response["data"];
+    assert_eq!(
+        data["vendor"]["vehicles"],
+        json!([{
+            "id": vehicle_id,
+            "routes": [{ "load": "7.00", "completedAt": null, "route": { "id": route_id } }],
+        }])
+    );
+    assert_eq!(data["route"]["distance"], "12.40");
+    assert_eq!(data["route"]["estimatedTravelTime"], "00:40:00");
+    assert_eq!(data["route"]["vehicles"][0]["vehicle"]["id"], vehicle_id);
+    // Only the trips, not their legs.
+    assert_eq!(
+        data["routes"],
+        json!([{ "id": route_id, "mainRoute": null }])
+    );
+    assert_eq!(data["order"], Value::Null);
+}
+
+#[sqlx::test]
+async fn only_reads_the_callers_tenant(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    let mut claims = app.issuer.claims(&[]);
+    claims["tid"] = json!("20000000-0000-0000-0000-00000000000b");
+
+    let response: Value = app
+        .root(Method::POST, "/v1/graphql")
+        .bearer_auth(app.issuer.mint(&claims))
+        .json(
+            &json!({ "query": format!("{{ customers {{ id }} customer(id: {}) {{ id }} }}", id) }),
+        )
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response["data"],
+        json!({ "customers": [], "customer": null })
+    );
+}
+
+#[sqlx::test]
+async fn requires_a_token(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .root(Method::POST, "/v1/graphql")
+        .json(&json!({ "query": "{ customers { id } }" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[sqlx::test]
+async fn refuses_queries_nested_too_deep(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .graphql(
+            "{ customers { orders { customer { orders { customer { orders { customer { orders { customer { orders { id } } } } } } } } } } }",
+        )
+        .await;
+
+    assert!(response["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("nested too deep"));
+}
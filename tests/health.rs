@@ -0,0 +1,81 @@
+use reqwest::Method;
+use serde_json::Value;
+use sqlx::PgPool;
+use url::Url;
+
+mod support;
+
+use support::TestApp;
+
+#[sqlx::test]
+async fn liveness_needs_no_token(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app.root(Method::GET, "/healthz").send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+}
+
+#[sqlx::test]
+async fn ready_with_a_migrated_database_and_published_keys(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app.root(Method::GET, "/readyz").send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["status"], "pass");
+    for check in ["database", "migrations", "jwks"] {
+        assert_eq!(report["checks"][check]["status"], "pass", "{}", check);
+    }
+}
+
+#[sqlx::test]
+async fn not_ready_without_the_signing_keys(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, |config| {
+        // Nothing listens on the discard port.
+        config.auth.jwks_url = Url::parse("http://127.0.0.1:9/keys").unwrap();
+    })
+    .await;
+
+    let response = app.root(Method::GET, "/readyz").send().await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["status"], "fail");
+    assert_eq!(report["checks"]["jwks"]["status"], "fail");
+    assert_eq!(report["checks"]["database"]["status"], "pass");
+}
+
+#[sqlx::test]
+async fn not_ready_with_an_unapplied_migration(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let latest: i64 = sqlx::query_scalar(
+        "DELETE FROM _sqlx_migrations WHERE version = (SELECT max(version) FROM _sqlx_migrations) RETURNING version",
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.root(Method::GET, "/readyz").send().await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    let report: Value = response.json().await.unwrap();
+    let output = report["checks"]["migrations"]["output"].as_str().unwrap();
+    assert!(output.contains(&latest.to_string()), "{}", output);
+    assert!(output.contains("is not applied"), "{}", output);
+}
+
+#[sqlx::test]
+async fn not_ready_once_shutting_down(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    app.shutdown.cancel();
+
+    let response = app.root(Method::GET, "/readyz").send().await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["checks"]["shutdown"]["status"], "fail");
+}
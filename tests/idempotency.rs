@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use sqlx::{Connection, PgConnection, PgPool};
+
+mod support;
+
+use support::{party, TestApp};
+
+async fn customer_count(app: &TestApp) -> usize {
+    let customers: Vec<Value> = app
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    customers.len()
+}
+
+#[sqlx::test]
+async fn a_retried_create_gets_the_first_response(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let body = party("Acme", "orders@acme.test");
+
+    let first = app
+        .post("/customers")
+        .header("idempotency-key", "3f0c5a52-create-acme")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 201);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let location = first.headers()["location"].clone();
+    let created: Value = first.json().await.unwrap();
+
+    let retry = app
+        .post("/customers")
+        .header("idempotency-key", "3f0c5a52-create-acme")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(retry.status(), 201);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(retry.headers()["location"], location);
+    assert_eq!(retry.json::<Value>().await.unwrap(), created);
+
+    assert_eq!(customer_count(&app).await, 1);
+}
+
+#[sqlx::test]
+async fn a_key_cannot_be_used_for_a_different_request(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .post("/vendors")
+        .header("idempotency-key", "create-haulage")
+        .json(&party("Haulage Co", "ops@haulage.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .post("/vendors")
+        .header("idempotency-key", "create-haulage")
+        .json(&party("Freight Co", "ops@freight.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    // Nor for the same body at another endpoint.
+    let response = app
+        .post("/customers")
+        .header("idempotency-key", "create-haulage")
+        .json(&party("Haulage Co", "ops@haulage.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+}
+
+#[sqlx::test]
+async fn keys_belong_to_the_caller(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let mut claims = app.issuer.claims(&[]);
+    claims["oid"] = "00000000-0000-0000-0000-000000000002".into();
+    let token = app.issuer.mint(&claims);
+
+    let response = app
+        .post("/customers")
+        .header("idempotency-key", "create-customer")
+        .json(&party("Acme", "orders@acme.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .anonymous(reqwest::Method::POST, "/customers")
+        .bearer_auth(token)
+        .header("idempotency-key", "create-customer")
+        .json(&party("Globex", "orders@globex.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    assert_eq!(customer_count(&app).await, 2);
+}
+
+#[sqlx::test]
+async fn an_expired_key_is_answered_afresh(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, |config| {
+        config.idempotency.key_ttl = Duration::ZERO;
+    })
+    .await;
+
+    for name in ["Acme", "Globex"] {
+        let response = app
+            .post("/customers")
+            .header("idempotency-key", "create-customer")
+            .json(&party(
+                name,
+                &format!("orders@{}.test", name.to_lowercase()),
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+        assert!(response.headers().get("idempotent-replayed").is_none());
+    }
+
+    assert_eq!(customer_count(&app).await, 2);
+}
+
+#[sqlx::test]
+async fn a_request_the_client_gave_up_on_is_still_answered(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let create = || {
+        app.post("/customers")
+            .header("idempotency-key", "create-acme")
+            .json(&party("Acme", "orders@acme.test"))
+    };
+
+    // Holds up every write to customers, on a connection of its own so the app keeps its pool.
+    let mut connection = PgConnection::connect_with(&app.db_pool.connect_options())
+        .await
+        .unwrap();
+    let mut tx = connection.begin().await.unwrap();
+    sqlx::query("LOCK TABLE customers IN EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    // Gives up only once the key is claimed, a request dropped before then is never answered.
+    let abandoned = tokio::spawn(create().send());
+    let mut claimed = false;
+    for _ in 0..250 {
+        claimed = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM idempotency_keys)")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+        if claimed {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(claimed);
+    abandoned.abort();
+    assert!(abandoned.await.unwrap_err().is_cancelled());
+
+    let retry = create().send().await.unwrap();
+    assert_eq!(retry.status(), 409);
+
+    tx.rollback().await.unwrap();
+
+    let mut retry = create().send().await.unwrap();
+    for _ in 0..50 {
+        if retry.status() != 409 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        retry = create().send().await.unwrap();
+    }
+    assert_eq!(retry.status(), 201);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+
+    assert_eq!(customer_count(&app).await, 1);
+}
+
+#[sqlx::test]
+async fn rejects_an_oversized_key(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .post("/customers")
+        .header("idempotency-key", "k".repeat(256))
+        .json(&party("Acme", "orders@acme.test"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    assert_eq!(customer_count(&app).await, 0);
+}
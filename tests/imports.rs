@@ -0,0 +1,104 @@
+use serde_json::Value;
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+use tsm::domain::aggregates::customer::Customer;
+use tsm::domain::value_objects::{address::Address, tenant_id::TenantId};
+use tsm::infrastructure::repositories::customer_repository::{CustomerRepository, Repository as _};
+
+const HEADER: &str = "name,email,address_line1,city,region,postcode,country_code\n";
+
+async fn import(app: &TestApp, rows: &str) -> reqwest::Response {
+    app.post("/customers/import?mode=commit")
+        .header("content-type", "text/csv")
+        .body(format!("{}{}", HEADER, rows))
+        .send()
+        .await
+        .unwrap()
+}
+
+fn customer(id: i32, email: &str) -> Customer {
+    let address = Address::new(&["1 Main St"], "Sydney", Some("NSW"), "2000", "AU", None).unwrap();
+
+    Customer::new(id, "Acme", email, address, None)
+}
+
+#[sqlx::test]
+async fn importing_again_updates_the_customers_with_the_email(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let globex_id = app.create_customer("Globex", "ops@globex.test").await;
+
+    let response = import(&app, "Acme,orders@acme.test,1 Main St,Sydney,NSW,2000,AU\n").await;
+    assert_eq!(response.status(), 201);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(
+        (&report["created"], &report["updated"]),
+        (&1.into(), &0.into())
+    );
+    let acme_id = report["rows"][0]["id"].clone();
+
+    let response = import(
+        &app,
+        "Acme,ORDERS@acme.test,2 Market St,Sydney,NSW,2000,AU\n\
+         Globex Corp,ops@globex.test,3 George St,Sydney,NSW,2000,AU\n",
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(
+        (&report["created"], &report["updated"]),
+        (&0.into(), &2.into())
+    );
+    assert_eq!(report["rows"][0]["id"], acme_id);
+    assert_eq!(report["rows"][1]["id"], globex_id);
+
+    let customers: Vec<Value> = app
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(customers.len(), 2);
+}
+
+#[sqlx::test]
+async fn imports_only_while_the_email_matches_hold(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let acme_id = app.create_customer("Acme", "orders@acme.test").await;
+    let repo = CustomerRepository::new(app.db_pool.clone(), TenantId::new(TENANT_ID));
+
+    // Matched as new before the customer was saved, in a race with its create.
+    let imported = repo
+        .import_by_email(&[customer(0, "Orders@Acme.test")])
+        .await
+        .unwrap();
+    assert_eq!(imported, None);
+
+    let imported = repo
+        .import_by_email(&[customer(acme_id, "orders@acme.test")])
+        .await
+        .unwrap();
+    assert_eq!(imported, Some(vec![acme_id]));
+
+    // A second customer with the email makes the match ambiguous.
+    app.create_customer("Acme Pty Ltd", "orders@acme.test")
+        .await;
+    let imported = repo
+        .import_by_email(&[customer(acme_id, "orders@acme.test")])
+        .await
+        .unwrap();
+    assert_eq!(imported, None);
+
+    // Nothing was saved by the imports refused.
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM customers ORDER BY id")
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(names, ["Acme", "Acme Pty Ltd"]);
+}
@@ -0,0 +1,183 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+/// A delivered order for three pallets at 10.00 each.
+async fn delivered_order(app: &TestApp, customer_id: i32) -> i32 {
+    let item_id: i32 = sqlx::query_scalar(
+        "INSERT INTO items (name, quantity_available, unit_price, tenant_id) VALUES ('Pallet', 100, 10, $1) RETURNING id",
+    )
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    let order_id: i32 = sqlx::query_scalar(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, 'delivered', $2) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO order_items (order_id, item_id, quantity, unit_price, tenant_id) VALUES ($1, $2, 3, 10, $3)",
+    )
+    .bind(order_id)
+    .bind(item_id)
+    .bind(TENANT_ID)
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    order_id
+}
+
+/// Invoices the customer's delivered orders and issues the invoice.
+async fn issued_invoice(app: &TestApp, customer_id: i32) -> Value {
+    let id = app
+        .create(&format!("/customers/{}/invoices", customer_id), &json!({}))
+        .await;
+
+    let response = app
+        .post(&format!("/invoices/{}/issue", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    response.json().await.unwrap()
+}
+
+async fn credit(app: &TestApp, invoice_id: i64, body: &Value) -> reqwest::Response {
+    app.post(&format!("/invoices/{}/credit-notes", invoice_id))
+        .json(body)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn void(app: &TestApp, invoice_id: i64) -> reqwest::Response {
+    app.post(&format!("/invoices/{}/void", invoice_id))
+        .json(&json!({ "reason": "Billed twice" }))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn credits_no_more_than_was_invoiced(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    delivered_order(&app, customer_id).await;
+
+    let invoice = issued_invoice(&app, customer_id).await;
+    let invoice_id = invoice["id"].as_i64().unwrap();
+    let line_id = &invoice["lines"][0]["id"];
+    assert_eq!(invoice["total"], "30.00");
+
+    let response = credit(
+        &app,
+        invoice_id,
+        &json!({
+            "reason": "One pallet damaged",
+            "lines": [{ "invoiceLineId": line_id, "quantity": 1 }],
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+
+    let credit_note: Value = response.json().await.unwrap();
+    assert_eq!(credit_note["invoiceType"], "credit_note");
+    assert_eq!(credit_note["status"], "issued");
+    assert_eq!(credit_note["creditedInvoiceId"], invoice_id);
+    assert_eq!(credit_note["total"], "-10.00");
+
+    // Two pallets are left, listed twice they would pass a per line check.
+    let response = credit(
+        &app,
+        invoice_id,
+        &json!({
+            "reason": "Split across lines",
+            "lines": [
+                { "invoiceLineId": line_id, "quantity": 2 },
+                { "invoiceLineId": line_id, "quantity": 2 },
+            ],
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    let errors: Value = response.json().await.unwrap();
+    assert_eq!(errors["lines"][0]["code"], "duplicate_line");
+
+    let response = credit(
+        &app,
+        invoice_id,
+        &json!({ "reason": "Too many", "lines": [{ "invoiceLineId": line_id, "quantity": 3 }] }),
+    )
+    .await;
+    assert_eq!(response.status(), 409);
+
+    let response = credit(
+        &app,
+        invoice_id,
+        &json!({ "reason": "Unknown line", "lines": [{ "invoiceLineId": 4040, "quantity": 1 }] }),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    // Everything not credited yet.
+    let response = credit(&app, invoice_id, &json!({ "reason": "Order cancelled" })).await;
+    assert_eq!(response.status(), 201);
+
+    let credit_note: Value = response.json().await.unwrap();
+    assert_eq!(credit_note["lines"][0]["quantity"], 2);
+    assert_eq!(credit_note["total"], "-20.00");
+
+    let response = credit(&app, invoice_id, &json!({ "reason": "Once more" })).await;
+    assert_eq!(response.status(), 409);
+
+    // Credited invoices stay on the books.
+    assert_eq!(void(&app, invoice_id).await.status(), 409);
+}
+
+#[sqlx::test]
+async fn voiding_an_invoice_bills_its_orders_again(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    let order_id = delivered_order(&app, customer_id).await;
+
+    let invoice = issued_invoice(&app, customer_id).await;
+    let invoice_id = invoice["id"].as_i64().unwrap();
+
+    let response = app
+        .post(&format!("/invoices/{}/void", invoice_id))
+        .json(&json!({ "reason": "" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    let response = void(&app, invoice_id).await;
+    assert_eq!(response.status(), 200);
+
+    let voided: Value = response.json().await.unwrap();
+    assert_eq!(voided["status"], "void");
+    assert_eq!(voided["voidReason"], "Billed twice");
+    assert!(voided["voidedAt"].is_string());
+
+    assert_eq!(void(&app, invoice_id).await.status(), 409);
+
+    let response = credit(&app, invoice_id, &json!({ "reason": "Too late" })).await;
+    assert_eq!(response.status(), 409);
+
+    let reissued = issued_invoice(&app, customer_id).await;
+    assert_ne!(reissued["id"], invoice_id);
+    assert_eq!(reissued["lines"][0]["orderId"], order_id);
+    assert_ne!(reissued["invoiceNumber"], invoice["invoiceNumber"]);
+}
@@ -0,0 +1,175 @@
+use reqwest::Method;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+impl TestApp {
+    async fn scrape(&self) -> String {
+        let response = self.root(Method::GET, "/metrics").send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+
+        response.text().await.unwrap()
+    }
+}
+
+/// The value of the sample written exactly as `series`, e.g. `tsm_orders{status="new"}`.
+fn value(metrics: &str, series: &str) -> Option<f64> {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .map(|value| value.parse().unwrap())
+}
+
+#[sqlx::test]
+async fn counts_requests_by_route_template_and_status(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    for _ in 0..2 {
+        app.get(&format!("/customers/{}", id)).send().await.unwrap();
+    }
+    app.get("/customers/999999").send().await.unwrap();
+    app.anonymous(Method::GET, "/customers")
+        .send()
+        .await
+        .unwrap();
+    app.root(Method::GET, "/wp-login.php").send().await.unwrap();
+
+    let metrics = app.scrape().await;
+
+    let requests = |method: &str, route: &str, status: u16| {
+        value(
+            &metrics,
+            &format!(
+                "tsm_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}}",
+                method, route, status
+            ),
+        )
+    };
+    assert_eq!(requests("GET", "/v1/api/customers/:id", 200), Some(2.0));
+    assert_eq!(requests("GET", "/v1/api/customers/:id", 404), Some(1.0));
+    assert_eq!(requests("POST", "/v1/api/customers", 201), Some(1.0));
+    assert_eq!(requests("GET", "/v1/api/customers", 401), Some(1.0));
+    assert_eq!(requests("GET", "unmatched", 404), Some(1.0));
+
+    assert_eq!(
+        value(
+            &metrics,
+            "tsm_http_request_duration_seconds_count{method=\"GET\",route=\"/v1/api/customers/:id\",status=\"200\"}"
+        ),
+        Some(2.0)
+    );
+    assert_eq!(
+        value(
+            &metrics,
+            "tsm_http_request_duration_seconds_bucket{method=\"GET\",route=\"/v1/api/customers/:id\",status=\"200\",le=\"+Inf\"}"
+        ),
+        Some(2.0)
+    );
+    // The scrape itself.
+    assert_eq!(value(&metrics, "tsm_http_requests_in_flight"), Some(1.0));
+}
+
+#[sqlx::test]
+async fn counts_signing_key_fetches(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    app.get("/customers").send().await.unwrap();
+    app.get("/vendors").send().await.unwrap();
+    app.root(Method::GET, "/readyz").send().await.unwrap();
+
+    let metrics = app.scrape().await;
+
+    // Fetched for the first token, then cached for the next one and the readiness check.
+    assert_eq!(
+        value(&metrics, "tsm_jwks_fetches_total{outcome=\"success\"}"),
+        Some(1.0)
+    );
+    assert_eq!(
+        value(&metrics, "tsm_jwks_fetches_total{outcome=\"failure\"}"),
+        Some(0.0)
+    );
+}
+
+#[sqlx::test]
+async fn reports_the_pool_and_business_gauges(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+
+    for available in [true, true, false] {
+        app.create(
+            &format!("/vendors/{}/vehicles", vendor_id),
+            &json!({ "vehicleType": "van", "capacity": "1200", "availabilityStatus": available }),
+        )
+        .await;
+    }
+
+    for status in ["pending", "pending", "delivered"] {
+        sqlx::query(
+            "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, $2, $3)",
+        )
+        .bind(customer_id)
+        .bind(status)
+        .bind(TENANT_ID)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let metrics = app.scrape().await;
+
+    assert_eq!(value(&metrics, "tsm_orders{status=\"pending\"}"), Some(2.0));
+    assert_eq!(
+        value(&metrics, "tsm_orders{status=\"delivered\"}"),
+        Some(1.0)
+    );
+    assert_eq!(
+        value(&metrics, "tsm_orders{status=\"in_transit\"}"),
+        Some(0.0)
+    );
+    assert_eq!(
+        value(&metrics, "tsm_vehicles{availability=\"available\"}"),
+        Some(2.0)
+    );
+    assert_eq!(
+        value(&metrics, "tsm_vehicles{availability=\"unavailable\"}"),
+        Some(1.0)
+    );
+    assert!(value(&metrics, "tsm_db_pool_max_connections").unwrap() >= 1.0);
+    assert!(value(&metrics, "tsm_db_pool_connections{state=\"idle\"}").is_some());
+}
+
+#[sqlx::test]
+async fn asks_for_the_token_when_one_is_configured(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, |config| {
+        config.metrics.token = Some("scrape-secret".to_string());
+    })
+    .await;
+
+    let anonymous = app.root(Method::GET, "/metrics").send().await.unwrap();
+    assert_eq!(anonymous.status(), 401);
+
+    let wrong = app
+        .root(Method::GET, "/metrics")
+        .bearer_auth("guess")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), 401);
+
+    let scraper = app
+        .root(Method::GET, "/metrics")
+        .bearer_auth("scrape-secret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(scraper.status(), 200);
+}
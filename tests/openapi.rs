@@ -0,0 +1,163 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+use tsm::application::routes::openapi;
+
+const ROUTES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/application/routes");
+
+// Served outside `/v1/api`, or the documentation itself.
+const UNDOCUMENTED: &[&str] = &[
+    "index.rs",
+    "forbidden.rs",
+    "health.rs",
+    "metrics.rs",
+    "api_docs.rs",
+    // GraphQL describes itself, by introspection.
+    "graphql.rs",
+];
+
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Every `.route(path, method(..).method(..))` registered by the route modules, as
+/// `METHOD /path/{param}`.
+fn routed_operations() -> BTreeSet<String> {
+    let mut operations = BTreeSet::new();
+
+    for entry in fs::read_dir(ROUTES_DIR).unwrap() {
+        let path = entry.unwrap().path();
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        if UNDOCUMENTED.contains(&file_name) {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path).unwrap();
+        for call in source.split(".route(").skip(1) {
+            let arguments = balanced_arguments(call);
+            let route = arguments.split('"').nth(1).unwrap();
+            let route = route
+                .split('/')
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(param) => format!("{{{}}}", param),
+                    None => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+
+            for method in METHODS {
+                if calls(&arguments, method) {
+                    operations.insert(format!("{} {}", method.to_uppercase(), route));
+                }
+            }
+        }
+    }
+
+    operations
+}
+
+/// The text up to the parenthesis closing the `.route(` call.
+fn balanced_arguments(call: &str) -> String {
+    let mut depth = 1;
+    let end = call
+        .char_indices()
+        .find(|(_, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        })
+        .map(|(i, _)| i)
+        .unwrap();
+
+    call[..end].to_string()
+}
+
+/// Whether `method(` appears as a routing function or method rather than inside a handler name.
+fn calls(arguments: &str, method: &str) -> bool {
+    arguments
+        .match_indices(&format!("{}(", method))
+        .any(|(i, _)| {
+            arguments[..i]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+        })
+}
+
+fn documented_operations() -> BTreeSet<String> {
+    let spec = serde_json::to_value(openapi()).unwrap();
+
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .unwrap()
+                .keys()
+                .filter(|key| METHODS.contains(&key.as_str()))
+                .map(move |method| format!("{} {}", method.to_uppercase(), path))
+        })
+        .collect()
+}
+
+#[test]
+fn every_route_is_documented() {
+    let undocumented: Vec<_> = routed_operations()
+        .difference(&documented_operations())
+        .cloned()
+        .collect();
+
+    assert!(
+        undocumented.is_empty(),
+        "Add a #[utoipa::path] for these routes and list it in the module's ApiDoc: {:#?}",
+        undocumented
+    );
+}
+
+#[test]
+fn every_documented_operation_is_routed() {
+    let unrouted: Vec<_> = documented_operations()
+        .difference(&routed_operations())
+        .cloned()
+        .collect();
+
+    assert!(
+        unrouted.is_empty(),
+        "These documented operations have no route: {:#?}",
+        unrouted
+    );
+}
+
+#[test]
+fn operations_require_the_bearer_token() {
+    let spec = serde_json::to_value(openapi()).unwrap();
+
+    assert_eq!(spec["openapi"], "3.1.0");
+    assert_eq!(
+        spec["components"]["securitySchemes"]["bearer"]["scheme"],
+        "bearer"
+    );
+    assert_eq!(spec["security"][0]["bearer"], serde_json::json!([]));
+}
+
+#[test]
+fn every_schema_reference_resolves() {
+    let spec = serde_json::to_value(openapi()).unwrap();
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+
+    let text = spec.to_string();
+    let dangling: BTreeSet<_> = text
+        .split("\"#/components/schemas/")
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap())
+        .filter(|name| !schemas.contains_key(*name))
+        .collect();
+
+    assert!(
+        dangling.is_empty(),
+        "Derive ToSchema for these types or list them in a path's body: {:?}",
+        dangling
+    );
+}
@@ -0,0 +1,350 @@
+use chrono::{DateTime, Duration, DurationRound, Offset, Utc};
+use chrono_tz::Australia::Sydney;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+async fn create_order(app: &TestApp, status: &str) -> i32 {
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+
+    sqlx::query_scalar(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(status)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+/// Whole seconds from now, so they read back exactly.
+fn from_now(hours: i64) -> DateTime<Utc> {
+    (Utc::now() + Duration::hours(hours))
+        .duration_trunc(Duration::seconds(1))
+        .unwrap()
+}
+
+fn window(earliest: DateTime<Utc>, latest: DateTime<Utc>) -> Value {
+    json!({ "earliest": earliest, "latest": latest })
+}
+
+async fn schedule(app: &TestApp, order_id: i32, body: &Value) -> reqwest::Response {
+    app.put(&format!("/orders/{}/time-windows", order_id))
+        .json(body)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn ids(app: &TestApp, path: &str) -> Vec<i64> {
+    let orders: Vec<Value> = app.get(path).send().await.unwrap().json().await.unwrap();
+    orders.iter().map(|o| o["id"].as_i64().unwrap()).collect()
+}
+
+#[sqlx::test]
+async fn schedules_windows_in_the_order_time_zone(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "pending").await;
+    let (pickup, delivery) = (from_now(2), from_now(6));
+
+    let response = schedule(
+        &app,
+        order_id,
+        &json!({
+            "timeZone": "Australia/Sydney",
+            "pickupWindow": window(pickup, pickup + Duration::hours(2)),
+            "deliveryWindow": window(delivery, delivery + Duration::hours(3)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let order: Value = app
+        .get(&format!("/orders/{}", order_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(order["timeZone"], "Australia/Sydney");
+
+    let earliest =
+        DateTime::parse_from_rfc3339(order["pickupWindow"]["earliest"].as_str().unwrap()).unwrap();
+    assert_eq!(earliest, pickup);
+    assert_eq!(
+        *earliest.offset(),
+        pickup.with_timezone(&Sydney).offset().fix()
+    );
+
+    let latest =
+        DateTime::parse_from_rfc3339(order["deliveryWindow"]["latest"].as_str().unwrap()).unwrap();
+    assert_eq!(latest, delivery + Duration::hours(3));
+
+    // Windows left out are cleared.
+    let response = schedule(&app, order_id, &json!({ "timeZone": "Australia/Sydney" })).await;
+    assert_eq!(response.status(), 200);
+
+    let order: Value = response.json().await.unwrap();
+    assert!(order["pickupWindow"].is_null());
+    assert!(order["deliveryWindow"].is_null());
+}
+
+#[sqlx::test]
+async fn rejects_windows_out_of_order_or_in_the_past(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "confirmed").await;
+    let soon = from_now(1);
+
+    let cases = [
+        (
+            "not_ordered",
+            json!({ "timeZone": "UTC", "deliveryWindow": window(soon, soon) }),
+        ),
+        (
+            "in_the_past",
+            json!({ "timeZone": "UTC", "deliveryWindow": window(from_now(-1), soon) }),
+        ),
+        (
+            "pickup_after_delivery",
+            json!({
+                "timeZone": "UTC",
+                "pickupWindow": window(from_now(3), from_now(4)),
+                "deliveryWindow": window(soon, from_now(5)),
+            }),
+        ),
+    ];
+
+    for (code, body) in cases {
+        let response = schedule(&app, order_id, &body).await;
+        assert_eq!(response.status(), 422, "{}", code);
+
+        let errors: Value = response.json().await.unwrap();
+        assert_eq!(errors["__all__"][0]["code"], code);
+    }
+
+    let response = schedule(
+        &app,
+        order_id,
+        &json!({ "timeZone": "Mars/Olympus_Mons", "deliveryWindow": window(soon, from_now(2)) }),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    let order: Value = app
+        .get(&format!("/orders/{}", order_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(order["timeZone"].is_null());
+}
+
+#[sqlx::test]
+async fn only_orders_still_to_leave_can_be_scheduled(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "in_transit").await;
+    let body = json!({ "timeZone": "UTC", "deliveryWindow": window(from_now(1), from_now(2)) });
+
+    assert_eq!(schedule(&app, order_id, &body).await.status(), 409);
+    assert_eq!(schedule(&app, order_id + 1000, &body).await.status(), 404);
+}
+
+#[sqlx::test]
+async fn lists_orders_due_today_and_late(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    // Windows in the past cannot be scheduled, so they are written directly.
+    let insert =
+        |status: &'static str, pickup_latest: &'static str, delivery_latest: &'static str| {
+            let db_pool = app.db_pool.clone();
+            async move {
+                let customer_id: i32 =
+                    sqlx::query_scalar("SELECT id FROM customers WHERE tenant_id = $1 LIMIT 1")
+                        .bind(TENANT_ID)
+                        .fetch_one(&db_pool)
+                        .await
+                        .unwrap();
+
+                sqlx::query_scalar::<_, i32>(&format!(
+                    r#"
+INSERT INTO orders (customer_id, order_status, time_zone, pickup_earliest, pickup_latest,
+    delivery_earliest, delivery_latest, tenant_id)
+SELECT $1, $2, 'Australia/Sydney', p - interval '1 hour', p, d - interval '1 hour', d, $3
+FROM (SELECT {} AS p, {} AS d) w
+RETURNING id
+                "#,
+                    pickup_latest, delivery_latest
+                ))
+                .bind(customer_id)
+                .bind(status)
+                .bind(TENANT_ID)
+                .fetch_one(&db_pool)
+                .await
+                .unwrap() as i64
+            }
+        };
+
+    app.create_customer("Acme", "orders@acme.test").await;
+
+    let end_of_today = "(date_trunc('day', now() AT TIME ZONE 'Australia/Sydney') \
+        + interval '23 hours 59 minutes') AT TIME ZONE 'Australia/Sydney'";
+    let two_days_ago = "now() - interval '2 days'";
+    let next_week = "now() + interval '7 days'";
+
+    let due_today = insert("confirmed", end_of_today, next_week).await;
+    let pickup_missed = insert("pending", two_days_ago, next_week).await;
+    let delivery_missed = insert("in_transit", two_days_ago, two_days_ago).await;
+    // Collected already, only the delivery window counts.
+    let on_its_way = insert("in_transit", two_days_ago, next_week).await;
+    let delivered = insert("delivered", two_days_ago, two_days_ago).await;
+    let unscheduled = create_order(&app, "pending").await as i64;
+
+    assert_eq!(ids(&app, "/orders?due=today").await, [due_today]);
+    assert_eq!(
+        ids(&app, "/orders?due=late").await,
+        [delivery_missed, pickup_missed]
+    );
+    assert_eq!(
+        ids(&app, "/orders?due=late&status=pending").await,
+        [pickup_missed]
+    );
+    assert_eq!(
+        ids(&app, "/orders").await,
+        [
+            unscheduled,
+            delivered,
+            on_its_way,
+            delivery_missed,
+            pickup_missed,
+            due_today
+        ]
+    );
+
+    let response = app.get("/orders?due=yesterday").send().await.unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn lists_orders_as_csv_or_xlsx(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let scheduled = create_order(&app, "pending").await;
+    create_order(&app, "delivered").await;
+    let pickup = from_now(2);
+
+    let response = schedule(
+        &app,
+        scheduled,
+        &json!({
+            "timeZone": "Australia/Sydney",
+            "pickupWindow": window(pickup, pickup + Duration::hours(2)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let response = app
+        .get("/orders?status=pending&format=csv")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"orders.csv\""
+    );
+
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "id,customer_id,status,created_at,time_zone,pickup_earliest,pickup_latest,delivery_earliest,delivery_latest"
+    );
+    assert_eq!(lines.len(), 2);
+
+    let fields: Vec<&str> = lines[1].split(',').collect();
+    assert_eq!(fields[0], scheduled.to_string());
+    assert_eq!(fields[2], "pending");
+    assert_eq!(fields[4], "Australia/Sydney");
+    assert_eq!(DateTime::parse_from_rfc3339(fields[5]).unwrap(), pickup);
+    assert_eq!(fields[7..], ["", ""]);
+
+    let response = app
+        .get("/orders")
+        .header(
+            "accept",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"orders.xlsx\""
+    );
+    assert!(response.bytes().await.unwrap().starts_with(b"PK\x03\x04"));
+
+    let response = app.get("/orders?format=pdf").send().await.unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn route_plans_wait_for_the_pickup_window(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "confirmed").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    app.create(
+        &format!("/vendors/{}/vehicles", vendor_id),
+        &json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true }),
+    )
+    .await;
+
+    let (pickup, delivery) = (from_now(2), from_now(3));
+    let response = schedule(
+        &app,
+        order_id,
+        &json!({
+            "timeZone": "Australia/Sydney",
+            "pickupWindow": window(pickup, pickup + Duration::hours(1)),
+            "deliveryWindow": window(delivery, delivery + Duration::hours(4)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let response = app
+        .post("/route-plans")
+        .json(&json!({
+            "depot": { "latitude": -33.8688, "longitude": 151.2093 },
+            "departureAt": Utc::now(),
+            "orders": [{
+                "orderId": order_id,
+                "location": { "latitude": -33.8915, "longitude": 151.2767 },
+                "load": 5,
+            }],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let plan: Value = response.json().await.unwrap();
+    assert!(plan["unassignedOrderIds"].as_array().unwrap().is_empty());
+
+    let route = &plan["routes"][0];
+    let departure_at: DateTime<Utc> =
+        serde_json::from_value(route["route"]["departureAt"].clone()).unwrap();
+    assert_eq!(departure_at, pickup);
+
+    // Early at the customer, so the vehicle waits for the stored delivery window to open.
+    let leg_departure: DateTime<Utc> =
+        serde_json::from_value(route["legs"][0]["departureAt"].clone()).unwrap();
+    assert!(leg_departure >= delivery);
+}
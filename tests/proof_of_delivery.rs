@@ -0,0 +1,194 @@
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+const BOUNDARY: &str = "pod-form-boundary";
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\nsignature";
+
+async fn create_order(app: &TestApp, status: &str) -> i32 {
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+
+    sqlx::query_scalar(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(status)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+/// A multipart body with the text fields and, when given, a signature of `content_type`.
+fn form(fields: &[(&str, String)], signature: Option<&str>) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                BOUNDARY, name, value
+            )
+            .as_bytes(),
+        );
+    }
+
+    if let Some(content_type) = signature {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"signature\"; filename=\"signature.png\"\r\nContent-Type: {}\r\n\r\n",
+                BOUNDARY, content_type
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(SIGNATURE);
+        body.extend_from_slice(b"\r\n");
+    }
+
+    body.extend_from_slice(format!("--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+fn fields(delivered_at: chrono::DateTime<Utc>) -> Vec<(&'static str, String)> {
+    vec![
+        ("recipientName", "Jane Citizen".to_string()),
+        ("deliveredAt", delivered_at.to_rfc3339()),
+        ("latitude", "-33.8688".to_string()),
+        ("longitude", "151.2093".to_string()),
+        ("notes", "Left at reception".to_string()),
+    ]
+}
+
+async fn record(app: &TestApp, order_id: i32, body: Vec<u8>) -> reqwest::Response {
+    app.post(&format!("/orders/{}/proof-of-delivery", order_id))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(body)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn order_status(app: &TestApp, order_id: i32) -> String {
+    let order: Value = app
+        .get(&format!("/orders/{}", order_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    order["status"].as_str().unwrap().to_string()
+}
+
+#[sqlx::test]
+async fn records_the_delivery_of_an_order_in_transit(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "in_transit").await;
+
+    let response = record(
+        &app,
+        order_id,
+        form(
+            &fields(Utc::now() - Duration::minutes(5)),
+            Some("image/png"),
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(
+        response.headers()["location"],
+        format!("/v1/api/orders/{}/proof-of-delivery", order_id).as_str()
+    );
+
+    let pod: Value = response.json().await.unwrap();
+    assert_eq!(pod["recipientName"], "Jane Citizen");
+    assert_eq!(pod["notes"], "Left at reception");
+    assert_eq!(pod["signature"]["size"], SIGNATURE.len());
+    assert!(pod["photo"].is_null());
+
+    assert_eq!(order_status(&app, order_id).await, "delivered");
+
+    let signature = app
+        .get(&format!("/orders/{}/proof-of-delivery/signature", order_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(signature.status(), 200);
+    assert_eq!(signature.headers()["content-type"], "image/png");
+    assert_eq!(signature.bytes().await.unwrap().as_ref(), SIGNATURE);
+
+    let photo = app
+        .get(&format!("/orders/{}/proof-of-delivery/photo", order_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(photo.status(), 404);
+}
+
+#[sqlx::test]
+async fn only_orders_in_transit_can_be_delivered(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let pending = create_order(&app, "pending").await;
+    let delivered_at = Utc::now() - Duration::minutes(5);
+
+    let response = record(&app, pending, form(&fields(delivered_at), None)).await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(order_status(&app, pending).await, "pending");
+
+    let in_transit = create_order(&app, "in_transit").await;
+    let response = record(&app, in_transit, form(&fields(delivered_at), None)).await;
+    assert_eq!(response.status(), 201);
+
+    // A second proof for the same order.
+    let response = record(&app, in_transit, form(&fields(delivered_at), None)).await;
+    assert_eq!(response.status(), 409);
+
+    let response = record(&app, in_transit + 1000, form(&fields(delivered_at), None)).await;
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .get(&format!("/orders/{}/proof-of-delivery", pending))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn rejects_an_invalid_proof(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let order_id = create_order(&app, "in_transit").await;
+
+    let response = record(
+        &app,
+        order_id,
+        form(&fields(Utc::now() + Duration::hours(1)), None),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    let errors: Value = response.json().await.unwrap();
+    assert_eq!(errors["delivered_at"][0]["code"], "delivered_at_in_future");
+
+    let response = record(
+        &app,
+        order_id,
+        form(&fields(Utc::now()), Some("application/pdf")),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let mut missing = fields(Utc::now());
+    missing.retain(|(name, _)| *name != "recipientName");
+    let response = record(&app, order_id, form(&missing, None)).await;
+    assert_eq!(response.status(), 400);
+
+    assert_eq!(order_status(&app, order_id).await, "in_transit");
+}
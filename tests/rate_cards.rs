@@ -0,0 +1,124 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+use tsm::domain::aggregates::rate_card::RateCard;
+use tsm::domain::value_objects::tenant_id::TenantId;
+use tsm::infrastructure::repositories::rate_card_repository::{
+    RateCardRepository, Repository as _,
+};
+
+fn rate_card(vehicle_type: &str, effective_from: &str, effective_to: Option<&str>) -> Value {
+    json!({
+        "name": format!("{} rates", vehicle_type),
+        "vehicleType": vehicle_type,
+        "minimumCharge": "50",
+        "effectiveFrom": effective_from,
+        "effectiveTo": effective_to,
+        "rates": [{
+            "minDistance": "0",
+            "minWeight": "0",
+            "baseCharge": "20",
+            "ratePerKm": "1.5",
+            "ratePerKg": "0.1",
+        }],
+    })
+}
+
+#[sqlx::test]
+async fn one_card_per_vehicle_type_is_in_effect_on_any_day(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let path = format!("/vendors/{}/rate-cards", vendor_id);
+
+    app.create(&path, &rate_card("Van", "2024-01-01", Some("2024-06-30")))
+        .await;
+    let truck_id = app
+        .create(&path, &rate_card("Truck", "2024-01-01", None))
+        .await;
+
+    for body in [
+        rate_card("Van", "2024-06-30", None),
+        rate_card("van", "2023-01-01", Some("2024-01-01")),
+    ] {
+        let response = app.post(&path).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 409);
+    }
+
+    let response = app
+        .put(&format!("{}/{}", path, truck_id))
+        .json(&rate_card("Van", "2024-03-01", None))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    app.create(&path, &rate_card("Van", "2024-07-01", None))
+        .await;
+
+    // Saved in a race with the card above, past the handler's check.
+    let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    let repo = RateCardRepository::new(app.db_pool.clone(), TenantId::new(TENANT_ID));
+    let racing = RateCard::new(
+        0,
+        vendor_id,
+        "Racing",
+        "VAN",
+        Decimal::ZERO,
+        date("2025-01-01"),
+        None,
+    );
+    assert_eq!(repo.create(&racing).await.unwrap(), None);
+
+    let cards: Vec<Value> = app.get(&path).send().await.unwrap().json().await.unwrap();
+    assert_eq!(cards.len(), 3);
+}
+
+#[sqlx::test]
+async fn quotes_are_priced_within_bounds(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    app.create(
+        &format!("/vendors/{}/vehicles", vendor_id),
+        &json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true }),
+    )
+    .await;
+    app.create(
+        &format!("/vendors/{}/rate-cards", vendor_id),
+        &rate_card("Van", "2024-01-01", None),
+    )
+    .await;
+
+    let response = app
+        .post("/quotes")
+        .json(&json!({ "distance": "100", "weight": "10" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let quotes: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0]["amount"], "171.00");
+
+    for (field, body) in [
+        (
+            "distance",
+            json!({ "distance": "100000000000000000000", "weight": "10" }),
+        ),
+        (
+            "weight",
+            json!({ "distance": "100", "weight": "79228162514264337593543950335" }),
+        ),
+    ] {
+        let response = app.post("/quotes").json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 422, "{}", field);
+
+        let errors: Value = response.json().await.unwrap();
+        assert_eq!(errors[field][0]["code"], "too_large");
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use reqwest::{Method, Response};
+use sqlx::PgPool;
+
+use tsm::application::config::{Config, CounterStore};
+
+mod support;
+
+use support::TestApp;
+
+/// Three requests per hour, so a test never straddles two windows.
+fn three_per_hour(config: &mut Config) {
+    config.rate_limit.requests = 3;
+    config.rate_limit.window = Duration::from_secs(3600);
+}
+
+async fn list_customers(app: &TestApp, times: usize) -> Vec<Response> {
+    let mut responses = Vec::new();
+    for _ in 0..times {
+        responses.push(app.get("/customers").send().await.unwrap());
+    }
+    responses
+}
+
+#[sqlx::test]
+async fn turns_away_a_principal_over_its_limit(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, three_per_hour).await;
+
+    let responses = list_customers(&app, 4).await;
+
+    for response in &responses[..3] {
+        assert_eq!(response.status(), 200);
+    }
+    assert_eq!(responses[3].status(), 429);
+    let retry_after: u64 = responses[3].headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=3600).contains(&retry_after), "{}", retry_after);
+
+    // Someone else still gets through.
+    let mut claims = app.issuer.claims(&[]);
+    claims["oid"] = "00000000-0000-0000-0000-000000000002".into();
+    let response = app
+        .anonymous(Method::GET, "/customers")
+        .bearer_auth(app.issuer.mint(&claims))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[sqlx::test]
+async fn counts_applications_by_their_client_id(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, three_per_hour).await;
+    let list = |client_id: &str| {
+        app.anonymous(Method::GET, "/customers")
+            .bearer_auth(app.issuer.mint(&app.issuer.app_claims(client_id, &[])))
+            .send()
+    };
+
+    let mut statuses = Vec::new();
+    for _ in 0..4 {
+        statuses.push(list("tsm-integration").await.unwrap().status().as_u16());
+    }
+    assert_eq!(statuses, [200, 200, 200, 429]);
+
+    assert_eq!(list("tsm-reporting").await.unwrap().status(), 200);
+}
+
+#[sqlx::test]
+async fn roles_get_their_own_limit(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, |config| {
+        three_per_hour(config);
+        config.rate_limit.roles = [("Integration".to_string(), 5)].into();
+    })
+    .await;
+    let integration = app.signed_in_with(&["Integration"]);
+
+    let responses = list_customers(&integration, 6).await;
+
+    let statuses: Vec<_> = responses.iter().map(|r| r.status().as_u16()).collect();
+    assert_eq!(statuses, [200, 200, 200, 200, 200, 429]);
+}
+
+#[sqlx::test]
+async fn requests_without_a_valid_token_are_not_counted(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, three_per_hour).await;
+
+    for _ in 0..5 {
+        let response = app
+            .anonymous(Method::GET, "/customers")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+    }
+
+    let responses = list_customers(&app, 1).await;
+    assert_eq!(responses[0].status(), 200);
+}
+
+#[sqlx::test]
+async fn instances_share_the_counters_kept_in_postgres(db_pool: PgPool) {
+    let shared = |config: &mut Config| {
+        three_per_hour(config);
+        config.rate_limit.store = CounterStore::Postgres;
+    };
+    let first = TestApp::spawn_with(db_pool.clone(), shared).await;
+    let second = TestApp::spawn_with(db_pool, shared).await;
+
+    list_customers(&first, 2).await;
+    let responses = list_customers(&second, 2).await;
+
+    assert_eq!(responses[0].status(), 200);
+    assert_eq!(responses[1].status(), 429);
+
+    let windows: i64 = sqlx::query_scalar("SELECT count(*) FROM rate_limit_windows")
+        .fetch_one(&first.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(windows, 1);
+}
@@ -0,0 +1,75 @@
+use reqwest::Method;
+use sqlx::PgPool;
+
+mod support;
+
+use support::TestApp;
+
+#[sqlx::test]
+async fn gives_every_response_a_request_id(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let first = app.root(Method::GET, "/healthz").send().await.unwrap();
+    let second = app
+        .anonymous(Method::GET, "/customers")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(second.status(), 401);
+
+    let first = first.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let second = second.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(first.len(), 36, "{}", first);
+    assert_ne!(first, second);
+}
+
+#[sqlx::test]
+async fn keeps_the_request_id_the_client_sent(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let response = app
+        .get("/customers")
+        .header("x-request-id", "frontend-4f1c")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-request-id"], "frontend-4f1c");
+}
+
+#[sqlx::test]
+async fn browsers_may_send_and_read_the_request_id(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let preflight = app
+        .anonymous(Method::OPTIONS, "/customers")
+        .header("origin", "http://localhost:5173")
+        .header("access-control-request-method", "GET")
+        .header("access-control-request-headers", "x-request-id")
+        .send()
+        .await
+        .unwrap();
+    let allowed = preflight.headers()["access-control-allow-headers"]
+        .to_str()
+        .unwrap();
+    assert!(allowed.contains("x-request-id"), "{}", allowed);
+
+    let response = app
+        .get("/customers")
+        .header("origin", "http://localhost:5173")
+        .send()
+        .await
+        .unwrap();
+    let exposed = response.headers()["access-control-expose-headers"]
+        .to_str()
+        .unwrap();
+    assert!(exposed.contains("x-request-id"), "{}", exposed);
+}
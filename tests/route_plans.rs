@@ -0,0 +1,178 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+use tsm::domain::value_objects::tenant_id::TenantId;
+use tsm::infrastructure::repositories::route_plan_repository::{
+    Repository as _, RoutePlanRepository,
+};
+
+async fn confirmed_order(app: &TestApp, customer_id: i32) -> i32 {
+    sqlx::query_scalar(
+        "INSERT INTO orders (customer_id, order_status, tenant_id) VALUES ($1, 'confirmed', $2) RETURNING id",
+    )
+    .bind(customer_id)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+async fn create_vehicle(app: &TestApp, vendor_id: i32) -> i32 {
+    app.create(
+        &format!("/vendors/{}/vehicles", vendor_id),
+        &json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true }),
+    )
+    .await
+}
+
+/// Drafts a plan delivering each order around the Sydney CBD, returns its id.
+async fn draft_plan(app: &TestApp, order_ids: &[i32]) -> i32 {
+    let orders: Vec<Value> = order_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            json!({
+                "orderId": id,
+                "location": { "latitude": -33.87 - 0.01 * i as f64, "longitude": 151.21 },
+                "load": 5,
+            })
+        })
+        .collect();
+
+    app.create(
+        "/route-plans",
+        &json!({
+            "depot": { "latitude": -33.8688, "longitude": 151.2093 },
+            "departureAt": Utc::now(),
+            "orders": orders,
+        }),
+    )
+    .await
+}
+
+async fn accept(app: &TestApp, plan_id: i32) -> reqwest::Response {
+    app.post(&format!("/route-plans/{}/accept", plan_id))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn count(app: &TestApp, sql: &str) -> i64 {
+    sqlx::query_scalar(sql)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn orders_and_vehicles_are_taken_by_one_plan_only(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let vehicle_id = create_vehicle(&app, vendor_id).await;
+    let orders = [
+        confirmed_order(&app, customer_id).await,
+        confirmed_order(&app, customer_id).await,
+    ];
+
+    let first = draft_plan(&app, &orders).await;
+    let second = draft_plan(&app, &orders).await;
+
+    let response = accept(&app, first).await;
+    assert_eq!(response.status(), 200);
+
+    let statuses: Vec<String> =
+        sqlx::query_scalar("SELECT order_status FROM orders WHERE id = ANY($1)")
+            .bind(&orders[..])
+            .fetch_all(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(statuses, ["assigned", "assigned"]);
+
+    let vehicle: Value = app
+        .get(&format!("/vendors/{}/vehicles/{}", vendor_id, vehicle_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(vehicle["availabilityStatus"], false);
+
+    let response = accept(&app, second).await;
+    assert_eq!(response.status(), 409);
+
+    // Accepting in a race with the first plan, past the handler's checks.
+    let repo = RoutePlanRepository::new(app.db_pool.clone(), TenantId::new(TENANT_ID));
+    let mut route_plan = repo.by_id(second).await.unwrap().unwrap();
+    route_plan.accept();
+    assert!(!repo.accept(&route_plan).await.unwrap());
+
+    assert_eq!(count(&app, "SELECT count(*) FROM vehicle_routes").await, 1);
+    assert_eq!(
+        count(
+            &app,
+            "SELECT count(*) FROM routes WHERE main_route_id IS NULL"
+        )
+        .await,
+        1
+    );
+
+    let plan: Value = app
+        .get(&format!("/route-plans/{}", second))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(plan["status"], "draft");
+}
+
+#[sqlx::test]
+async fn completing_the_route_puts_the_vehicle_back_in_service(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let customer_id = app.create_customer("Acme", "orders@acme.test").await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let vehicle_id = create_vehicle(&app, vendor_id).await;
+    let order_id = confirmed_order(&app, customer_id).await;
+
+    let plan_id = draft_plan(&app, &[order_id]).await;
+    let plan: Value = accept(&app, plan_id).await.json().await.unwrap();
+    let route_id = plan["routes"][0]["route"]["id"].as_i64().unwrap();
+
+    // With its only vehicle on the road, nothing is left to plan the next order with.
+    let next_order = confirmed_order(&app, customer_id).await;
+    let response = app
+        .post("/route-plans")
+        .json(&json!({
+            "depot": { "latitude": -33.8688, "longitude": 151.2093 },
+            "departureAt": Utc::now(),
+            "orders": [{
+                "orderId": next_order,
+                "location": { "latitude": -33.88, "longitude": 151.21 },
+                "load": 5,
+            }],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    let response = app
+        .post(&format!(
+            "/vehicles/{}/routes/{}/complete",
+            vehicle_id, route_id
+        ))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    draft_plan(&app, &[next_order]).await;
+}
@@ -0,0 +1,257 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+struct Fleet {
+    vendor_id: i32,
+    van_id: i32,
+    truck_id: i32,
+}
+
+/// A vendor with a van priced by a rate card and a truck without one.
+async fn fleet(app: &TestApp) -> Fleet {
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+
+    let vehicles = format!("/vendors/{}/vehicles", vendor_id);
+    let mut vehicle = json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true });
+    let van_id = app.create(&vehicles, &vehicle).await;
+    vehicle["vehicleType"] = json!("Truck");
+    let truck_id = app.create(&vehicles, &vehicle).await;
+
+    app.create(
+        &format!("/vendors/{}/rate-cards", vendor_id),
+        &json!({
+            "name": "Van rates",
+            "vehicleType": "Van",
+            "minimumCharge": "50",
+            "effectiveFrom": "2024-01-01",
+            "rates": [{
+                "minDistance": "0",
+                "minWeight": "0",
+                "baseCharge": "20",
+                "ratePerKm": "1.5",
+                "ratePerKg": "0.1",
+            }],
+        }),
+    )
+    .await;
+
+    Fleet {
+        vendor_id,
+        van_id,
+        truck_id,
+    }
+}
+
+/// Gives the vehicle a route of `distance` km and completes it through the API.
+async fn completed_route(
+    app: &TestApp,
+    vehicle_id: i32,
+    distance: &str,
+    load: &str,
+    completed_at: &str,
+) -> i32 {
+    let route_id: i32 = sqlx::query_scalar(
+        "INSERT INTO routes (origin, destination, distance, estimated_travel_time, tenant_id) VALUES ('Depot', 'Customer', $1::numeric, '01:00', $2) RETURNING id",
+    )
+    .bind(distance)
+    .bind(TENANT_ID)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    sqlx::query("INSERT INTO vehicle_routes (vehicle_id, route_id, tenant_id) VALUES ($1, $2, $3)")
+        .bind(vehicle_id)
+        .bind(route_id)
+        .bind(TENANT_ID)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+    let response = app
+        .post(&format!(
+            "/vehicles/{}/routes/{}/complete",
+            vehicle_id, route_id
+        ))
+        .json(&json!({ "completedAt": completed_at, "load": load }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    route_id
+}
+
+async fn draft(app: &TestApp, vendor_id: i32) -> reqwest::Response {
+    app.post(&format!("/vendors/{}/settlements", vendor_id))
+        .json(&json!({ "periodStart": "2024-03-01", "periodEnd": "2024-03-31" }))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn payables(app: &TestApp) -> String {
+    let today = Utc::now().date_naive();
+    let response = app
+        .get(&format!("/payables/export?from={}&to={}", today, today))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+
+    response.text().await.unwrap()
+}
+
+#[sqlx::test]
+async fn drafts_a_settlement_at_the_agreed_rates(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let fleet = fleet(&app).await;
+
+    let long = completed_route(&app, fleet.van_id, "100", "10", "2024-03-05T10:00:00Z").await;
+    let short = completed_route(&app, fleet.van_id, "10", "0", "2024-03-06T10:00:00Z").await;
+    let unpriced = completed_route(&app, fleet.truck_id, "50", "5", "2024-03-07T10:00:00Z").await;
+    completed_route(&app, fleet.van_id, "100", "10", "2024-04-01T10:00:00Z").await;
+
+    let response = draft(&app, fleet.vendor_id).await;
+    assert_eq!(response.status(), 201);
+
+    // The settlement, alongside the routes left out.
+    let settlement: Value = response.json().await.unwrap();
+    assert_eq!(settlement["status"], "draft");
+    assert_eq!(settlement["total"], "221.00");
+
+    let lines = settlement["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["routeId"], long);
+    assert_eq!(lines[0]["amount"], "171.00");
+    assert_eq!(lines[0]["minimumChargeApplied"], false);
+    assert_eq!(lines[1]["routeId"], short);
+    assert_eq!(lines[1]["amount"], "50.00");
+    assert_eq!(lines[1]["minimumChargeApplied"], true);
+
+    let unpriced_routes = settlement["unpricedRoutes"].as_array().unwrap();
+    assert_eq!(unpriced_routes.len(), 1);
+    assert_eq!(unpriced_routes[0]["routeId"], unpriced);
+    assert_eq!(unpriced_routes[0]["vehicleType"], "Truck");
+
+    // The priced routes are taken, the truck's route still cannot be priced.
+    let response = draft(&app, fleet.vendor_id).await;
+    assert_eq!(response.status(), 422);
+
+    let settlement: Value = response.json().await.unwrap();
+    assert!(settlement["lines"].as_array().unwrap().is_empty());
+    assert_eq!(settlement["unpricedRoutes"][0]["routeId"], unpriced);
+
+    let response = app
+        .post(&format!("/vendors/{}/settlements", fleet.vendor_id))
+        .json(&json!({ "periodStart": "2024-03-31", "periodEnd": "2024-03-01" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    let response = draft(&app, fleet.vendor_id + 1000).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn approved_settlements_are_exported_as_payables(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let fleet = fleet(&app).await;
+    completed_route(&app, fleet.van_id, "100", "10", "2024-03-05T10:00:00Z").await;
+
+    assert_eq!(
+        payables(&app).await,
+        "settlement_id,vendor_id,vendor_name,vendor_email,period_start,period_end,approved_at,approved_by,route_count,amount\n"
+    );
+
+    let settlement: Value = draft(&app, fleet.vendor_id).await.json().await.unwrap();
+    let id = settlement["id"].as_i64().unwrap();
+
+    let response = app
+        .post(&format!("/settlements/{}/approve", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let settlement: Value = response.json().await.unwrap();
+    assert_eq!(settlement["status"], "approved");
+    assert_eq!(settlement["approvedBy"], "test.user@example.com");
+    assert!(settlement["approvedAt"].is_string());
+
+    let response = app
+        .post(&format!("/settlements/{}/approve", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    let response = app
+        .post(&format!("/settlements/{}/reject", id))
+        .json(&json!({ "reason": "Too late" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    let csv = payables(&app).await;
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows.len(), 2);
+
+    let row: Vec<&str> = rows[1].split(',').collect();
+    assert_eq!(row[0], id.to_string());
+    assert_eq!(row[2], "Haulage Co");
+    assert_eq!(row[4..6], ["2024-03-01", "2024-03-31"]);
+    assert_eq!(row[7..], ["test.user@example.com", "1", "171.00"]);
+}
+
+#[sqlx::test]
+async fn rejected_settlements_give_their_routes_back(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let fleet = fleet(&app).await;
+    let route_id = completed_route(&app, fleet.van_id, "100", "10", "2024-03-05T10:00:00Z").await;
+
+    let settlement: Value = draft(&app, fleet.vendor_id).await.json().await.unwrap();
+    let id = settlement["id"].as_i64().unwrap();
+
+    let reject = |reason: &'static str| {
+        app.post(&format!("/settlements/{}/reject", id))
+            .json(&json!({ "reason": reason }))
+            .send()
+    };
+
+    assert_eq!(reject("").await.unwrap().status(), 422);
+
+    let response = reject("Wrong rate card").await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let settlement: Value = response.json().await.unwrap();
+    assert_eq!(settlement["status"], "rejected");
+    assert_eq!(settlement["rejectionReason"], "Wrong rate card");
+
+    let response = app
+        .post(&format!("/settlements/{}/approve", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    let response = draft(&app, fleet.vendor_id).await;
+    assert_eq!(response.status(), 201);
+
+    let redraft: Value = response.json().await.unwrap();
+    assert_ne!(redraft["id"], id);
+    assert_eq!(redraft["lines"][0]["routeId"], route_id);
+
+    // Neither the rejected settlement nor the new draft is payable.
+    assert_eq!(payables(&app).await.lines().count(), 1);
+}
@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde_json::{json, Value};
+use url::Url;
+
+use tsm::application::auth::Authority;
+
+const KEY_ID: &str = "tsm-test-key";
+const AUDIENCE: &str = "api://tsm-test";
+
+// A throwaway key pair, the certificate is self-signed and only ever published by the stub.
+const PRIVATE_KEY: &str = include_str!("issuer_key.pem");
+const CERTIFICATE: &str = include_str!("issuer_cert.pem");
+
+/// Publishes a signing key the way Entra ID does and mints access tokens signed with it.
+pub struct Issuer {
+    pub authority: Authority,
+    encoding_key: EncodingKey,
+    key_id: Mutex<String>,
+    jwks: Arc<Mutex<Value>>,
+}
+
+/// The JWKS document publishing the certificate under `key_id`.
+fn jwks(key_id: &str) -> Value {
+    // The `x5c` entry is the base64 DER of the certificate, its PEM body on one line.
+    let x5c: String = CERTIFICATE
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+
+    json!({
+        "keys": [{ "kty": "RSA", "use": "sig", "kid": key_id, "x5c": [x5c] }]
+    })
+}
+
+impl Issuer {
+    pub async fn start() -> Self {
+        let jwks = Arc::new(Mutex::new(jwks(KEY_ID)));
+        let jwks_url = super::serve_json("/discovery/v2.0/keys", jwks.clone()).await;
+        let issuer = jwks_url
+            .trim_end_matches("/discovery/v2.0/keys")
+            .to_string();
+
+        Self {
+            authority: Authority {
+                jwks_url: Url::parse(&jwks_url).unwrap(),
+                issuer,
+                audience: AUDIENCE.to_string(),
+            },
+            encoding_key: EncodingKey::from_rsa_pem(PRIVATE_KEY.as_bytes()).unwrap(),
+            key_id: Mutex::new(KEY_ID.to_string()),
+            jwks,
+        }
+    }
+
+    /// Replaces the published key with one under `key_id` and signs with it from now on, as
+    /// Entra ID does when it rolls its keys over.
+    pub fn rotate(&self, key_id: &str) {
+        *self.jwks.lock().unwrap() = jwks(key_id);
+        *self.key_id.lock().unwrap() = key_id.to_string();
+    }
+
+    /// The claims of a valid access token for the test user, to adjust before `mint`.
+    pub fn claims(&self, roles: &[&str]) -> Value {
+        json!({
+            "iss": self.authority.issuer,
+            "aud": self.authority.audience,
+            "exp": (Utc::now() + Duration::hours(1)).timestamp(),
+            "oid": "00000000-0000-0000-0000-000000000001",
+            "sub": "test-subject",
+            "tid": super::TENANT_ID,
+            "name": "Test User",
+            "preferred_username": "test.user@example.com",
+            "roles": roles,
+        })
+    }
+
+    /// The claims of a valid access token an application got for itself, with no user claims.
+    pub fn app_claims(&self, client_id: &str, roles: &[&str]) -> Value {
+        let mut claims = self.claims(roles);
+        let user = claims.as_object_mut().unwrap();
+        for claim in ["oid", "name", "preferred_username"] {
+            user.remove(claim);
+        }
+        user.insert("azp".to_string(), client_id.into());
+        user.insert("sub".to_string(), client_id.into());
+
+        claims
+    }
+
+    /// Signs `claims` with the published key.
+    pub fn mint(&self, claims: &Value) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(self.key_id.lock().unwrap().clone());
+
+        encode(&header, claims, &self.encoding_key).unwrap()
+    }
+
+    /// A valid access token for the test user holding `roles`.
+    pub fn token(&self, roles: &[&str]) -> String {
+        self.mint(&self.claims(roles))
+    }
+}
@@ -0,0 +1,257 @@
+//! Runs the API against the database of a `#[sqlx::test]`, with a local token issuer in place of
+//! Entra ID and a geocoder that never leaves the process.
+//!
+//! ```ignore
+//! #[sqlx::test]
+//! async fn lists_customers(db_pool: PgPool) {
+//!     let app = TestApp::spawn(db_pool).await;
+//!     let response = app.get("/customers").send().await.unwrap();
+//! }
+//! ```
+
+// Every test crate compiles this module, each using only part of it.
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use axum::{async_trait, routing::get, Json, Router};
+use reqwest::{Client, Method, RequestBuilder};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use tsm::application::auth::SigningKeys;
+use tsm::application::config::Config;
+use tsm::application::metrics::Metrics;
+use tsm::application::rate_limit::RateLimiter;
+use tsm::application::routes::create_app;
+use tsm::application::utils::app_state::AppState;
+use tsm::domain::services::geocoder::Geocoder;
+use tsm::domain::value_objects::{address::Address, coordinates::Coordinates};
+
+mod issuer;
+
+pub use issuer::Issuer;
+
+/// The tenant of the test user, whose rows the tests work with.
+pub const TENANT_ID: &str = "10000000-0000-0000-0000-00000000000a";
+
+pub struct TestApp {
+    pub db_pool: PgPool,
+    pub issuer: Arc<Issuer>,
+    /// Fails readiness when cancelled, the server itself keeps running.
+    pub shutdown: CancellationToken,
+    base_url: String,
+    client: Client,
+    token: String,
+}
+
+impl TestApp {
+    /// Serves the app on a free local port, signed in as a user without roles.
+    pub async fn spawn(db_pool: PgPool) -> Self {
+        Self::spawn_with(db_pool, |_| {}).await
+    }
+
+    /// Serves the app with the test configuration changed by `configure`.
+    pub async fn spawn_with(db_pool: PgPool, configure: impl FnOnce(&mut Config)) -> Self {
+        let issuer = Arc::new(Issuer::start().await);
+        let shutdown = CancellationToken::new();
+
+        let mut config = config(&issuer);
+        configure(&mut config);
+
+        let app = create_app(AppState {
+            db_pool: db_pool.clone(),
+            geocoder: Arc::new(PostcodeGeocoder),
+            rate_limiter: Arc::new(RateLimiter::new(db_pool.clone(), config.rate_limit.clone())),
+            config: Arc::new(config),
+            metrics: Arc::new(Metrics::default()),
+            signing_keys: Arc::new(SigningKeys::default()),
+            shutdown: shutdown.clone(),
+        });
+
+        let base_url = serve(app).await;
+        let token = issuer.token(&[]);
+
+        Self {
+            db_pool,
+            issuer,
+            shutdown,
+            base_url,
+            client: Client::new(),
+            token,
+        }
+    }
+
+    /// The same app, with requests signed in as a user holding `roles`.
+    pub fn signed_in_with(&self, roles: &[&str]) -> Self {
+        Self {
+            db_pool: self.db_pool.clone(),
+            issuer: self.issuer.clone(),
+            shutdown: self.shutdown.clone(),
+            base_url: self.base_url.clone(),
+            client: self.client.clone(),
+            token: self.issuer.token(roles),
+        }
+    }
+
+    /// The same app, with requests signed in as a user of another tenant.
+    pub fn in_tenant(&self, tenant_id: &str) -> Self {
+        let mut claims = self.issuer.claims(&[]);
+        claims["tid"] = json!(tenant_id);
+
+        Self {
+            token: self.issuer.mint(&claims),
+            ..self.signed_in_with(&[])
+        }
+    }
+
+    /// An absolute URL for a path under `/v1/api`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/v1/api{}", self.base_url, path)
+    }
+
+    /// A request without credentials for a path outside `/v1/api`.
+    pub fn root(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.base_url, path))
+    }
+
+    /// A request without credentials.
+    pub fn anonymous(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client.request(method, self.url(path))
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.anonymous(method, path).bearer_auth(&self.token)
+    }
+
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, path)
+    }
+
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PUT, path)
+    }
+
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+
+    /// Creates a customer and returns its id.
+    pub async fn create_customer(&self, name: &str, email: &str) -> i32 {
+        self.create("/customers", &party(name, email)).await
+    }
+
+    /// Creates a vendor and returns its id.
+    pub async fn create_vendor(&self, name: &str, email: &str) -> i32 {
+        self.create("/vendors", &party(name, email)).await
+    }
+
+    /// Polls the customer or vendor at `path` until the geocoder has located it, and returns it.
+    pub async fn located(&self, path: &str) -> Value {
+        for _ in 0..50 {
+            let party: Value = self.get(path).send().await.unwrap().json().await.unwrap();
+            if party["address"]["latitude"].is_number() {
+                return party;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        panic!("{} was not located", path);
+    }
+
+    /// Posts `body` to a create endpoint and returns the id of the new resource.
+    pub async fn create(&self, path: &str, body: &Value) -> i32 {
+        let response = self.post(path).json(body).send().await.unwrap();
+        assert_eq!(response.status(), 201, "POST {}", path);
+
+        let created: Value = response.json().await.unwrap();
+        created["id"].as_i64().unwrap() as i32
+    }
+}
+
+/// A create request for a customer or vendor in Sydney.
+pub fn party(name: &str, email: &str) -> Value {
+    json!({
+        "name": name,
+        "email": email,
+        "address": address("1 Martin Place", "2000"),
+        "contactNumber": "+61 2 9000 0000",
+    })
+}
+
+pub fn address(line1: &str, postcode: &str) -> Value {
+    json!({
+        "lines": [line1],
+        "city": "Sydney",
+        "region": "NSW",
+        "postcode": postcode,
+        "countryCode": "AU",
+    })
+}
+
+/// The test profile, trusting the tokens of `issuer`.
+pub fn config(issuer: &Issuer) -> Config {
+    let authority = &issuer.authority;
+
+    Config::from_sources(
+        |name| match name {
+            "TSM_PROFILE" => Some("test".to_string()),
+            // The pool comes from `#[sqlx::test]`, this is never connected to.
+            "DATABASE_URL" => Some("postgres://localhost/tsm_test".to_string()),
+            "JWKS_URL" => Some(authority.jwks_url.to_string()),
+            "TOKEN_ISSUER" => Some(authority.issuer.clone()),
+            "AUDIENCE" => Some(authority.audience.clone()),
+            _ => None,
+        },
+        None,
+    )
+    .unwrap()
+}
+
+async fn serve(app: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    format!("http://{}", address)
+}
+
+/// Places every address a little south of Sydney's CBD by its postcode, so nearby postcodes are
+/// nearby points and the same address always lands on the same point.
+pub struct PostcodeGeocoder;
+
+#[async_trait]
+impl Geocoder for PostcodeGeocoder {
+    fn name(&self) -> &'static str {
+        "postcode-stub"
+    }
+
+    async fn geocode(&self, address: &Address) -> anyhow::Result<Option<Coordinates>> {
+        let Ok(postcode) = address.postcode.parse::<u32>() else {
+            return Ok(None);
+        };
+
+        let offset = f64::from(postcode % 1000) / 100.0;
+
+        Ok(Some(Coordinates::new(-33.87 - offset, 151.21)))
+    }
+}
+
+/// Serves the current `value` as JSON at `path` on a free local port and returns the URL.
+async fn serve_json(path: &'static str, value: Arc<Mutex<Value>>) -> String {
+    let app = Router::new().route(
+        path,
+        get(move || async move { Json(value.lock().unwrap().clone()) }),
+    );
+
+    format!("{}{}", serve(app).await, path)
+}
@@ -0,0 +1,171 @@
+use serde_json::{json, Value};
+use sqlx::{PgPool, Row};
+
+use tsm::domain::value_objects::tenant_id::TenantId;
+use tsm::infrastructure::tenancy;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+
+const OTHER_TENANT_ID: &str = "20000000-0000-0000-0000-00000000000b";
+
+#[sqlx::test]
+async fn tenants_only_see_their_own_customers(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let id = app.create_customer("Acme", "orders@acme.test").await;
+
+    let other = app.in_tenant(OTHER_TENANT_ID);
+
+    let customers: Value = other
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(customers, json!([]));
+
+    let response = other
+        .get(&format!("/customers/{}", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // The same email is free in another tenant.
+    other.create_customer("Acme", "orders@acme.test").await;
+
+    let customers: Vec<Value> = app
+        .get("/customers")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(customers.len(), 1);
+    assert_eq!(customers[0]["id"], id);
+}
+
+#[sqlx::test]
+async fn tenants_cannot_work_with_the_vendors_of_another(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+
+    let other = app.in_tenant(OTHER_TENANT_ID);
+
+    let response = other
+        .post(&format!("/vendors/{}/vehicles", vendor_id))
+        .json(&json!({ "vehicleType": "van", "capacity": "1200", "availabilityStatus": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = other
+        .get(&format!("/vendors/{}/contacts", vendor_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn row_level_security_hides_other_tenants_rows(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool.clone()).await;
+    app.create_customer("Acme", "orders@acme.test").await;
+    app.in_tenant(OTHER_TENANT_ID)
+        .create_customer("Globex", "orders@globex.test")
+        .await;
+
+    // The tests connect as a superuser, which skips the policies. Roles are shared by the
+    // databases of concurrent tests.
+    sqlx::query(
+        r#"
+DO $$
+BEGIN
+    CREATE ROLE tsm_tenancy_test NOLOGIN;
+EXCEPTION WHEN duplicate_object OR unique_violation THEN NULL;
+END
+$$
+        "#,
+    )
+    .execute(&db_pool)
+    .await
+    .unwrap();
+    sqlx::query("GRANT SELECT ON customers TO tsm_tenancy_test")
+        .execute(&db_pool)
+        .await
+        .unwrap();
+
+    let visible = |tenant_id: &'static str, all_tenants: &'static str| {
+        let db_pool = db_pool.clone();
+        async move {
+            let mut tx = db_pool.begin().await.unwrap();
+            sqlx::query("SET LOCAL ROLE tsm_tenancy_test")
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+            sqlx::query(
+                "SELECT set_config('app.tenant_id', $1, true), set_config('app.all_tenants', $2, true)",
+            )
+            .bind(tenant_id)
+            .bind(all_tenants)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+            let names: Vec<String> = sqlx::query("SELECT name FROM customers ORDER BY name")
+                .map(|row: sqlx::postgres::PgRow| row.get("name"))
+                .fetch_all(&mut *tx)
+                .await
+                .unwrap();
+
+            tx.rollback().await.unwrap();
+            names
+        }
+    };
+
+    assert_eq!(visible(TENANT_ID, "off").await, ["Acme"]);
+    assert_eq!(visible(OTHER_TENANT_ID, "off").await, ["Globex"]);
+    assert!(visible("", "off").await.is_empty());
+    assert_eq!(visible("", "on").await, ["Acme", "Globex"]);
+}
+
+#[sqlx::test]
+async fn pooled_connections_take_the_scope_of_the_caller(db_pool: PgPool) {
+    let pool = tenancy::pool_options()
+        .max_connections(1)
+        .connect_with((*db_pool.connect_options()).clone())
+        .await
+        .unwrap();
+
+    let settings = || async {
+        let row = sqlx::query(
+            "SELECT current_setting('app.tenant_id') AS tenant_id, current_setting('app.all_tenants') AS all_tenants",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        (
+            row.get::<String, _>("tenant_id"),
+            row.get::<String, _>("all_tenants"),
+        )
+    };
+
+    let tenant = TenantId::new(TENANT_ID);
+
+    // One connection, handed to each scope in turn.
+    assert_eq!(
+        tenancy::in_tenant(tenant, settings()).await,
+        (TENANT_ID.to_string(), "off".to_string())
+    );
+    assert_eq!(
+        tenancy::across_tenants(settings()).await,
+        (String::new(), "on".to_string())
+    );
+    assert_eq!(settings().await, (String::new(), "off".to_string()));
+}
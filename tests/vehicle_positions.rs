@@ -0,0 +1,196 @@
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+
+mod support;
+
+use support::{TestApp, TENANT_ID};
+use tsm::application::jobs::vehicle_position_retention;
+
+async fn vehicle(app: &TestApp) -> (i32, i32) {
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+    let vehicle_id = app
+        .create(
+            &format!("/vendors/{}/vehicles", vendor_id),
+            &json!({ "vehicleType": "Van", "capacity": 20, "availabilityStatus": true }),
+        )
+        .await;
+
+    (vendor_id, vehicle_id)
+}
+
+/// A fix taken `minutes_ago`, to the second like a GPS unit reports it.
+fn fix(minutes_ago: i64, latitude: f64) -> Value {
+    let recorded_at = (Utc::now() - Duration::minutes(minutes_ago))
+        .duration_trunc(Duration::seconds(1))
+        .unwrap();
+
+    json!({ "recordedAt": recorded_at, "latitude": latitude, "longitude": 151.2, "speed": 40 })
+}
+
+async fn latest(app: &TestApp, query: &str) -> Vec<Value> {
+    app.get(&format!("/vehicles/positions/latest{}", query))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+fn timestamp(value: &Value) -> DateTime<Utc> {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+#[sqlx::test]
+async fn stores_each_fix_once_and_keeps_the_latest(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let (vendor_id, vehicle_id) = vehicle(&app).await;
+    let positions = format!("/vehicles/{}/positions", vehicle_id);
+
+    let first = fix(60, -33.80);
+    let response = app.post(&positions).json(&first).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report, json!({ "received": 1, "stored": 1 }));
+
+    // A device re-sending its buffer after losing signal.
+    let newer = fix(30, -33.85);
+    let response = app
+        .post(&format!("{}/batch", positions))
+        .json(&json!({ "positions": [first, newer] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report, json!({ "received": 2, "stored": 1 }));
+
+    // Arriving late, it is kept in the history but does not move the vehicle back.
+    let response = app
+        .post(&positions)
+        .json(&fix(90, -33.70))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    let stored: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM vehicle_positions WHERE vehicle_id = $1")
+            .bind(vehicle_id)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, 3);
+
+    let positions = latest(&app, "").await;
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0]["vehicleId"], vehicle_id);
+    assert_eq!(positions[0]["vendorId"], vendor_id);
+    assert_eq!(positions[0]["latitude"], -33.85);
+    assert_eq!(
+        timestamp(&positions[0]["recordedAt"]),
+        timestamp(&newer["recordedAt"])
+    );
+
+    assert_eq!(
+        latest(&app, &format!("?vendorId={}", vendor_id))
+            .await
+            .len(),
+        1
+    );
+    assert!(latest(&app, &format!("?vendorId={}", vendor_id + 1000))
+        .await
+        .is_empty());
+}
+
+#[sqlx::test]
+async fn rejects_fixes_outside_the_retention_window(db_pool: PgPool) {
+    let app = TestApp::spawn_with(db_pool, |config| {
+        config.vehicle_positions.retention_days = 7;
+    })
+    .await;
+    let (_, vehicle_id) = vehicle(&app).await;
+    let positions = format!("/vehicles/{}/positions", vehicle_id);
+
+    for body in [fix(8 * 24 * 60, -33.80), fix(-10, -33.80)] {
+        let response = app.post(&positions).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 422, "{}", body["recordedAt"]);
+    }
+
+    // One fix out of the window fails the whole batch.
+    let response = app
+        .post(&format!("{}/batch", positions))
+        .json(&json!({ "positions": [fix(5, -33.80), fix(-10, -33.81)] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    let response = app
+        .post(&format!("/vehicles/{}/positions", vehicle_id + 1000))
+        .json(&fix(5, -33.80))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    assert!(latest(&app, "").await.is_empty());
+}
+
+#[sqlx::test]
+async fn retention_drops_the_partitions_past_the_window(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let (_, vehicle_id) = vehicle(&app).await;
+
+    for days_ago in [0, 3] {
+        sqlx::query(
+            "INSERT INTO vehicle_positions (vehicle_id, recorded_at, latitude, longitude, tenant_id) VALUES ($1, now() - make_interval(days => $2), -33.8, 151.2, $3)",
+        )
+        .bind(vehicle_id)
+        .bind(days_ago)
+        .bind(TENANT_ID)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let partition = |days_ago: i64| {
+        let day = Utc::now().date_naive() - Duration::days(days_ago);
+        format!("vehicle_positions_{}", day.format("%Y%m%d"))
+    };
+    let exists = |name: String| {
+        sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+            .bind(name)
+            .fetch_one(&app.db_pool)
+    };
+    assert!(exists(partition(3)).await.unwrap());
+
+    // The first run starts straight away.
+    let shutdown = CancellationToken::new();
+    let job = vehicle_position_retention::spawn(app.db_pool.clone(), 1, shutdown.clone());
+
+    let mut dropped = false;
+    for _ in 0..50 {
+        if !exists(partition(3)).await.unwrap() {
+            dropped = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    shutdown.cancel();
+    job.await.unwrap();
+    assert!(dropped);
+
+    assert!(exists(partition(0)).await.unwrap());
+    assert!(exists(partition(-2)).await.unwrap());
+
+    let left: i64 = sqlx::query_scalar("SELECT count(*) FROM vehicle_positions")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(left, 1);
+}
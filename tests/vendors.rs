@@ -0,0 +1,115 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod support;
+
+use support::TestApp;
+
+#[sqlx::test]
+async fn quotes_the_rate_cards_of_vendors(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+    let vendor_id = app.create_vendor("Haulage Co", "ops@haulage.test").await;
+
+    app.create(
+        &format!("/vendors/{}/vehicles", vendor_id),
+        &json!({ "vehicleType": "van", "capacity": "1200", "availabilityStatus": true }),
+    )
+    .await;
+
+    let rate_card_id = app
+        .create(
+            &format!("/vendors/{}/rate-cards", vendor_id),
+            &json!({
+                "name": "Vans 2024",
+                "vehicleType": "van",
+                "minimumCharge": "50",
+                "effectiveFrom": "2024-01-01",
+                "rates": [{
+                    "minDistance": "0",
+                    "minWeight": "0",
+                    "baseCharge": "10",
+                    "ratePerKm": "1.5",
+                    "ratePerKg": "0.1",
+                }],
+            }),
+        )
+        .await;
+
+    let response = app
+        .post("/quotes")
+        .json(&json!({ "distance": "120", "weight": "200", "shipDate": "2024-06-01" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let quotes: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0]["rateCardId"], rate_card_id);
+    assert_eq!(quotes[0]["vendorId"], vendor_id);
+    assert_eq!(quotes[0]["amount"], "210.00");
+    assert_eq!(quotes[0]["minimumChargeApplied"], false);
+}
+
+#[sqlx::test]
+async fn imports_vendors_after_a_dry_run(db_pool: PgPool) {
+    let app = TestApp::spawn(db_pool).await;
+
+    let file = "name,email,address_line1,city,region,postcode,country_code\n\
+                Haulage Co,ops@haulage.test,1 Dock Road,Sydney,NSW,2000,AU\n\
+                No Address,none@haulage.test,,Sydney,NSW,2000,AU\n";
+
+    let dry_run: Value = app
+        .post("/vendors/import?mode=dry-run")
+        .header("content-type", "text/csv")
+        .body(file)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dry_run["rows"][0]["action"], "create");
+    assert_eq!(dry_run["rows"][1]["action"], "reject");
+
+    let vendors: Vec<Value> = app
+        .get("/vendors")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(vendors.is_empty());
+
+    let response = app
+        .post("/vendors/import?mode=commit")
+        .header("content-type", "text/csv")
+        .body(file)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let report: Value = response.json().await.unwrap();
+    let errors = app
+        .get(&format!("/imports/{}/errors", report["importId"]))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(errors.contains("none@haulage.test"));
+
+    let vendors: Vec<Value> = app
+        .get("/vendors")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(vendors.len(), 1);
+    assert_eq!(vendors[0]["name"], "Haulage Co");
+}